@@ -1,13 +1,104 @@
 //! This module contains the code for analyzing the input Rust code and extracting the necessary information from it.
 
+pub mod cfg;
+pub mod const_;
 pub mod crate_;
+pub mod docstring;
 pub mod enum_;
+pub mod extern_;
 pub mod function;
+pub mod impl_;
+pub mod links;
+pub mod macro_;
 pub mod module;
+pub mod query;
+pub mod recursive;
 pub mod struct_;
+pub mod trait_;
 pub mod type_;
+pub mod type_alias;
 
-pub use self::crate_::analyze_crate;
+pub use self::crate_::{
+    analyze_crate, analyze_crates, AnalysisOptions, CargoNotFoundError, DocUrlResolver,
+    MetadataTimeoutError, PathRewriter, StrictModeError,
+};
+
+use crate::data_model::{Attribute, SerdeAttributes, Stability, TypeSegment, TypeSignature};
+
+/// The marker appended to truncated text
+pub const ELLIPSIS: &str = "…";
+
+/// Truncate text to at most ``limit`` characters, ending with an ellipsis if it was truncated.
+///
+/// Truncation is performed on character boundaries, so multi-byte UTF-8 characters are never split.
+/// A ``limit`` of zero leaves no room for the ellipsis, so the text is dropped entirely.
+pub fn truncate(text: &str, limit: usize) -> String {
+    if limit == 0 {
+        return String::new();
+    }
+    if text.chars().nth(limit).is_none() {
+        return text.to_string();
+    }
+    let keep = limit - 1;
+    let end = text
+        .char_indices()
+        .nth(keep)
+        .map(|(index, _)| index)
+        .unwrap_or(text.len());
+    format!("{}{}", &text[..end], ELLIPSIS)
+}
+
+/// Truncate a type signature to at most ``limit`` characters, ending with an ellipsis if it was truncated.
+///
+/// A path that is cut is kept as plain text, since its prefix may not name (or link to) any item.
+pub fn truncate_signature(signature: &TypeSignature, limit: usize) -> TypeSignature {
+    let length: usize = signature.iter().map(|s| s.content().chars().count()).sum();
+    if length <= limit {
+        return signature.clone();
+    }
+    let mut budget = limit.saturating_sub(1);
+    let mut output = vec![];
+    for segment in signature {
+        let count = segment.content().chars().count();
+        if count <= budget {
+            output.push(segment.clone());
+            budget -= count;
+            continue;
+        }
+        if budget > 0 {
+            let text: String = segment.content().chars().take(budget).collect();
+            output.push(TypeSegment::String(text));
+        }
+        output.push(TypeSegment::String(ELLIPSIS.to_string()));
+        break;
+    }
+    type_::merge_strings(output)
+}
+
+/// Create a stable, URL-safe identifier for an item, from its kind and fully qualified name,
+/// e.g. ``struct.my_crate.Foo`` for the struct ``my_crate::Foo``.
+///
+/// The kind distinguishes items of the same name in different namespaces,
+/// e.g. the module ``my_crate::foo`` and the function ``my_crate::foo``.
+/// Segments are joined with ``.``, and any character of a segment other than an ASCII letter, digit or ``_``
+/// is encoded as its UTF-8 bytes in hex, each surrounded by ``-``, so that distinct names give distinct ids.
+pub fn anchor_id(kind: &str, segments: &[String]) -> String {
+    let mut id = kind.to_string();
+    for segment in segments {
+        id.push('.');
+        for c in segment.chars() {
+            if c.is_ascii_alphanumeric() || c == '_' {
+                id.push(c);
+            } else {
+                let mut bytes = [0; 4];
+                for byte in c.encode_utf8(&mut bytes).bytes() {
+                    id.push_str(&format!("-{:02x}-", byte));
+                }
+            }
+        }
+    }
+    id
+}
 
 /// Extracts the docstring from an object's attributes
 ///
@@ -47,6 +138,216 @@ pub(super) fn docstring_from_attrs(attrs: &[syn::Attribute]) -> String {
         .join("\n")
 }
 
+/// Extracts the search aliases from an object's ``#[doc(alias = "...")]``
+/// and ``#[doc(alias("...", "..."))]`` attributes, in the order they are declared
+pub(super) fn doc_aliases_from_attrs(attrs: &[syn::Attribute]) -> Vec<String> {
+    let mut aliases = vec![];
+    for attr in attrs {
+        if !attr.path().is_ident("doc") || !matches!(attr.meta, syn::Meta::List(_)) {
+            continue;
+        }
+        let _ = attr.parse_nested_meta(|meta| {
+            let is_alias = meta.path.is_ident("alias");
+            if meta.input.peek(syn::Token![=]) {
+                let value = meta.value()?;
+                if is_alias {
+                    aliases.push(value.parse::<syn::LitStr>()?.value());
+                } else {
+                    value.parse::<syn::Expr>()?;
+                }
+            } else if meta.input.peek(syn::token::Paren) {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                if is_alias {
+                    let values = content.parse_terminated(
+                        <syn::LitStr as syn::parse::Parse>::parse,
+                        syn::Token![,],
+                    )?;
+                    aliases.extend(values.iter().map(|v| v.value()));
+                } else {
+                    content.parse::<proc_macro2::TokenStream>()?;
+                }
+            }
+            Ok(())
+        });
+    }
+    aliases
+}
+
+/// Extracts the API stability from an object's ``#[stable(...)]`` or ``#[unstable(...)]`` attribute,
+/// as used by the standard library and other crates built with ``#![feature(staged_api)]``.
+///
+/// An ``issue = "none"`` is treated as no tracking issue, and other keys, such as ``implied_by``, are ignored.
+pub(super) fn stability_from_attrs(attrs: &[syn::Attribute]) -> Option<Stability> {
+    let attr = attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("stable") || attr.path().is_ident("unstable"))?;
+    let mut values = std::collections::BTreeMap::new();
+    if matches!(attr.meta, syn::Meta::List(_)) {
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.input.peek(syn::Token![=]) {
+                let value = meta.value()?.parse::<syn::Expr>()?;
+                if let syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(value),
+                    ..
+                }) = value
+                {
+                    values.insert(type_::render_tokens(&meta.path), value.value());
+                }
+            }
+            Ok(())
+        });
+    }
+    let mut value = |key: &str| values.remove(key);
+    Some(if attr.path().is_ident("stable") {
+        Stability::Stable {
+            feature: value("feature"),
+            since: value("since"),
+        }
+    } else {
+        Stability::Unstable {
+            feature: value("feature"),
+            issue: value("issue").filter(|issue| issue != "none"),
+            reason: value("reason"),
+        }
+    })
+}
+
+/// Extracts the arguments of an object's ``#[serde(...)]`` attributes, in the order they are declared,
+/// or ``None`` if it has none.
+///
+/// Arguments that cannot be parsed are ignored.
+pub(super) fn serde_from_attrs(attrs: &[syn::Attribute]) -> Option<SerdeAttributes> {
+    type Arguments = syn::punctuated::Punctuated<syn::Meta, syn::Token![,]>;
+    let mut serde: Option<SerdeAttributes> = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        let serde = serde.get_or_insert_with(Default::default);
+        let Ok(arguments) = attr.parse_args_with(Arguments::parse_terminated) else {
+            continue;
+        };
+        for argument in arguments {
+            serde.arguments.push(type_::render_tokens(&argument));
+            let key = type_::render_tokens(argument.path());
+            match (key.as_str(), &argument) {
+                ("rename", syn::Meta::List(list)) => {
+                    for inner in list
+                        .parse_args_with(Arguments::parse_terminated)
+                        .ok()
+                        .into_iter()
+                        .flatten()
+                    {
+                        match (
+                            type_::render_tokens(inner.path()).as_str(),
+                            meta_str(&inner),
+                        ) {
+                            ("serialize", Some(value)) => serde.rename = Some(value),
+                            ("deserialize", Some(value)) => serde.aliases.push(value),
+                            _ => {}
+                        }
+                    }
+                }
+                ("rename", _) => serde.rename = meta_str(&argument),
+                ("alias", _) => serde.aliases.extend(meta_str(&argument)),
+                ("skip", _) => {
+                    serde.skip_serializing = true;
+                    serde.skip_deserializing = true;
+                }
+                ("skip_serializing", _) => serde.skip_serializing = true,
+                ("skip_deserializing", _) => serde.skip_deserializing = true,
+                ("skip_serializing_if", _) => serde.skip_serializing_if = meta_str(&argument),
+                ("default", syn::Meta::Path(_)) => {
+                    serde.default = Some("Default::default".to_string())
+                }
+                ("default", _) => serde.default = meta_str(&argument),
+                ("flatten", _) => serde.flatten = true,
+                _ => {}
+            }
+        }
+    }
+    serde
+}
+
+/// The string value of a ``key = "value"`` argument of an attribute
+fn meta_str(meta: &syn::Meta) -> Option<String> {
+    match meta {
+        syn::Meta::NameValue(syn::MetaNameValue {
+            value:
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(value),
+                    ..
+                }),
+            ..
+        }) => Some(value.value()),
+        _ => None,
+    }
+}
+
+/// The names of the attributes that are built into the compiler
+const BUILTIN_ATTRIBUTES: &[&str] = &[
+    "allow",
+    "automatically_derived",
+    "cfg",
+    "cfg_attr",
+    "cold",
+    "deny",
+    "deprecated",
+    "derive",
+    "doc",
+    "expect",
+    "export_name",
+    "forbid",
+    "global_allocator",
+    "ignore",
+    "inline",
+    "link",
+    "link_name",
+    "link_section",
+    "macro_export",
+    "macro_use",
+    "must_use",
+    "naked",
+    "no_mangle",
+    "non_exhaustive",
+    "panic_handler",
+    "path",
+    "proc_macro",
+    "proc_macro_attribute",
+    "proc_macro_derive",
+    "repr",
+    "should_panic",
+    "target_feature",
+    "test",
+    "track_caller",
+    "used",
+    "warn",
+];
+
+/// The namespaces of attributes that are used by tools, rather than being attribute macros
+const TOOL_NAMESPACES: &[&str] = &["clippy", "diagnostic", "rustdoc", "rustfmt"];
+
+/// Extracts the attributes of an object, other than ``doc`` attributes, in the order they are declared
+pub(super) fn attributes_from_attrs(attrs: &[syn::Attribute]) -> Vec<Attribute> {
+    attrs
+        .iter()
+        .filter(|attr| !attr.path().is_ident("doc"))
+        .map(|attr| {
+            let path = type_::render_tokens(attr.path());
+            let builtin = match attr.path().segments.first() {
+                Some(first) if attr.path().segments.len() > 1 => {
+                    TOOL_NAMESPACES.contains(&first.ident.to_string().as_str())
+                }
+                Some(first) => BUILTIN_ATTRIBUTES.contains(&first.ident.to_string().as_str()),
+                None => false,
+            };
+            Attribute {
+                path,
+                rendered: format!("#[{}]", type_::render_tokens(&attr.meta)),
+                builtin,
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -61,4 +362,182 @@ mod tests {
         let result = docstring_from_attrs(&attrs);
         assert_eq!(result, "This is a docstring\nAnother docstring");
     }
+
+    #[test]
+    fn test_stability_from_attrs() {
+        let attrs: Vec<syn::Attribute> = vec![
+            syn::parse_quote! { #[doc = "This is a docstring"] },
+            syn::parse_quote! { #[stable(feature = "rust1", since = "1.0.0")] },
+        ];
+        assert_eq!(
+            stability_from_attrs(&attrs),
+            Some(Stability::Stable {
+                feature: Some("rust1".to_string()),
+                since: Some("1.0.0".to_string()),
+            })
+        );
+        let attrs: Vec<syn::Attribute> = vec![syn::parse_quote! {
+            #[unstable(feature = "allocator_api", issue = "32838", reason = "the API may change", soft)]
+        }];
+        assert_eq!(
+            stability_from_attrs(&attrs),
+            Some(Stability::Unstable {
+                feature: Some("allocator_api".to_string()),
+                issue: Some("32838".to_string()),
+                reason: Some("the API may change".to_string()),
+            })
+        );
+        let attrs: Vec<syn::Attribute> = vec![syn::parse_quote! {
+            #[unstable(feature = "internal", issue = "none")]
+        }];
+        assert_eq!(
+            stability_from_attrs(&attrs),
+            Some(Stability::Unstable {
+                feature: Some("internal".to_string()),
+                issue: None,
+                reason: None,
+            })
+        );
+        let attrs: Vec<syn::Attribute> = vec![syn::parse_quote! { #[derive(Debug)] }];
+        assert_eq!(stability_from_attrs(&attrs), None);
+    }
+
+    #[test]
+    fn test_serde_from_attrs() {
+        let attrs: Vec<syn::Attribute> = vec![
+            syn::parse_quote! { #[doc = "This is a docstring"] },
+            syn::parse_quote! { #[serde(rename = "type", alias = "kind", default)] },
+            syn::parse_quote! { #[serde(skip_serializing_if = "Option::is_none", with = "hex")] },
+        ];
+        assert_eq!(
+            serde_from_attrs(&attrs),
+            Some(SerdeAttributes {
+                rename: Some("type".to_string()),
+                aliases: vec!["kind".to_string()],
+                skip_serializing_if: Some("Option::is_none".to_string()),
+                default: Some("Default::default".to_string()),
+                arguments: vec![
+                    "rename = \"type\"".to_string(),
+                    "alias = \"kind\"".to_string(),
+                    "default".to_string(),
+                    "skip_serializing_if = \"Option::is_none\"".to_string(),
+                    "with = \"hex\"".to_string(),
+                ],
+                ..Default::default()
+            })
+        );
+        let attrs: Vec<syn::Attribute> = vec![
+            syn::parse_quote! { #[serde(rename(serialize = "out", deserialize = "in"), skip, flatten)] },
+        ];
+        let serde = serde_from_attrs(&attrs).unwrap();
+        assert_eq!(
+            (
+                serde.rename.as_deref(),
+                serde.aliases,
+                serde.skip_serializing,
+                serde.skip_deserializing,
+                serde.flatten
+            ),
+            (Some("out"), vec!["in".to_string()], true, true, true)
+        );
+        let attrs: Vec<syn::Attribute> = vec![syn::parse_quote! { #[derive(Serialize)] }];
+        assert_eq!(serde_from_attrs(&attrs), None);
+    }
+
+    #[test]
+    fn test_doc_aliases_from_attrs() {
+        let attrs: Vec<syn::Attribute> = vec![
+            syn::parse_quote! { #[doc = "This is a docstring"] },
+            syn::parse_quote! { #[doc(alias = "first")] },
+            syn::parse_quote! { #[doc(hidden, cfg(unix), alias("second", "third"))] },
+            syn::parse_quote! { #[other(alias = "ignored")] },
+        ];
+        assert_eq!(
+            doc_aliases_from_attrs(&attrs),
+            vec!["first", "second", "third"]
+        );
+    }
+
+    #[test]
+    fn test_attributes_from_attrs() {
+        let attrs: Vec<syn::Attribute> = vec![
+            syn::parse_quote! { #[doc = "This is a docstring"] },
+            syn::parse_quote! { #[derive(Debug, Clone)] },
+            syn::parse_quote! { #[tokio::main(flavor = "current_thread")] },
+            syn::parse_quote! { #[clippy::allow(dead_code)] },
+            syn::parse_quote! { #[wasm_bindgen] },
+        ];
+        assert_eq!(
+            attributes_from_attrs(&attrs)
+                .iter()
+                .map(|a| (a.path.as_str(), a.rendered.as_str(), a.builtin))
+                .collect::<Vec<_>>(),
+            vec![
+                ("derive", "#[derive(Debug, Clone)]", true),
+                (
+                    "tokio::main",
+                    "#[tokio::main(flavor = \"current_thread\")]",
+                    false
+                ),
+                ("clippy::allow", "#[clippy::allow(dead_code)]", true),
+                ("wasm_bindgen", "#[wasm_bindgen]", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_anchor_id() {
+        let path = |segments: &[&str]| segments.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            anchor_id("struct", &path(&["my_crate", "Foo"])),
+            "struct.my_crate.Foo"
+        );
+        assert_eq!(
+            anchor_id("method", &path(&["my_crate", "[T]", "len"])),
+            "method.my_crate.-5b-T-5d-.len"
+        );
+        assert_eq!(
+            anchor_id("fn", &path(&["crate", "héllo"])),
+            "fn.crate.h-c3--a9-llo"
+        );
+        assert_ne!(
+            anchor_id("mod", &path(&["my_crate", "foo"])),
+            anchor_id("fn", &path(&["my_crate", "foo"]))
+        );
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(truncate("hello", 5), "hello");
+        assert_eq!(truncate("hello!", 5), "hell…");
+        // multi-byte characters at the truncation boundary
+        assert_eq!(truncate("héllo wörld", 3), "hé…");
+        assert_eq!(truncate("ab😀cd", 4), "ab😀…");
+        assert_eq!(truncate("ab😀cd", 3), "ab…");
+        assert_eq!(truncate("日本語のテキスト", 4), "日本語…");
+        assert_eq!(truncate("日本語", 1), "…");
+        assert_eq!(truncate("日本語", 0), "");
+        assert_eq!(truncate("", 0), "");
+    }
+
+    #[test]
+    fn test_truncate_signature() {
+        let signature = vec![
+            TypeSegment::String("fn é(a: ".to_string()),
+            TypeSegment::Path("Vec<ü8>".to_string()),
+            TypeSegment::String(") -> bool".to_string()),
+        ];
+        assert_eq!(truncate_signature(&signature, 100).len(), 3);
+        let truncated = truncate_signature(&signature, 12);
+        assert_eq!(
+            truncated.iter().map(|s| s.content()).collect::<Vec<_>>(),
+            vec!["fn é(a: Vec…"]
+        );
+        let truncated = truncate_signature(&signature, 20);
+        assert_eq!(
+            truncated.iter().map(|s| s.content()).collect::<Vec<_>>(),
+            vec!["fn é(a: ", "Vec<ü8>", ") ->…"]
+        );
+        assert!(matches!(truncated[1], TypeSegment::Path(_)));
+    }
 }
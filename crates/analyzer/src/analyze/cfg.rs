@@ -0,0 +1,459 @@
+//! Evaluate conditional compilation (``cfg``) predicates
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+use super::type_::render_tokens;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// A ``cfg`` predicate, e.g. ``unix`` or ``feature = "std"``
+pub enum CfgPredicate {
+    /// A configuration option name, e.g. ``unix``
+    Name(String),
+    /// A configuration option key and value, e.g. ``feature = "std"`` or ``target_os = "linux"``
+    KeyValue(String, String),
+    /// The negation of a predicate, e.g. ``not(feature = "std")``
+    Not(Box<CfgPredicate>),
+    /// Whether all the predicates are satisfied, e.g. ``all(unix, target_arch = "x86_64")``
+    All(Vec<CfgPredicate>),
+    /// Whether any of the predicates are satisfied, e.g. ``any(unix, windows)``
+    Any(Vec<CfgPredicate>),
+    /// A predicate that cannot be evaluated, as written
+    Other(String),
+}
+
+impl CfgPredicate {
+    /// Extract the predicate from the AST
+    pub fn parse(meta: &syn::Meta) -> Self {
+        match meta {
+            syn::Meta::Path(path) => match path.get_ident() {
+                Some(ident) => Self::Name(ident.to_string()),
+                None => Self::Other(render_tokens(path)),
+            },
+            syn::Meta::NameValue(name_value) => {
+                match (name_value.path.get_ident(), &name_value.value) {
+                    (
+                        Some(ident),
+                        syn::Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Str(value),
+                            ..
+                        }),
+                    ) => Self::KeyValue(ident.to_string(), value.value()),
+                    _ => Self::Other(render_tokens(name_value)),
+                }
+            }
+            syn::Meta::List(list) if list.path.is_ident("not") => match list.parse_args() {
+                Ok(inner) => Self::Not(Box::new(Self::parse(&inner))),
+                Err(_) => Self::Other(render_tokens(list)),
+            },
+            syn::Meta::List(list) if list.path.is_ident("all") || list.path.is_ident("any") => {
+                let nested = match list.parse_args_with(
+                    syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated,
+                ) {
+                    Ok(nested) => nested.iter().map(Self::parse).collect(),
+                    Err(_) => return Self::Other(render_tokens(list)),
+                };
+                if list.path.is_ident("all") {
+                    Self::All(nested)
+                } else {
+                    Self::Any(nested)
+                }
+            }
+            syn::Meta::List(list) => Self::Other(render_tokens(list)),
+        }
+    }
+    /// Parse a rendered predicate, e.g. ``feature = "std"``, or ``None`` if it is not valid syntax
+    pub fn parse_str(text: &str) -> Option<Self> {
+        syn::parse_str::<syn::Meta>(text)
+            .ok()
+            .map(|meta| Self::parse(&meta))
+    }
+    /// The features that enabling may satisfy the predicate, i.e. those named in ``feature = "..."``
+    /// outside of a ``not(...)``, e.g. ``serde`` and ``std`` for ``all(feature = "serde", any(feature = "std", unix))``
+    pub fn enabling_features(&self) -> BTreeSet<String> {
+        match self {
+            Self::KeyValue(key, value) if key == "feature" => BTreeSet::from([value.clone()]),
+            Self::All(predicates) | Self::Any(predicates) => predicates
+                .iter()
+                .flat_map(|p| p.enabling_features())
+                .collect(),
+            Self::Name(_) | Self::KeyValue(..) | Self::Not(_) | Self::Other(_) => BTreeSet::new(),
+        }
+    }
+}
+
+impl std::fmt::Display for CfgPredicate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Name(name) => write!(f, "{}", name),
+            Self::KeyValue(key, value) => write!(f, "{} = {:?}", key, value),
+            Self::Not(inner) => write!(f, "not({})", inner),
+            Self::All(predicates) => write!(f, "all({})", join(predicates)),
+            Self::Any(predicates) => write!(f, "any({})", join(predicates)),
+            Self::Other(other) => write!(f, "{}", other),
+        }
+    }
+}
+
+/// Render predicates separated by commas
+fn join(predicates: &[CfgPredicate]) -> String {
+    predicates
+        .iter()
+        .map(|p| p.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[derive(Debug, Clone, Default)]
+/// The set of enabled configuration options, against which predicates are evaluated
+pub struct CfgSet {
+    names: BTreeSet<String>,
+    key_values: BTreeSet<(String, String)>,
+}
+
+impl CfgSet {
+    /// Create the set from options in the form accepted by ``rustc --cfg``,
+    /// e.g. ``unix`` or ``feature="std"``
+    pub fn new(options: &[String]) -> Self {
+        let mut set = Self::default();
+        for option in options {
+            match option.split_once('=') {
+                Some((key, value)) => {
+                    let value = value.trim();
+                    let value = value
+                        .strip_prefix('"')
+                        .and_then(|v| v.strip_suffix('"'))
+                        .unwrap_or(value);
+                    set.key_values
+                        .insert((key.trim().to_string(), value.to_string()));
+                }
+                None => {
+                    set.names.insert(option.trim().to_string());
+                }
+            }
+        }
+        set
+    }
+
+    /// Evaluate whether a predicate is satisfied.
+    ///
+    /// As for ``rustc``, ``all()`` is satisfied and ``any()`` is not.
+    /// ``unix`` and ``windows`` are also satisfied by the matching ``target_family``, and vice versa.
+    /// Predicates that cannot be evaluated are treated as not satisfied.
+    pub fn evaluate(&self, predicate: &CfgPredicate) -> bool {
+        match predicate {
+            CfgPredicate::Name(name) => {
+                self.names.contains(name)
+                    || (FAMILIES.contains(&name.as_str())
+                        && self
+                            .key_values
+                            .contains(&("target_family".to_string(), name.to_string())))
+            }
+            CfgPredicate::KeyValue(key, value) => {
+                self.key_values
+                    .contains(&(key.to_string(), value.to_string()))
+                    || (key == "target_family"
+                        && FAMILIES.contains(&value.as_str())
+                        && self.names.contains(value))
+            }
+            CfgPredicate::Not(inner) => !self.evaluate(inner),
+            CfgPredicate::All(predicates) => predicates.iter().all(|p| self.evaluate(p)),
+            CfgPredicate::Any(predicates) => predicates.iter().any(|p| self.evaluate(p)),
+            CfgPredicate::Other(_) => false,
+        }
+    }
+}
+
+/// The target families that are also set as a configuration option name, e.g. ``unix``
+const FAMILIES: &[&str] = &["unix", "windows"];
+
+/// Extract the paths from ``#[path = "..."]`` and ``#[cfg_attr(predicate, path = "...")]`` attributes,
+/// in the order they are declared, with the predicate of the ``cfg_attr`` (if any)
+pub(super) fn paths_from_attrs(attrs: &[syn::Attribute]) -> Vec<(Option<CfgPredicate>, String)> {
+    let mut paths = vec![];
+    for attr in attrs {
+        if attr.path().is_ident("path") {
+            if let Some(path) = path_from_meta(&attr.meta) {
+                paths.push((None, path));
+            }
+        } else if let Some((predicate, nested)) = parse_cfg_attr(attr) {
+            for meta in nested {
+                if let Some(path) = path_from_meta(&meta) {
+                    paths.push((Some(predicate.clone()), path));
+                }
+            }
+        }
+    }
+    paths
+}
+
+/// Extract the ``#![no_std]`` and ``#![cfg_attr(predicate, no_std)]`` attributes,
+/// in the order they are declared, with the predicate of the ``cfg_attr`` (if any)
+pub(super) fn no_std_from_attrs(attrs: &[syn::Attribute]) -> Vec<Option<CfgPredicate>> {
+    let mut no_std = vec![];
+    for attr in attrs {
+        if attr.path().is_ident("no_std") {
+            no_std.push(None);
+        } else if let Some((predicate, nested)) = parse_cfg_attr(attr) {
+            if nested.iter().any(|meta| meta.path().is_ident("no_std")) {
+                no_std.push(Some(predicate));
+            }
+        }
+    }
+    no_std
+}
+
+/// Extract the predicate of the ``#[cfg(...)]`` attributes of an object,
+/// combined with ``all(...)`` if there are several
+pub(super) fn cfg_from_attrs(attrs: &[syn::Attribute]) -> Option<CfgPredicate> {
+    let mut predicates = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg"))
+        .map(|attr| match attr.parse_args::<syn::Meta>() {
+            Ok(meta) => CfgPredicate::parse(&meta),
+            Err(_) => CfgPredicate::Other(render_tokens(&attr.meta)),
+        })
+        .collect::<Vec<_>>();
+    match predicates.len() {
+        0 => None,
+        1 => predicates.pop(),
+        _ => Some(CfgPredicate::All(predicates)),
+    }
+}
+
+/// Combine rendered predicates, e.g. of an item and its enclosing modules, with ``all(...)``,
+/// flattening nested ``all(...)`` and omitting repeated predicates
+pub(super) fn combine_cfg<'a>(
+    predicates: impl IntoIterator<Item = Option<&'a str>>,
+) -> Option<String> {
+    let mut combined: Vec<CfgPredicate> = vec![];
+    for predicate in predicates.into_iter().flatten() {
+        let predicate = CfgPredicate::parse_str(predicate)
+            .unwrap_or_else(|| CfgPredicate::Other(predicate.to_string()));
+        let nested = match predicate {
+            CfgPredicate::All(nested) => nested,
+            other => vec![other],
+        };
+        for predicate in nested {
+            if !combined.contains(&predicate) {
+                combined.push(predicate);
+            }
+        }
+    }
+    match combined.len() {
+        0 => None,
+        1 => combined.pop().map(|p| p.to_string()),
+        _ => Some(CfgPredicate::All(combined).to_string()),
+    }
+}
+
+/// Split a ``#[cfg_attr(predicate, attr, ...)]`` attribute into its predicate and attributes
+fn parse_cfg_attr(attr: &syn::Attribute) -> Option<(CfgPredicate, Vec<syn::Meta>)> {
+    if !attr.path().is_ident("cfg_attr") {
+        return None;
+    }
+    let nested = attr
+        .parse_args_with(syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated)
+        .ok()?;
+    let mut nested = nested.into_iter();
+    let predicate = CfgPredicate::parse(&nested.next()?);
+    Some((predicate, nested.collect()))
+}
+
+/// Extract the value of a ``path = "..."`` attribute
+fn path_from_meta(meta: &syn::Meta) -> Option<String> {
+    match meta {
+        syn::Meta::NameValue(syn::MetaNameValue {
+            path,
+            value:
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(value),
+                    ..
+                }),
+            ..
+        }) if path.is_ident("path") => Some(value.value()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_cfg_set_evaluate() {
+        let cfg = CfgSet::new(&["unix".to_string(), "feature = \"std\"".to_string()]);
+        assert!(cfg.evaluate(&CfgPredicate::parse(&parse_quote!(unix))));
+        assert!(!cfg.evaluate(&CfgPredicate::parse(&parse_quote!(windows))));
+        assert!(cfg.evaluate(&CfgPredicate::parse(&parse_quote!(feature = "std"))));
+        assert!(!cfg.evaluate(&CfgPredicate::parse(&parse_quote!(feature = "alloc"))));
+        assert!(!cfg.evaluate(&CfgPredicate::parse(&parse_quote!(not(feature = "std")))));
+        assert!(cfg.evaluate(&CfgPredicate::parse(&parse_quote!(not(windows)))));
+    }
+
+    #[test]
+    fn test_combine_cfg() {
+        assert_eq!(combine_cfg([None, None]), None);
+        assert_eq!(
+            combine_cfg([None, Some("feature = \"std\"")]).as_deref(),
+            Some("feature = \"std\"")
+        );
+        assert_eq!(
+            combine_cfg([
+                Some("all(unix, feature = \"std\")"),
+                Some("feature = \"std\""),
+                Some("any(a, b)"),
+            ])
+            .as_deref(),
+            Some("all(unix, feature = \"std\", any(a, b))")
+        );
+    }
+
+    #[test]
+    fn test_cfg_set_evaluate_compound() {
+        let cfg = CfgSet::new(&[
+            "target_family=\"unix\"".to_string(),
+            "target_os=\"linux\"".to_string(),
+            "target_arch=\"x86_64\"".to_string(),
+            "feature=\"std\"".to_string(),
+        ]);
+        let evaluate = |meta: syn::Meta| cfg.evaluate(&CfgPredicate::parse(&meta));
+        // the family implies the name
+        assert!(evaluate(parse_quote!(unix)));
+        assert!(!evaluate(parse_quote!(windows)));
+        assert!(evaluate(parse_quote!(all(unix, target_arch = "x86_64"))));
+        assert!(!evaluate(parse_quote!(all(unix, target_arch = "aarch64"))));
+        assert!(evaluate(parse_quote!(any(windows, target_os = "linux"))));
+        assert!(!evaluate(parse_quote!(any(windows, target_os = "macos"))));
+        assert!(evaluate(parse_quote!(all(
+            feature = "std",
+            not(any(target_os = "macos", target_os = "ios")),
+        ))));
+        assert!(!evaluate(parse_quote!(not(all(unix, feature = "std")))));
+        // empty lists, as for rustc
+        assert!(evaluate(parse_quote!(all())));
+        assert!(!evaluate(parse_quote!(any())));
+        // unknown predicates are not satisfied, even when nested
+        assert!(!evaluate(parse_quote!(all(unix, version("1.70")))));
+        assert!(evaluate(parse_quote!(any(unix, version("1.70")))));
+
+        let cfg = CfgSet::new(&["windows".to_string()]);
+        assert!(cfg.evaluate(&CfgPredicate::parse(&parse_quote!(
+            target_family = "windows"
+        ))));
+    }
+
+    #[test]
+    fn test_cfg_predicate_display() {
+        let predicate = CfgPredicate::parse(&parse_quote!(all(
+            unix,
+            any(feature = "std", not(target_os = "none")),
+            version("1.70")
+        )));
+        assert_eq!(
+            predicate.to_string(),
+            "all(unix, any(feature = \"std\", not(target_os = \"none\")), version(\"1.70\"))"
+        );
+        assert_eq!(
+            CfgPredicate::parse(&parse_quote!(any(unix, windows))),
+            CfgPredicate::Any(vec![
+                CfgPredicate::Name("unix".to_string()),
+                CfgPredicate::Name("windows".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_cfg_from_attrs() {
+        let attrs: Vec<syn::Attribute> = vec![
+            parse_quote! { #[doc = "A docstring"] },
+            parse_quote! { #[cfg(feature = "std")] },
+        ];
+        let predicate = cfg_from_attrs(&attrs).unwrap();
+        assert_eq!(predicate.to_string(), "feature = \"std\"");
+        assert_eq!(
+            CfgPredicate::parse_str(&predicate.to_string()),
+            Some(predicate)
+        );
+        let attrs: Vec<syn::Attribute> = vec![
+            parse_quote! { #[cfg(unix)] },
+            parse_quote! { #[cfg(not(test))] },
+        ];
+        assert_eq!(
+            cfg_from_attrs(&attrs).unwrap().to_string(),
+            "all(unix, not(test))"
+        );
+        assert_eq!(
+            cfg_from_attrs(&[parse_quote! { #[cfg_attr(unix, path = "a.rs")] }]),
+            None
+        );
+    }
+
+    #[test]
+    fn test_no_std_from_attrs() {
+        let file: syn::File = parse_quote! {
+            #![no_std]
+            #![cfg_attr(not(feature = "std"), no_std)]
+            #![cfg_attr(docsrs, feature(doc_cfg))]
+        };
+        let no_std = no_std_from_attrs(&file.attrs);
+        assert_eq!(
+            no_std,
+            vec![
+                None,
+                Some(CfgPredicate::Not(Box::new(CfgPredicate::KeyValue(
+                    "feature".to_string(),
+                    "std".to_string()
+                )))),
+            ]
+        );
+        assert_eq!(
+            no_std[1].as_ref().unwrap().to_string(),
+            "not(feature = \"std\")"
+        );
+    }
+
+    #[test]
+    fn test_paths_from_attrs() {
+        let item: syn::ItemMod = parse_quote! {
+            #[cfg_attr(unix, path = "unix.rs")]
+            #[cfg_attr(windows, path = "windows.rs")]
+            #[path = "other.rs"]
+            mod imp;
+        };
+        assert_eq!(
+            paths_from_attrs(&item.attrs),
+            vec![
+                (
+                    Some(CfgPredicate::Name("unix".to_string())),
+                    "unix.rs".to_string()
+                ),
+                (
+                    Some(CfgPredicate::Name("windows".to_string())),
+                    "windows.rs".to_string()
+                ),
+                (None, "other.rs".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_enabling_features() {
+        let features = |text: &str| {
+            CfgPredicate::parse_str(text)
+                .unwrap()
+                .enabling_features()
+                .into_iter()
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(features("feature = \"std\""), vec!["std"]);
+        assert_eq!(
+            features(
+                "all(feature = \"serde\", any(feature = \"std\", unix), not(feature = \"alloc\"))"
+            ),
+            vec!["serde", "std"]
+        );
+        assert_eq!(features("target_feature = \"avx2\""), Vec::<String>::new());
+    }
+}
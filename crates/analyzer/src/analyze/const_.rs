@@ -0,0 +1,146 @@
+//! Analyze constants and statics
+use syn::spanned::Spanned;
+
+use crate::data_model::{Const, Static};
+
+use super::{
+    anchor_id, attributes_from_attrs,
+    cfg::cfg_from_attrs,
+    doc_aliases_from_attrs, docstring_from_attrs, stability_from_attrs, truncate,
+    type_::{convert_type, render_expr},
+};
+
+/// The maximum number of characters of the preview of an initializer
+pub const VALUE_PREVIEW_LENGTH: usize = 80;
+
+impl Const {
+    /// Fully qualified name of the constant
+    pub fn path_str(&self) -> String {
+        self.path.join("::")
+    }
+    /// A stable, URL-safe identifier for the constant, e.g. for anchor links
+    pub fn anchor_id(&self) -> String {
+        anchor_id("constant", &self.path)
+    }
+    /// Extract the relevant information from the AST
+    pub fn parse(parent: &[&str], ast: &syn::ItemConst) -> Self {
+        let name = ast.ident.to_string();
+        Self {
+            name: name.clone(),
+            path: parent
+                .iter()
+                .map(|s| s.to_string())
+                .chain(Some(name))
+                .collect(),
+            file: None,
+            docstring: docstring_from_attrs(&ast.attrs),
+            doc_aliases: doc_aliases_from_attrs(&ast.attrs),
+            attributes: attributes_from_attrs(&ast.attrs),
+            stability: stability_from_attrs(&ast.attrs),
+            type_: convert_type(&ast.ty),
+            value: value_preview(&ast.expr),
+            full_value: ast.expr.span().source_text(),
+            effectively_public: matches!(ast.vis, syn::Visibility::Public(_)),
+            cfg_info: cfg_from_attrs(&ast.attrs).map(|p| p.to_string()),
+        }
+    }
+}
+
+impl Static {
+    /// Fully qualified name of the static
+    pub fn path_str(&self) -> String {
+        self.path.join("::")
+    }
+    /// A stable, URL-safe identifier for the static, e.g. for anchor links
+    pub fn anchor_id(&self) -> String {
+        anchor_id("static", &self.path)
+    }
+    /// Extract the relevant information from the AST
+    pub fn parse(parent: &[&str], ast: &syn::ItemStatic) -> Self {
+        let name = ast.ident.to_string();
+        Self {
+            name: name.clone(),
+            path: parent
+                .iter()
+                .map(|s| s.to_string())
+                .chain(Some(name))
+                .collect(),
+            file: None,
+            docstring: docstring_from_attrs(&ast.attrs),
+            doc_aliases: doc_aliases_from_attrs(&ast.attrs),
+            attributes: attributes_from_attrs(&ast.attrs),
+            stability: stability_from_attrs(&ast.attrs),
+            mutable: matches!(ast.mutability, syn::StaticMutability::Mut(_)),
+            type_: convert_type(&ast.ty),
+            value: value_preview(&ast.expr),
+            full_value: ast.expr.span().source_text(),
+            effectively_public: matches!(ast.vis, syn::Visibility::Public(_)),
+            cfg_info: cfg_from_attrs(&ast.attrs).map(|p| p.to_string()),
+        }
+    }
+}
+
+/// Render an initializer from its tokens, truncated to ``VALUE_PREVIEW_LENGTH`` characters
+fn value_preview(expr: &syn::Expr) -> String {
+    truncate(&render_expr(expr), VALUE_PREVIEW_LENGTH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_yaml_snapshot;
+
+    #[test]
+    fn test_parse_const_long_initializer() {
+        let ast: syn::ItemConst = syn::parse_quote! {
+            /// The primes below a hundred
+            pub const PRIMES: [u32; 25] = [
+                2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47,
+                53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+            ];
+        };
+        let const_ = Const::parse(&["crate"], &ast);
+        assert_eq!(const_.value.chars().count(), VALUE_PREVIEW_LENGTH);
+        assert_yaml_snapshot!(const_, @r###"
+        ---
+        kind: const
+        name: PRIMES
+        path:
+          - crate
+          - PRIMES
+        docstring: The primes below a hundred
+        doc_aliases: []
+        attributes: []
+        type_:
+          - String: "["
+          - Path: u32
+          - String: "; 25]"
+        value: "[2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73…"
+        effectively_public: true
+        "###);
+    }
+
+    #[test]
+    fn test_parse_static_short_initializer() {
+        let ast: syn::ItemStatic = syn::parse_quote! {
+            static mut COUNTER: AtomicUsize = AtomicUsize::new(0);
+        };
+        let static_ = Static::parse(&["crate"], &ast);
+        assert_yaml_snapshot!(static_, @r###"
+        ---
+        kind: static
+        name: COUNTER
+        path:
+          - crate
+          - COUNTER
+        docstring: ""
+        doc_aliases: []
+        attributes: []
+        mutable: true
+        type_:
+          - Path: AtomicUsize
+        value: "AtomicUsize::new(0)"
+        effectively_public: false
+        "###);
+    }
+}
@@ -1,17 +1,935 @@
 //! Analyze the crate
 use anyhow::{anyhow, Context, Result};
-use cargo_metadata::{MetadataCommand, Target};
+use cargo_metadata::{
+    DependencyKind, Message, Metadata, MetadataCommand, Package, PackageId, Target,
+};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use crate::data_model::{Crate, Enum, Function, Module, Struct};
+use crate::data_model::{
+    Const, Crate, DocSections, Enum, ExternBlock, Function, FunctionOwner, Impl, Macro, Module,
+    NoStd, Static, Struct, TargetKind, Trait, TypeAlias, TypeSignature,
+};
 
-pub fn analyze_crate(path: &str) -> Result<AnalysisResult> {
+use super::{
+    cfg::{combine_cfg, CfgPredicate, CfgSet},
+    docstring::broken_examples,
+    impl_::resolve_relative_path,
+    links::SYSROOT_CRATES,
+    module::{select_declarations, IncludePath, ModuleDeclaration, ModuleItems},
+    truncate, truncate_signature,
+    type_::{truncate_type_depth, with_max_parse_depth, DEFAULT_MAX_PARSE_DEPTH},
+    type_alias::expand_type_aliases,
+};
+
+#[derive(Debug, Clone, Default)]
+/// Options for the crate analysis
+pub struct AnalysisOptions {
+    /// Glob patterns for modules to exclude from the analysis.
+    ///
+    /// Patterns are matched against the fully qualified name of the module,
+    /// both with and without the crate name, e.g. ``my_crate::internal`` and ``internal``.
+    /// ``*`` matches any sequence of characters (including ``::``) and ``?`` matches a single character.
+    pub exclude_patterns: Vec<String>,
+    /// The maximum number of characters of rendered values and signatures,
+    /// after which they are truncated with an ellipsis (no truncation if ``None``)
+    pub max_render_length: Option<usize>,
+    /// The maximum nesting depth of brackets in rendered types,
+    /// beyond which their content is truncated with an ellipsis,
+    /// e.g. ``Vec<Vec<…>>`` for a depth of 1 (no truncation if ``None``)
+    pub max_type_depth: Option<usize>,
+    /// The maximum nesting depth of types when parsed, beyond which they are rendered as an ellipsis,
+    /// which bounds the recursion of the parser for pathological types
+    /// (``DEFAULT_MAX_PARSE_DEPTH`` if ``None``)
+    pub max_parse_depth: Option<usize>,
+    /// Do not resolve symlinks in the crate path.
+    ///
+    /// By default the crate path is canonicalized, which resolves symlinks.
+    /// If set, the path is instead made absolute and normalized lexically,
+    /// so that module files are resolved within the (possibly symlinked) crate directory.
+    pub preserve_symlinks: bool,
+    /// The enabled configuration options, in the form accepted by ``rustc --cfg``,
+    /// e.g. ``unix`` or ``feature="std"``.
+    ///
+    /// These are used to evaluate ``#[cfg_attr(..., path = "...")]`` attributes on module declarations,
+    /// and, if any are given, the ``#[cfg(...)]`` attributes of fields and variants,
+    /// which are dropped if their predicate is not satisfied.
+    pub cfg: Vec<String>,
+    /// The ``OUT_DIR`` of the crate's build script,
+    /// used to resolve ``include!(concat!(env!("OUT_DIR"), "..."))`` in modules
+    pub out_dir: Option<String>,
+    /// Run ``cargo build`` to find the ``OUT_DIR`` of the crate's build script,
+    /// if ``out_dir`` is not given.
+    ///
+    /// If the build fails, a warning is recorded and generated files are not included.
+    pub run_build: bool,
+    /// The depth of dependencies to also analyze, e.g. ``1`` for direct dependencies only
+    /// (``0`` by default).
+    ///
+    /// The library targets of normal dependencies are analyzed with their resolved features,
+    /// and their items are added to the result, with the dependency name as the first path segment.
+    pub dependency_depth: usize,
+    /// A function to rewrite the fully qualified names of all items once the analysis is complete,
+    /// e.g. to collapse ``my_crate::internal::Foo`` to ``my_crate::Foo``
+    pub path_rewriter: Option<PathRewriter>,
+    /// Keep modules and items that are not reachable from the crate root
+    /// through public modules and re-exports.
+    ///
+    /// By default these are dropped once their effective visibility is computed.
+    pub include_private: bool,
+    /// The name to use as the first path segment of all modules and items,
+    /// instead of the name of the crate's target,
+    /// e.g. to document the crate under the name of a facade that re-exports it
+    pub crate_name_override: Option<String>,
+    /// Fail the analysis if there are any warnings, e.g. for a module file that cannot be found
+    /// or fails to parse, rather than returning them with the (incomplete) result
+    pub strict: bool,
+    /// Only emit the items declared in these files, e.g. those changed since a git revision,
+    /// for a partial rebuild of the documentation.
+    ///
+    /// The whole crate is still analyzed, so that visibility and paths are resolved as usual,
+    /// and the modules containing the items are kept to place them in the module tree.
+    /// Paths may be absolute, or relative to any directory, e.g. ``src/net/socket.rs``.
+    pub changed_files: Option<Vec<PathBuf>>,
+    /// Replace the type aliases of the crate in rendered types with their target types,
+    /// e.g. to render a field of type ``Bytes`` as ``Vec<u8>`` for ``type Bytes = Vec<u8>;``.
+    ///
+    /// Only one level of aliasing is expanded, and the targets of the aliases themselves are kept as written.
+    pub expand_type_aliases: bool,
+    /// The markers that identify a generated module file, when found (ignoring case)
+    /// in the comments at the start of the file, before any code or docstring
+    /// (``@generated``, ``generated by`` and ``do not edit`` if ``None``)
+    pub generated_markers: Option<Vec<String>>,
+    /// The time to wait for ``cargo metadata`` to read the crate metadata, before it is killed and retried
+    /// (120 seconds if ``None``).
+    ///
+    /// ``cargo metadata`` may stall waiting for a lock on the package cache, or for the network
+    /// when it needs to update the registry index.
+    pub metadata_timeout: Option<Duration>,
+    /// Record a warning for each public module and item of the crate without a docstring,
+    /// e.g. to fail the documentation build on them in strict mode
+    pub warn_undocumented: bool,
+    /// Record a warning for each public ``unsafe`` function, method or trait of the crate
+    /// whose docstring has no ``# Safety`` section
+    pub warn_missing_safety: bool,
+    /// Record a warning for each Rust code example in the docstrings of the crate that does not parse,
+    /// e.g. to catch typos in doc examples without compiling them
+    pub warn_broken_examples: bool,
+    /// Also analyze the example targets of the crate, e.g. ``examples/demo.rs``.
+    ///
+    /// Each target is analyzed as its own crate root, named by the target,
+    /// and its modules are tagged with their ``target_kind``.
+    pub include_examples: bool,
+    /// Also analyze the integration test targets of the crate, e.g. ``tests/api.rs``,
+    /// as for ``include_examples``
+    pub include_tests: bool,
+    /// Also analyze the benchmark targets of the crate, e.g. ``benches/parse.rs``,
+    /// as for ``include_examples``
+    pub include_benches: bool,
+    /// Keep the signatures of functions as written in the source, as ``source_signature``,
+    /// preserving the formatting and comments that re-serializing the parsed tokens normalizes.
+    ///
+    /// The rendered signatures are still computed, as a fallback for functions without source text.
+    pub preserve_signature_formatting: bool,
+    /// Keep the initializers of constants and statics as written in the source, as ``full_value``,
+    /// in addition to the truncated ``value`` preview
+    pub full_initializers: bool,
+    /// Drop the ``PhantomData<...>`` marker fields of structs and enum variants,
+    /// rather than keeping them for their variance and auto trait information
+    pub hide_phantom_fields: bool,
+    /// Mark the structs and enums whose fields reference the type itself, e.g. tree or AST types,
+    /// as ``recursive``
+    pub detect_recursive_types: bool,
+    /// The base URLs of the documentation of external crates, by crate name,
+    /// in the style of an intersphinx mapping, e.g. ``serde`` to ``https://docs.rs/serde/1.0.0/serde/``,
+    /// used to link the paths to their items in rendered types (see ``with_external_docs``)
+    pub external_docs: BTreeMap<String, String>,
+    /// Link the paths to items of external crates that are not in ``external_docs`` to docs.rs,
+    /// or doc.rust-lang.org for the standard library, rather than leaving them unlinked
+    pub link_docs_rs: bool,
+    /// A function to build the documentation URLs of items of external crates that are not in ``external_docs``,
+    /// e.g. for a private registry, tried before docs.rs if ``link_docs_rs`` is set
+    pub doc_url_resolver: Option<DocUrlResolver>,
+}
+
+/// The default timeout of ``cargo metadata``
+const METADATA_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// The number of times ``cargo metadata`` is run, before failing with a ``MetadataTimeoutError``
+const METADATA_ATTEMPTS: usize = 3;
+
+/// The default markers of a generated module file, e.g. from ``prost`` or ``bindgen``
+const GENERATED_MARKERS: &[&str] = &["@generated", "generated by", "do not edit"];
+
+type RewriteFn = dyn Fn(&[String]) -> Vec<String> + Send + Sync;
+
+#[derive(Clone)]
+/// A function to rewrite fully qualified names, given as a list of path segments
+pub struct PathRewriter(Arc<RewriteFn>);
+
+impl PathRewriter {
+    pub fn new(rewriter: impl Fn(&[String]) -> Vec<String> + Send + Sync + 'static) -> Self {
+        Self(Arc::new(rewriter))
+    }
+    /// Rewrite a fully qualified name
+    pub fn rewrite(&self, path: &[String]) -> Vec<String> {
+        (self.0)(path)
+    }
+}
+
+impl std::fmt::Debug for PathRewriter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PathRewriter")
+    }
+}
+
+type DocUrlFn = dyn Fn(&str, &[String]) -> Option<String> + Send + Sync;
+
+#[derive(Clone)]
+/// A function to build the documentation URL of an item of an external crate,
+/// given the name of the crate and the path of the item within it,
+/// e.g. ``serde`` and ``["de", "Deserialize"]`` for ``serde::de::Deserialize``
+pub struct DocUrlResolver(Arc<DocUrlFn>);
+
+impl DocUrlResolver {
+    pub fn new(
+        resolver: impl Fn(&str, &[String]) -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        Self(Arc::new(resolver))
+    }
+    /// Build the documentation URL of an item, if known
+    pub fn resolve(&self, crate_name: &str, path: &[String]) -> Option<String> {
+        (self.0)(crate_name, path)
+    }
+    /// Link to a search for the item in the latest documentation on docs.rs,
+    /// or on doc.rust-lang.org for ``std``, ``core``, ``alloc``, ``proc_macro`` and ``test``.
+    ///
+    /// A search is used, since the URL of an item page depends on its kind, which is not known.
+    pub fn docs_rs() -> Self {
+        Self::new(|crate_name, path| {
+            let base = if SYSROOT_CRATES.contains(&crate_name) {
+                format!("https://doc.rust-lang.org/{}/", crate_name)
+            } else {
+                format!("https://docs.rs/{0}/latest/{0}/", crate_name)
+            };
+            Some(search_url(base, path))
+        })
+    }
+    /// Link to a search for the item in the documentation at the base URL of its crate in the mapping,
+    /// e.g. ``https://docs.rs/serde/1.0.0/serde/`` for ``serde``,
+    /// or with the fallback resolver if the crate is not mapped
+    pub fn from_mapping(
+        mapping: BTreeMap<String, String>,
+        fallback: Option<DocUrlResolver>,
+    ) -> Self {
+        Self::new(move |crate_name, path| match mapping.get(crate_name) {
+            Some(base) if base.ends_with('/') => Some(search_url(base.clone(), path)),
+            Some(base) => Some(search_url(format!("{}/", base), path)),
+            None => fallback.as_ref()?.resolve(crate_name, path),
+        })
+    }
+}
+
+/// The URL of a search for an item in the documentation of a crate at the base URL,
+/// or of the crate root if the path is empty
+fn search_url(base: String, path: &[String]) -> String {
+    match path {
+        [] => base,
+        path => format!("{}?search={}", base, path.join("::")),
+    }
+}
+
+impl Default for DocUrlResolver {
+    fn default() -> Self {
+        Self::docs_rs()
+    }
+}
+
+impl std::fmt::Debug for DocUrlResolver {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("DocUrlResolver")
+    }
+}
+
+impl AnalysisOptions {
+    /// Register the base URL of the documentation of an external crate, to link the paths to its items
+    /// in rendered types, e.g.
+    /// ``AnalysisOptions::default().with_external_docs("serde", "https://docs.rs/serde/1.0.0/serde/")``
+    pub fn with_external_docs(
+        mut self,
+        crate_name: impl Into<String>,
+        base_url: impl Into<String>,
+    ) -> Self {
+        self.external_docs
+            .insert(crate_name.into(), base_url.into());
+        self
+    }
+    /// The resolver of the documentation URLs of items of external crates,
+    /// from ``external_docs``, ``doc_url_resolver`` and ``link_docs_rs``, or ``None`` if they are not linked
+    pub fn external_doc_resolver(&self) -> Option<DocUrlResolver> {
+        let fallback = match (self.doc_url_resolver.clone(), self.link_docs_rs) {
+            (Some(resolver), true) => {
+                let docs_rs = DocUrlResolver::docs_rs();
+                Some(DocUrlResolver::new(move |crate_name, path| {
+                    resolver
+                        .resolve(crate_name, path)
+                        .or_else(|| docs_rs.resolve(crate_name, path))
+                }))
+            }
+            (resolver, link_docs_rs) => resolver.or(link_docs_rs.then(DocUrlResolver::docs_rs)),
+        };
+        if self.external_docs.is_empty() && fallback.is_none() {
+            return None;
+        }
+        Some(DocUrlResolver::from_mapping(
+            self.external_docs.clone(),
+            fallback,
+        ))
+    }
+    /// Check if the header of a module file marks it as generated,
+    /// i.e. the leading blank and comment lines, other than ``//!`` docstrings, contain a generated marker
+    pub fn is_generated(&self, content: &str) -> bool {
+        let header = content
+            .lines()
+            .map(str::trim)
+            .take_while(|line| {
+                line.is_empty()
+                    || (line.starts_with("//") && !line.starts_with("//!"))
+                    || (line.starts_with("/*") && !line.starts_with("/*!"))
+                    || line.starts_with('*')
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+            .to_lowercase();
+        match &self.generated_markers {
+            Some(markers) => markers
+                .iter()
+                .any(|marker| header.contains(&marker.to_lowercase())),
+            None => GENERATED_MARKERS
+                .iter()
+                .any(|marker| header.contains(marker)),
+        }
+    }
+    /// Check if a module path matches any of the exclude patterns
+    pub fn is_excluded(&self, path: &[String]) -> bool {
+        let full_name = path.join("::");
+        let relative_name = path[1..].join("::");
+        self.exclude_patterns.iter().any(|pattern| {
+            glob_match(pattern, &full_name)
+                || (!relative_name.is_empty() && glob_match(pattern, &relative_name))
+        })
+    }
+}
+
+/// Make a path absolute and remove ``.`` and ``..`` components, without resolving symlinks
+fn normalize_path(path: &Path) -> std::io::Result<PathBuf> {
+    let absolute = std::path::absolute(path)?;
+    let mut normalized = PathBuf::new();
+    for component in absolute.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    Ok(normalized)
+}
+
+/// Match a string against a glob pattern, supporting ``*`` and ``?`` wildcards
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // position of the last ``*`` in the pattern, and the text position it was matched at
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = backtrack {
+            p = star_p + 1;
+            t = star_t + 1;
+            backtrack = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Build the crate with ``cargo build``, and return the ``OUT_DIR`` of its build script (if it has one)
+fn build_out_dir(manifest_path: &Path, package_id: &PackageId) -> Result<Option<PathBuf>> {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let output = std::process::Command::new(cargo)
+        .arg("build")
+        .arg("--message-format=json")
+        .arg("--manifest-path")
+        .arg(manifest_path)
+        .output()
+        .context("Failed to run `cargo build`")?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "`cargo build` failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    out_dir_from_messages(&output.stdout, package_id)
+}
+
+/// Find the ``OUT_DIR`` of a package's build script in the JSON messages of ``cargo build``
+fn out_dir_from_messages(messages: &[u8], package_id: &PackageId) -> Result<Option<PathBuf>> {
+    for message in Message::parse_stream(messages) {
+        if let Message::BuildScriptExecuted(script) = message? {
+            if &script.package_id == package_id {
+                return Ok(Some(script.out_dir.into_std_path_buf()));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Read the files included in a module with ``include!``, and add their items to the module's items.
+///
+/// The inner docstring (``//!``) and ``use`` declarations of the included files belong to the including module,
+/// and are appended to those of the module.
+/// Problems resolving or parsing the files are recorded as warnings,
+/// as are those reading the files included in the docstrings of the module and included files.
+fn read_includes(
+    module: &mut Module,
+    module_file: &Path,
+    items: &mut ModuleItems,
+    out_dir: Option<&Path>,
+    warnings: &mut Vec<AnalysisWarning>,
+) {
+    let path = module.path.clone();
+    let path = path.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+    let file_dir = module_file.parent().unwrap_or(Path::new(""));
+    let mut includes: Vec<(PathBuf, IncludePath)> = std::mem::take(&mut items.includes)
+        .into_iter()
+        .map(|include| (file_dir.to_path_buf(), include))
+        .collect();
+    let mut read_files = vec![];
+    while let Some((dir, include)) = includes.pop() {
+        let mut warn = |message: String| {
+            warnings.push(AnalysisWarning {
+                message,
+                file: Some(module_file.to_string_lossy().to_string()),
+            })
+        };
+        let file = match &include {
+            IncludePath::File(file) => dir.join(file),
+            IncludePath::OutDir(file) => match out_dir {
+                Some(out_dir) => {
+                    module.is_generated = true;
+                    out_dir.join(file)
+                }
+                None => {
+                    warn(format!(
+                        "Cannot include {:?} in {}, as the OUT_DIR is not known",
+                        file,
+                        path.join("::")
+                    ));
+                    continue;
+                }
+            },
+        };
+        if read_files.contains(&file) {
+            continue;
+        }
+        read_files.push(file.clone());
+        let content = match std::fs::read_to_string(&file) {
+            Ok(content) => content,
+            Err(err) => {
+                warn(format!(
+                    "Cannot read included file {}: {}",
+                    file.to_string_lossy(),
+                    err
+                ));
+                continue;
+            }
+        };
+        match Module::parse(Some(&file), &path, &content) {
+            Ok((included_module, mut included)) => {
+                if !included_module.docstring.is_empty() {
+                    if !module.docstring.is_empty() {
+                        module.docstring.push_str("\n\n");
+                    }
+                    module.docstring.push_str(&included_module.docstring);
+                }
+                module.uses.extend(included_module.uses);
+                // paths in the included file are relative to that file
+                let included_dir = file.parent().unwrap_or(Path::new("")).to_path_buf();
+                includes.extend(
+                    std::mem::take(&mut included.includes)
+                        .into_iter()
+                        .map(|include| (included_dir.clone(), include)),
+                );
+                items.extend(included);
+            }
+            Err(err) => warn(format!(
+                "Error parsing included file {}: {}",
+                file.to_string_lossy(),
+                err
+            )),
+        }
+    }
+    for message in std::mem::take(&mut items.doc_errors) {
+        warnings.push(AnalysisWarning {
+            message,
+            file: Some(module_file.to_string_lossy().to_string()),
+        });
+    }
+}
+
+/// Create the crate representation from its package metadata
+fn crate_from_package(name: &str, pkg: &Package) -> Crate {
+    Crate {
+        name: name.to_string(),
+        version: pkg.version.to_string(), // workspace-aware
+        description: pkg.description.clone(),
+        authors: pkg.authors.clone(),
+        license: pkg.license.clone().filter(|l| !l.trim().is_empty()),
+        license_file: pkg.license_file.as_ref().map(|f| f.to_string()),
+        repository: pkg.repository.clone(),
+        homepage: pkg.homepage.clone(),
+        rust_version: pkg.rust_version.as_ref().map(|v| v.to_string()),
+        no_std: NoStd::Std,
+    }
+}
+
+/// Determine whether the crate is ``#![no_std]``, from the attributes of its root module;
+/// an unconditional ``#![no_std]`` takes precedence over a conditional one
+fn no_std_status(attrs: &[Option<CfgPredicate>], cfg: &CfgSet) -> NoStd {
+    if attrs.iter().any(Option::is_none) {
+        return NoStd::Always;
+    }
+    match attrs.iter().flatten().next() {
+        Some(predicate) => NoStd::Conditional {
+            condition: predicate.to_string(),
+            active: cfg.evaluate(predicate),
+        },
+        None => NoStd::Std,
+    }
+}
+
+/// A sub-module declaration that is still to be read
+struct PendingModule {
+    /// The directory of the declaring file
+    file_dir: PathBuf,
+    /// The directory to look for default module files in
+    parent_dir: PathBuf,
+    decl: ModuleDeclaration,
+    /// The path of the declaring module
+    parent: Vec<String>,
+    /// The files of the declaring module and its ancestors, to stop at a module that includes itself
+    ancestors: Vec<PathBuf>,
+    /// The ``cfg`` predicates of the alternative declarations of the module, if it is declared more than once
+    cfg_variants: Vec<String>,
+}
+
+/// Read the root module of a crate target and, recursively, its sub-modules,
+/// adding the modules and their items to the result.
+///
+/// Returns whether the crate is ``#![no_std]``.
+fn analyze_modules(
+    root_module: &Path,
+    crate_name: &str,
+    options: &AnalysisOptions,
+    cfg: &CfgSet,
+    out_dir: Option<&Path>,
+    result: &mut AnalysisResult,
+) -> Result<NoStd> {
+    // read the top-level module
+    let content = std::fs::read_to_string(root_module)?;
+    let (mut module, mut items) = Module::parse(Some(root_module), &[crate_name], &content)
+        .context(format!(
+            "Error parsing module {}",
+            root_module.to_string_lossy()
+        ))?;
+    module.is_generated = options.is_generated(&content);
+    read_includes(
+        &mut module,
+        root_module,
+        &mut items,
+        out_dir,
+        &mut result.warnings,
+    );
+    let no_std = no_std_status(&items.no_std, cfg);
+
+    let root_dir = root_module.parent().unwrap().to_path_buf();
+    let configured = !options.cfg.is_empty();
+    let mut modules_to_read = select_declarations(&items.submodules, cfg, configured)
+        .into_iter()
+        .map(|(decl, cfg_variants)| PendingModule {
+            file_dir: root_dir.clone(),
+            parent_dir: root_dir.clone(),
+            decl: decl.clone(),
+            parent: vec![crate_name.to_string()],
+            ancestors: vec![root_module.to_path_buf()],
+            cfg_variants,
+        })
+        .collect::<Vec<_>>();
+
+    result.modules.push(module);
+    result.extend_items(items);
+
+    // recursively find/read the sub‑modules
+    let mut read_modules = vec![];
+    while let Some(PendingModule {
+        file_dir,
+        parent_dir,
+        decl,
+        parent,
+        ancestors,
+        cfg_variants,
+    }) = modules_to_read.pop()
+    {
+        let module_name = &decl.name;
+        let (module_path, submodule_dir) = if let Some(path) = decl.active_path(cfg) {
+            // ``path`` attributes are relative to the directory of the declaring file,
+            // and sub-modules of the loaded file are looked up relative to its own directory
+            let module_path = file_dir.join(path);
+            let submodule_dir = module_path
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_else(|| file_dir.clone());
+            (module_path, submodule_dir)
+        } else if parent_dir.join(module_name).with_extension("rs").exists() {
+            (
+                parent_dir.join(module_name).with_extension("rs"),
+                parent_dir.join(module_name),
+            )
+        } else {
+            (
+                parent_dir.join(module_name).join("mod.rs"),
+                parent_dir.join(module_name),
+            )
+        };
+        if !module_path.exists() {
+            result.warnings.push(AnalysisWarning {
+                message: format!(
+                    "Could not find the file of module {}::{}",
+                    parent.join("::"),
+                    module_name
+                ),
+                file: Some(module_path.to_string_lossy().to_string()),
+            });
+            continue;
+        }
+
+        // a file may be shared by several modules through ``path`` attributes,
+        // in which case it is read once for each of them
+        let path: Vec<String> = [&parent[..], std::slice::from_ref(module_name)].concat();
+        let read_key = (module_path.clone(), path.clone());
+        if ancestors.contains(&module_path) || read_modules.contains(&read_key) {
+            continue;
+        }
+        read_modules.push(read_key);
+
+        if options.is_excluded(&path) {
+            result.excluded_modules.push(path.join("::"));
+            continue;
+        }
+
+        let content = match std::fs::read_to_string(&module_path) {
+            Ok(content) => content,
+            Err(err) => {
+                result.warnings.push(AnalysisWarning {
+                    message: format!("Could not read module {}: {}", path.join("::"), err),
+                    file: Some(module_path.to_string_lossy().to_string()),
+                });
+                continue;
+            }
+        };
+        let path_refs = path.iter().map(|s| s.as_str()).collect::<Vec<&str>>();
+        let (mut module, mut items) = match Module::parse(Some(&module_path), &path_refs, &content)
+        {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                result.warnings.push(AnalysisWarning {
+                    message: format!("Error parsing module {}: {}", path.join("::"), err),
+                    file: Some(module_path.to_string_lossy().to_string()),
+                });
+                continue;
+            }
+        };
+        // reachability through parent modules and re-exports is resolved once all modules are read
+        module.effectively_public = decl.public;
+        module.cfg_info = combine_cfg([decl.cfg.as_deref(), module.cfg_info.as_deref()]);
+        module.cfg_variants = cfg_variants;
+        module.is_generated = options.is_generated(&content);
+        read_includes(
+            &mut module,
+            &module_path,
+            &mut items,
+            out_dir,
+            &mut result.warnings,
+        );
+
+        let file_dir = module_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| file_dir.clone());
+        let ancestors = [&ancestors[..], std::slice::from_ref(&module_path)].concat();
+        modules_to_read.extend(
+            select_declarations(&items.submodules, cfg, configured)
+                .into_iter()
+                .map(|(decl, cfg_variants)| PendingModule {
+                    file_dir: file_dir.clone(),
+                    parent_dir: submodule_dir.clone(),
+                    decl: decl.clone(),
+                    parent: path.clone(),
+                    ancestors: ancestors.clone(),
+                    cfg_variants,
+                }),
+        );
+        result.modules.push(module);
+        result.extend_items(items);
+    }
+
+    Ok(no_std)
+}
+
+/// Analyze the example, test and benchmark targets of a package, as requested by the options,
+/// adding their modules and items to the result, with the modules tagged by the kind of their target.
+///
+/// Targets that cannot be analyzed are recorded as warnings.
+fn analyze_extra_targets(
+    root_pkg: &Package,
+    options: &AnalysisOptions,
+    cfg: &CfgSet,
+    out_dir: Option<&Path>,
+    result: &mut AnalysisResult,
+) {
+    let kinds = [
+        (TargetKind::Example, "example", options.include_examples),
+        (TargetKind::Test, "test", options.include_tests),
+        (TargetKind::Bench, "bench", options.include_benches),
+    ];
+    for (kind, cargo_kind, included) in kinds {
+        if !included {
+            continue;
+        }
+        for target in root_pkg
+            .targets
+            .iter()
+            .filter(|t| t.kind.iter().any(|k| k == cargo_kind))
+        {
+            let name = target.name.replace('-', "_");
+            let root_module = target.src_path.clone().into_std_path_buf();
+            let first_module = result.modules.len();
+            if let Err(err) = analyze_modules(&root_module, &name, options, cfg, out_dir, result) {
+                result.warnings.push(AnalysisWarning {
+                    message: format!(
+                        "Could not analyze {} target {}: {:#}",
+                        cargo_kind, name, err
+                    ),
+                    file: Some(root_module.to_string_lossy().to_string()),
+                });
+                continue;
+            }
+            for module in result.modules[first_module..].iter_mut() {
+                module.target_kind = Some(kind);
+            }
+        }
+    }
+}
+
+/// Analyze the library targets of the dependencies of a package, up to the configured depth,
+/// adding their modules and items to the result.
+///
+/// Dependencies that cannot be analyzed are recorded as warnings.
+fn analyze_dependencies(
+    metadata: &Metadata,
+    root_pkg: &Package,
+    options: &AnalysisOptions,
+    result: &mut AnalysisResult,
+) {
+    let Some(resolve) = &metadata.resolve else {
+        return;
+    };
+    let mut visited = vec![root_pkg.id.clone()];
+    let mut current = vec![root_pkg.id.clone()];
+    for _ in 0..options.dependency_depth {
+        let mut next = vec![];
+        for id in &current {
+            let Some(node) = resolve.nodes.iter().find(|n| &n.id == id) else {
+                continue;
+            };
+            for dep in &node.deps {
+                let is_normal = dep
+                    .dep_kinds
+                    .iter()
+                    .any(|k| k.kind == DependencyKind::Normal);
+                if is_normal && !visited.contains(&dep.pkg) {
+                    visited.push(dep.pkg.clone());
+                    next.push(dep.pkg.clone());
+                }
+            }
+        }
+        for id in &next {
+            let Some(pkg) = metadata.packages.iter().find(|p| &p.id == id) else {
+                continue;
+            };
+            let Some(target) = pkg.targets.iter().find(|t| t.kind.contains(&"lib".into())) else {
+                continue;
+            };
+            // evaluate the dependency with the features it is resolved with
+            let features = resolve
+                .nodes
+                .iter()
+                .find(|n| &n.id == id)
+                .map(|n| n.features.clone())
+                .unwrap_or_default();
+            let cfg_options: Vec<String> = options
+                .cfg
+                .iter()
+                .filter(|c| !c.trim_start().starts_with("feature"))
+                .cloned()
+                .chain(features.iter().map(|f| format!("feature=\"{}\"", f)))
+                .collect();
+            let name = target.name.replace('-', "_");
+            let root_module = target.src_path.clone().into_std_path_buf();
+            // the dependency is analyzed separately, so that nothing is added if it fails part way
+            let mut analysis = AnalysisResult::new(crate_from_package(&name, pkg));
+            let no_std = match analyze_modules(
+                &root_module,
+                &name,
+                options,
+                &CfgSet::new(&cfg_options),
+                None,
+                &mut analysis,
+            ) {
+                Ok(no_std) => no_std,
+                Err(err) => {
+                    result.warnings.push(AnalysisWarning {
+                        message: format!("Could not analyze dependency {}: {:#}", name, err),
+                        file: Some(pkg.manifest_path.to_string()),
+                    });
+                    continue;
+                }
+            };
+            let mut dependency = analysis.crate_.clone();
+            dependency.no_std = no_std;
+            result.extend_analysis(analysis);
+            result.dependencies.push(dependency);
+        }
+        current = next;
+    }
+}
+
+/// The stack size of the thread that crates are analyzed on.
+///
+/// Parsing and rendering the AST is recursive,
+/// so this is larger than the default, to allow for deeply nested types.
+const ANALYSIS_STACK_SIZE: usize = 64 * 1024 * 1024;
+
+/// Run an analysis on a thread with a stack of ``ANALYSIS_STACK_SIZE``,
+/// parsing types up to the ``max_parse_depth`` of the options
+fn on_analysis_thread<T: Send>(
+    options: &AnalysisOptions,
+    analyze: impl FnOnce() -> Result<T> + Send,
+) -> Result<T> {
+    let max_depth = options.max_parse_depth.unwrap_or(DEFAULT_MAX_PARSE_DEPTH);
+    std::thread::scope(|scope| {
+        std::thread::Builder::new()
+            .stack_size(ANALYSIS_STACK_SIZE)
+            .spawn_scoped(scope, || with_max_parse_depth(max_depth, analyze))?
+            .join()
+            .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+    })
+}
+
+pub fn analyze_crate(path: &str, options: &AnalysisOptions) -> Result<AnalysisResult> {
+    on_analysis_thread(options, || {
+        let (crate_dir, cargo_toml_path) = resolve_manifest(path, options)?;
+
+        // use `cargo_metadata` instead of implementing own TOML parser
+        let metadata = read_metadata(&cargo_toml_path, options)?;
+        let root_pkg = metadata
+            .root_package()
+            .ok_or_else(|| anyhow!("`cargo metadata` returned no root package"))?;
+
+        analyze_package(&crate_dir, &cargo_toml_path, &metadata, root_pkg, options)
+    })
+}
+
+/// Analyze several crates, in order, with the same options.
+///
+/// The metadata of a workspace is read once, and reused for the crates that are members of it.
+/// The analysis fails if any of the crates cannot be analyzed.
+pub fn analyze_crates(paths: &[&str], options: &AnalysisOptions) -> Result<Vec<AnalysisResult>> {
+    on_analysis_thread(options, || analyze_each_crate(paths, options))
+}
+
+fn analyze_each_crate(paths: &[&str], options: &AnalysisOptions) -> Result<Vec<AnalysisResult>> {
+    let mut workspaces: Vec<Metadata> = vec![];
+    let mut results = vec![];
+    for path in paths {
+        let (crate_dir, cargo_toml_path) = resolve_manifest(path, options)?;
+        // cargo reports the manifest paths with symlinks resolved
+        let manifest = dunce::canonicalize(&cargo_toml_path)?;
+        let is_member = |metadata: &Metadata| workspace_member(metadata, &manifest).is_some();
+        let index = match workspaces.iter().position(is_member) {
+            Some(index) => index,
+            None => {
+                workspaces.push(read_metadata(&cargo_toml_path, options)?);
+                workspaces.len() - 1
+            }
+        };
+        let metadata = &workspaces[index];
+        let root_pkg = workspace_member(metadata, &manifest).ok_or_else(|| {
+            anyhow!(
+                "`cargo metadata` returned no package for {}",
+                cargo_toml_path.to_string_lossy()
+            )
+        })?;
+        let result = analyze_package(&crate_dir, &cargo_toml_path, metadata, root_pkg, options)
+            .context(format!("Error analyzing crate: {}", path))?;
+        results.push(result);
+    }
+    Ok(results)
+}
+
+/// The workspace member of the metadata with the given (canonical) manifest path
+fn workspace_member<'a>(metadata: &'a Metadata, manifest: &Path) -> Option<&'a Package> {
+    metadata
+        .packages
+        .iter()
+        .find(|pkg| pkg.manifest_path == manifest && metadata.workspace_members.contains(&pkg.id))
+}
+
+/// Read the metadata of the package with the given manifest, and of its workspace
+fn read_metadata(cargo_toml_path: &Path, options: &AnalysisOptions) -> Result<Metadata> {
+    let mut metadata_command = MetadataCommand::new();
+    metadata_command.manifest_path(cargo_toml_path);
+    exec_metadata(
+        &metadata_command,
+        options.metadata_timeout.unwrap_or(METADATA_TIMEOUT),
+        METADATA_ATTEMPTS,
+    )
+}
+
+/// Resolve the directory of a crate, and check that it has a ``Cargo.toml``
+fn resolve_manifest(path: &str, options: &AnalysisOptions) -> Result<(PathBuf, PathBuf)> {
     // make the path absolute
     // TODO we use dunce to canonicalize the path because otherwise there is issues with python's os.path.relpath on windows, but maybe we should fix this on the Python side
-    let crate_dir =
-        dunce::canonicalize(path).context(format!("Error resolving crate path: {}", path))?;
-    eprintln!("running new analyzer");
+    let crate_dir = if options.preserve_symlinks {
+        normalize_path(Path::new(path))
+    } else {
+        dunce::canonicalize(path)
+    }
+    .context(format!("Error resolving crate path: {}", path))?;
     // check the path is a directory
     if !crate_dir.is_dir() {
         return Err(anyhow!(
@@ -27,117 +945,304 @@ pub fn analyze_crate(path: &str) -> Result<AnalysisResult> {
             crate_dir.to_string_lossy()
         ));
     }
+    Ok((crate_dir, cargo_toml_path))
+}
 
-    // use `cargo_metadata` instead of implementing own TOML parser
-    let metadata = MetadataCommand::new()
-        .manifest_path(&cargo_toml_path)
-        .exec()
-        .context("Failed to run `cargo metadata`")?;
-
-    let root_pkg = metadata
-        .root_package()
-        .ok_or_else(|| anyhow!("`cargo metadata` returned no root package"))?;
-
+/// Analyze a package, given its metadata and that of its workspace
+fn analyze_package(
+    crate_dir: &Path,
+    cargo_toml_path: &Path,
+    metadata: &Metadata,
+    root_pkg: &Package,
+    options: &AnalysisOptions,
+) -> Result<AnalysisResult> {
     // Prefer library target; fall back to the first binary target
     let root_target: &Target = root_pkg
         .targets
         .iter()
         .find(|t| t.kind.contains(&"lib".into()))
-        .or_else(|| root_pkg.targets.iter().find(|t| t.kind.contains(&"bin".into())))
+        .or_else(|| {
+            root_pkg
+                .targets
+                .iter()
+                .find(|t| t.kind.contains(&"bin".into()))
+        })
         .ok_or_else(|| anyhow!("No lib or bin target defined in manifest"))?;
 
-    let crate_name = root_target.name.clone();
-    let root_module = PathBuf::from(&root_target.src_path);
+    // items are named by the crate name they are imported with, which is that of the target
+    // rather than the package, e.g. ``foo`` for ``[lib] name = "foo"`` in the package ``my-crate``;
+    // cargo reports the names of lib targets with ``-`` replaced, but not those of bin targets
+    let crate_name = options
+        .crate_name_override
+        .clone()
+        .unwrap_or_else(|| root_target.name.replace('-', "_"));
+    let mut root_module = PathBuf::from(&root_target.src_path);
+    if options.preserve_symlinks {
+        // cargo reports the source path with symlinks resolved, so re-root it on the crate directory
+        if let Ok(real_dir) = dunce::canonicalize(crate_dir) {
+            if let Ok(relative) = root_module.strip_prefix(&real_dir) {
+                root_module = crate_dir.join(relative);
+            }
+        }
+    }
 
-    let mut result = AnalysisResult::new(Crate {
-        name: crate_name.clone(),
-        version: root_pkg.version.to_string(), // workspace-aware
-    });
+    let mut result = AnalysisResult::new(crate_from_package(&crate_name, root_pkg));
 
     // check existence of the root module
     if !root_module.exists() {
         return Ok(result);
     }
 
-    // read the top-level module
-    let content = std::fs::read_to_string(&root_module)?;
-    let (module, structs, enums, functions) = Module::parse(
-        Some(&root_module),
-        &[&result.crate_.name],
-        &content,
-    )
-    .context(format!(
-        "Error parsing module {}",
-        root_module.to_string_lossy()
-    ))?;
+    let out_dir = match (&options.out_dir, options.run_build) {
+        (Some(out_dir), _) => Some(PathBuf::from(out_dir)),
+        (None, true) => match build_out_dir(cargo_toml_path, &root_pkg.id) {
+            Ok(out_dir) => out_dir,
+            Err(err) => {
+                result.warnings.push(AnalysisWarning {
+                    message: format!("Could not determine the OUT_DIR: {:#}", err),
+                    file: Some(cargo_toml_path.to_string_lossy().to_string()),
+                });
+                None
+            }
+        },
+        (None, false) => None,
+    };
 
-    let mut modules_to_read = module
-        .declarations
-        .iter()
-        .map(|s| {
-            (
-                root_module.parent().unwrap().to_path_buf(),
-                s.to_string(),
-                vec![result.crate_.name.clone()],
-            )
-        })
-        .collect::<Vec<_>>();
+    let cfg = CfgSet::new(&options.cfg);
+    result.crate_.no_std = analyze_modules(
+        &root_module,
+        &crate_name,
+        options,
+        &cfg,
+        out_dir.as_deref(),
+        &mut result,
+    )?;
 
-    result.modules.push(module);
-    result.structs.extend(structs);
-    result.enums.extend(enums);
-    result.functions.extend(functions);
+    analyze_extra_targets(root_pkg, options, &cfg, out_dir.as_deref(), &mut result);
+    if options.dependency_depth > 0 {
+        analyze_dependencies(metadata, root_pkg, options, &mut result);
+    }
 
-    // recursively find/read the public sub‑modules
-    let mut read_modules = vec![];
-    while let Some((parent_dir, module_name, parent)) = modules_to_read.pop() {
-        let (module_path, submodule_dir) = if parent_dir.join(&module_name).with_extension("rs").exists() {
-            (
-                parent_dir.join(&module_name).with_extension("rs"),
-                parent_dir.join(&module_name),
-            )
-        } else if parent_dir.join(&module_name).join("mod.rs").exists() {
-            (
-                parent_dir.join(&module_name).join("mod.rs"),
-                parent_dir.to_path_buf(),
-            )
-        } else {
-            // TODO warn about missing module?
-            continue;
-        };
+    result.resolve_visibility();
+    result.resolve_sealed_traits();
+    result.resolve_deref_targets();
+    result.resolve_cfg_info();
+    if options.detect_recursive_types {
+        result.resolve_recursive_types();
+    }
+    if !options.cfg.is_empty() {
+        result.retain_cfg(&cfg);
+    }
+    if !options.include_private {
+        result.retain_public();
+    }
+    if let Some(files) = &options.changed_files {
+        result.retain_files(files);
+    }
+    if options.warn_undocumented {
+        let undocumented = result.undocumented_items();
+        result.warnings.extend(undocumented);
+    }
+    if options.warn_missing_safety {
+        let missing_safety = result.missing_safety_sections();
+        result.warnings.extend(missing_safety);
+    }
+    if options.warn_broken_examples {
+        let broken = result.broken_examples();
+        result.warnings.extend(broken);
+    }
 
-        if read_modules.contains(&module_path) {
-            continue;
+    if options.strict && !result.warnings.is_empty() {
+        return Err(StrictModeError {
+            warnings: result.warnings,
         }
-        read_modules.push(module_path.clone());
+        .into());
+    }
 
-        let content = std::fs::read_to_string(&module_path)?;
-        let path: Vec<String> = [&parent[..], &[module_name]].concat();
-        let (module, structs, enums, functions) = Module::parse(
-            Some(&module_path),
-            &path.iter().map(|s| s.as_str()).collect::<Vec<&str>>(),
-            &content,
-        )
-        .context(format!(
-            "Error parsing module {}",
-            module_path.to_string_lossy()
-        ))?;
+    if !options.preserve_signature_formatting {
+        result.drop_source_signatures();
+    }
+    if !options.full_initializers {
+        result.drop_full_initializers();
+    }
+    if options.hide_phantom_fields {
+        result.drop_phantom_fields();
+    }
+    if options.expand_type_aliases {
+        result.expand_type_aliases();
+    }
+    if let Some(resolver) = options.external_doc_resolver() {
+        result.link_external_types(&resolver);
+    }
+    if let Some(max_depth) = options.max_type_depth {
+        result.truncate_type_depth(max_depth);
+    }
+    if let Some(limit) = options.max_render_length {
+        result.truncate_rendered(limit);
+    }
 
-        modules_to_read.extend(
-            module
-                .declarations
-                .iter()
-                .map(|s| (submodule_dir.clone(), s.to_string(), path.clone())),
-        );
-        result.modules.push(module);
-        result.structs.extend(structs);
-        result.enums.extend(enums);
-        result.functions.extend(functions);
+    if let Some(rewriter) = &options.path_rewriter {
+        result.rewrite_paths(|path| rewriter.rewrite(path));
     }
 
+    result.sort_items();
+
     Ok(result)
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// The ``cargo`` binary, needed to read the crate metadata, could not be found
+pub struct CargoNotFoundError;
+
+impl std::fmt::Display for CargoNotFoundError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "`cargo` was not found, but is required to read the crate metadata; \
+            install it, add it to the PATH, or set the CARGO environment variable to its location"
+        )
+    }
+}
+
+impl std::error::Error for CargoNotFoundError {}
+
+#[derive(Debug, Clone)]
+/// The analysis produced warnings, and was run with ``strict`` set
+pub struct StrictModeError {
+    pub warnings: Vec<AnalysisWarning>,
+}
+
+impl std::fmt::Display for StrictModeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the analysis produced {} warning(s) in strict mode:",
+            self.warnings.len()
+        )?;
+        for warning in &self.warnings {
+            write!(f, "\n- {}", warning)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for StrictModeError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// ``cargo metadata`` did not finish within the timeout, in any of its attempts
+pub struct MetadataTimeoutError {
+    pub timeout: Duration,
+    pub attempts: usize,
+}
+
+impl std::fmt::Display for MetadataTimeoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "`cargo metadata` did not finish within {:?} in {} attempt(s); \
+            it may be waiting for a lock on the package cache, or for the network",
+            self.timeout, self.attempts
+        )
+    }
+}
+
+impl std::error::Error for MetadataTimeoutError {}
+
+/// Run ``cargo metadata`` up to ``attempts`` times, until it finishes within the timeout
+fn exec_metadata(
+    command: &MetadataCommand,
+    timeout: Duration,
+    attempts: usize,
+) -> Result<Metadata> {
+    for _ in 0..attempts {
+        if let Some(metadata) = exec_with_timeout(command, timeout).map_err(metadata_error)? {
+            return Ok(metadata);
+        }
+    }
+    Err(MetadataTimeoutError { timeout, attempts }.into())
+}
+
+/// Run ``cargo metadata``, killing it if it does not finish within the timeout,
+/// in which case ``None`` is returned
+fn exec_with_timeout(
+    command: &MetadataCommand,
+    timeout: Duration,
+) -> cargo_metadata::Result<Option<Metadata>> {
+    let mut child = command
+        .cargo_command()
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    // the output is read on separate threads, so that ``cargo`` cannot block on a full pipe
+    let stdout = read_pipe(child.stdout.take());
+    let stderr = read_pipe(child.stderr.take());
+    let deadline = Instant::now() + timeout;
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(None);
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    };
+    let stdout = stdout.join().unwrap_or_default();
+    if !status.success() {
+        let stderr = stderr.join().unwrap_or_default();
+        return Err(cargo_metadata::Error::CargoMetadata {
+            stderr: String::from_utf8_lossy(&stderr).to_string(),
+        });
+    }
+    let stdout = std::str::from_utf8(&stdout)?;
+    let json = stdout
+        .lines()
+        .find(|line| line.starts_with('{'))
+        .ok_or(cargo_metadata::Error::NoJson)?;
+    MetadataCommand::parse(json).map(Some)
+}
+
+/// Read a pipe of a child process to its end, on a separate thread
+fn read_pipe(pipe: Option<impl Read + Send + 'static>) -> std::thread::JoinHandle<Vec<u8>> {
+    std::thread::spawn(move || {
+        let mut output = vec![];
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut output);
+        }
+        output
+    })
+}
+
+/// Convert an error from ``cargo metadata``,
+/// distinguishing the case where ``cargo`` itself could not be found
+fn metadata_error(err: cargo_metadata::Error) -> anyhow::Error {
+    match err {
+        cargo_metadata::Error::Io(io_err) if io_err.kind() == std::io::ErrorKind::NotFound => {
+            anyhow::Error::new(CargoNotFoundError)
+        }
+        err => anyhow::Error::new(err).context("Failed to run `cargo metadata`"),
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// A non-fatal problem encountered during the analysis
+pub struct AnalysisWarning {
+    pub message: String,
+    /// The file the problem relates to, if any
+    pub file: Option<String>,
+}
+
+impl std::fmt::Display for AnalysisWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.file {
+            Some(file) => write!(f, "{}: {}", file, self.message),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 /// Result from a crate analysis
 pub struct AnalysisResult {
@@ -146,6 +1251,22 @@ pub struct AnalysisResult {
     pub structs: Vec<Struct>,
     pub enums: Vec<Enum>,
     pub functions: Vec<Function>,
+    pub traits: Vec<Trait>,
+    pub type_aliases: Vec<TypeAlias>,
+    pub impls: Vec<Impl>,
+    pub extern_blocks: Vec<ExternBlock>,
+    #[serde(default)]
+    pub macros: Vec<Macro>,
+    #[serde(default)]
+    pub consts: Vec<Const>,
+    #[serde(default)]
+    pub statics: Vec<Static>,
+    /// The fully qualified names of modules that were excluded from the analysis
+    pub excluded_modules: Vec<String>,
+    /// Non-fatal problems encountered during the analysis
+    pub warnings: Vec<AnalysisWarning>,
+    /// The dependency crates whose items were also analyzed
+    pub dependencies: Vec<Crate>,
 }
 
 impl AnalysisResult {
@@ -156,17 +1277,1044 @@ impl AnalysisResult {
             structs: vec![],
             enums: vec![],
             functions: vec![],
+            traits: vec![],
+            type_aliases: vec![],
+            impls: vec![],
+            extern_blocks: vec![],
+            macros: vec![],
+            consts: vec![],
+            statics: vec![],
+            excluded_modules: vec![],
+            warnings: vec![],
+            dependencies: vec![],
         }
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use insta::assert_yaml_snapshot;
 
-    #[test]
-    fn test_analyze_crate() -> Result<()> {
+    /// Rewrite the fully qualified names of all items, including those of their members,
+    /// the modules that items and impls are declared in, and the types and traits that methods belong to.
+    ///
+    /// Paths written in the source that resolve to items of the analysis, e.g. in signatures,
+    /// ``use`` declarations, supertraits and impl headers, are rewritten to resolve to the new names.
+    pub fn rewrite_paths(&mut self, rewriter: impl Fn(&[String]) -> Vec<String>) {
+        self.rewrite_written_paths(&rewriter);
+        let mut declarations: BTreeMap<Vec<String>, Vec<String>> = BTreeMap::new();
+        for module in &self.modules {
+            for name in &module.declarations {
+                let child = rewriter(&[module.path.clone(), vec![name.clone()]].concat());
+                if let Some((name, parent)) = child.split_last() {
+                    let names = declarations.entry(parent.to_vec()).or_default();
+                    if !names.contains(name) {
+                        names.push(name.clone());
+                    }
+                }
+            }
+        }
+        let rewrite_str = |path: &str| {
+            rewriter(&path.split("::").map(String::from).collect::<Vec<_>>()).join("::")
+        };
+        // the name of an item is the last segment of its path, so may also change
+        let rewrite = |name: &mut String, path: &mut Vec<String>| {
+            *path = rewriter(path);
+            *name = path.last().cloned().unwrap_or_default();
+        };
+        let rewrite_function = |func: &mut Function| {
+            rewrite(&mut func.name, &mut func.path);
+            func.owner = match &func.owner {
+                FunctionOwner::Free => FunctionOwner::Free,
+                FunctionOwner::Inherent(type_) => FunctionOwner::Inherent(rewrite_str(type_)),
+                FunctionOwner::Trait(trait_) => FunctionOwner::Trait(rewrite_str(trait_)),
+                FunctionOwner::TraitImpl { type_, trait_ } => FunctionOwner::TraitImpl {
+                    type_: rewrite_str(type_),
+                    trait_: trait_.clone(),
+                },
+            };
+        };
+        for module in self.modules.iter_mut() {
+            rewrite(&mut module.name, &mut module.path);
+            module.declarations = declarations.remove(&module.path).unwrap_or_default();
+        }
+        for struct_ in self.structs.iter_mut() {
+            rewrite(&mut struct_.name, &mut struct_.path);
+            for field in struct_.fields.iter_mut() {
+                rewrite(&mut field.name, &mut field.path);
+            }
+        }
+        for enum_ in self.enums.iter_mut() {
+            rewrite(&mut enum_.name, &mut enum_.path);
+            for variant in enum_.variants.iter_mut() {
+                rewrite(&mut variant.name, &mut variant.path);
+                for field in variant.fields.iter_mut() {
+                    rewrite(&mut field.name, &mut field.path);
+                }
+            }
+        }
+        self.functions.iter_mut().for_each(rewrite_function);
+        for trait_ in self.traits.iter_mut() {
+            rewrite(&mut trait_.name, &mut trait_.path);
+            trait_.functions.iter_mut().for_each(rewrite_function);
+        }
+        for alias in self.type_aliases.iter_mut() {
+            rewrite(&mut alias.name, &mut alias.path);
+        }
+        for macro_ in self.macros.iter_mut() {
+            rewrite(&mut macro_.name, &mut macro_.path);
+        }
+        for const_ in self.consts.iter_mut() {
+            rewrite(&mut const_.name, &mut const_.path);
+        }
+        for static_ in self.statics.iter_mut() {
+            rewrite(&mut static_.name, &mut static_.path);
+        }
+        for impl_ in self.impls.iter_mut() {
+            impl_.parent = rewriter(&impl_.parent);
+            impl_.functions.iter_mut().for_each(rewrite_function);
+        }
+        for block in self.extern_blocks.iter_mut() {
+            block.parent = rewriter(&block.parent);
+            block.functions.iter_mut().for_each(rewrite_function);
+            for static_ in block.statics.iter_mut() {
+                rewrite(&mut static_.name, &mut static_.path);
+            }
+        }
+        for module in self.excluded_modules.iter_mut() {
+            *module = rewrite_str(module);
+        }
+    }
+
+    /// Rewrite the paths written in the source that resolve to items or modules of the analysis,
+    /// as for ``rewrite_paths``, before the fully qualified names are rewritten
+    fn rewrite_written_paths(&mut self, rewriter: &impl Fn(&[String]) -> Vec<String>) {
+        let known: BTreeSet<Vec<String>> = self
+            .item_kinds()
+            .into_keys()
+            .map(|path| path.split("::").map(String::from).collect())
+            .chain(self.modules.iter().map(|m| m.path.clone()))
+            .collect();
+        let rewrite =
+            |module: &[String], path: &str| rewrite_written_path(rewriter, &known, module, path);
+        let rewrite_type = |module: &[String], type_: &str| {
+            rewrite_leading_path(type_, |path| rewrite(module, path))
+        };
+        self.map_signatures_in(|module, signature| {
+            signature
+                .iter()
+                .map(
+                    |segment| match segment.path().and_then(|p| rewrite_type(module, p)) {
+                        Some(path) => segment.with_content(path),
+                        None => segment.clone(),
+                    },
+                )
+                .collect()
+        });
+        for module in self.modules.iter_mut() {
+            for use_ in module.uses.iter_mut() {
+                if let Some(path) = rewrite(&module.path, &use_.path.join("::")) {
+                    use_.path = path.split("::").map(String::from).collect();
+                }
+            }
+        }
+        for trait_ in self.traits.iter_mut() {
+            let module = &trait_.path[..trait_.path.len() - 1];
+            for supertrait in trait_.supertraits.iter_mut() {
+                if let Some(rewritten) = rewrite_type(module, supertrait) {
+                    *supertrait = rewritten;
+                }
+            }
+        }
+        for impl_ in self.impls.iter_mut() {
+            let module = &impl_.parent;
+            if let Some(base) = impl_.base_type.as_mut() {
+                if let Some(rewritten) = rewrite(module, base) {
+                    *base = rewritten;
+                }
+            }
+            for segment in impl_.trait_.iter_mut().flatten() {
+                if let Some(path) = segment.path().and_then(|p| rewrite_type(module, p)) {
+                    *segment = segment.with_content(path);
+                }
+            }
+            for function in impl_.functions.iter_mut() {
+                if let FunctionOwner::TraitImpl { trait_, .. } = &mut function.owner {
+                    if let Some(rewritten) = rewrite_type(module, trait_) {
+                        *trait_ = rewritten;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Compute whether modules and items are reachable from a crate root
+    /// through public modules and ``pub use`` re-exports.
+    ///
+    /// This expects ``effectively_public`` to initially reflect the local ``pub`` keyword
+    /// of each declaration, as set when parsing, and so should only be called once.
+    pub fn resolve_visibility(&mut self) {
+        let all_modules: BTreeSet<&[String]> =
+            self.modules.iter().map(|m| m.path.as_slice()).collect();
+        let local_modules: BTreeSet<&[String]> = self
+            .modules
+            .iter()
+            .filter(|m| m.effectively_public)
+            .map(|m| m.path.as_slice())
+            .collect();
+        let local_items: BTreeSet<&[String]> = self
+            .structs
+            .iter()
+            .filter(|s| s.effectively_public)
+            .map(|s| s.path.as_slice())
+            .chain(
+                self.enums
+                    .iter()
+                    .filter(|e| e.effectively_public)
+                    .map(|e| e.path.as_slice()),
+            )
+            .chain(
+                self.functions
+                    .iter()
+                    .filter(|f| f.effectively_public)
+                    .map(|f| f.path.as_slice()),
+            )
+            .chain(
+                self.traits
+                    .iter()
+                    .filter(|t| t.effectively_public)
+                    .map(|t| t.path.as_slice()),
+            )
+            .chain(
+                self.type_aliases
+                    .iter()
+                    .filter(|a| a.effectively_public)
+                    .map(|a| a.path.as_slice()),
+            )
+            .chain(
+                self.consts
+                    .iter()
+                    .filter(|c| c.effectively_public)
+                    .map(|c| c.path.as_slice()),
+            )
+            .chain(
+                self.statics
+                    .iter()
+                    .filter(|s| s.effectively_public)
+                    .map(|s| s.path.as_slice()),
+            )
+            .collect();
+        let parent = |path: &[String]| path[..path.len() - 1].to_vec();
+
+        // the crate roots are always reachable
+        let mut reachable: BTreeSet<Vec<String>> = self
+            .modules
+            .iter()
+            .filter(|m| m.path.len() == 1)
+            .map(|m| m.path.clone())
+            .collect();
+        let mut reexported: BTreeSet<Vec<String>> = BTreeSet::new();
+        loop {
+            let mut changed = false;
+            for path in &local_modules {
+                if path.len() > 1 && reachable.contains(&parent(path)) {
+                    changed |= reachable.insert(path.to_vec());
+                }
+            }
+            for module in &self.modules {
+                if !reachable.contains(&module.path) {
+                    continue;
+                }
+                let module_path = module.path.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+                for use_ in module.uses.iter().filter(|u| u.public) {
+                    let target = resolve_relative_path(&module_path, &use_.path.join("::"));
+                    if use_.name.is_some() {
+                        if all_modules.contains(target.as_slice()) {
+                            changed |= reachable.insert(target);
+                        } else {
+                            changed |= reexported.insert(target);
+                        }
+                    } else {
+                        // a glob re-exports the public items and sub-modules of the target
+                        for path in local_modules.iter().filter(|p| p.len() > 1) {
+                            if parent(path) == target {
+                                changed |= reachable.insert(path.to_vec());
+                            }
+                        }
+                        for path in local_items.iter() {
+                            if parent(path) == target {
+                                changed |= reexported.insert(path.to_vec());
+                            }
+                        }
+                    }
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        let is_public = |path: &[String], local: bool| {
+            (local && reachable.contains(&parent(path))) || reexported.contains(path)
+        };
+        for module in self.modules.iter_mut() {
+            module.effectively_public = reachable.contains(&module.path);
+        }
+        for struct_ in self.structs.iter_mut() {
+            struct_.effectively_public = is_public(&struct_.path, struct_.effectively_public);
+        }
+        for enum_ in self.enums.iter_mut() {
+            enum_.effectively_public = is_public(&enum_.path, enum_.effectively_public);
+        }
+        for function in self.functions.iter_mut() {
+            function.effectively_public = is_public(&function.path, function.effectively_public);
+        }
+        for trait_ in self.traits.iter_mut() {
+            trait_.effectively_public = is_public(&trait_.path, trait_.effectively_public);
+        }
+        for alias in self.type_aliases.iter_mut() {
+            alias.effectively_public = is_public(&alias.path, alias.effectively_public);
+        }
+        for const_ in self.consts.iter_mut() {
+            const_.effectively_public = is_public(&const_.path, const_.effectively_public);
+        }
+        for static_ in self.statics.iter_mut() {
+            static_.effectively_public = is_public(&static_.path, static_.effectively_public);
+        }
+    }
+
+    /// Record the ``Deref`` targets of the structs and enums, from the ``Deref`` impls of the analysis
+    pub fn resolve_deref_targets(&mut self) {
+        let kinds = self.item_kinds();
+        let targets: BTreeMap<String, TypeSignature> = self
+            .impls
+            .iter()
+            .filter_map(|impl_| {
+                let target = impl_.deref_target()?;
+                Some((self.impl_owner(&kinds, impl_)?, target.clone()))
+            })
+            .collect();
+        for struct_ in self.structs.iter_mut() {
+            struct_.deref_target = targets.get(&struct_.path_str()).cloned();
+        }
+        for enum_ in self.enums.iter_mut() {
+            enum_.deref_target = targets.get(&enum_.path_str()).cloned();
+        }
+    }
+
+    /// Combine the ``#[cfg(...)]`` predicates of modules and items with those of their enclosing modules,
+    /// so that ``cfg_info`` is the predicate under which the module or item is available,
+    /// e.g. ``all(feature = "net", unix)`` for an item with ``#[cfg(unix)]`` in a module with ``#[cfg(feature = "net")]``.
+    ///
+    /// This expects ``cfg_info`` to initially reflect the attributes of each module or item,
+    /// as set when parsing, and so should only be called once.
+    pub fn resolve_cfg_info(&mut self) {
+        let own: BTreeMap<Vec<String>, Option<String>> = self
+            .modules
+            .iter()
+            .map(|m| (m.path.clone(), m.cfg_info.clone()))
+            .collect();
+        // the predicate of a module path, including those of its ancestors
+        let effective = |path: &[String]| {
+            combine_cfg((1..=path.len()).map(|i| own.get(&path[..i]).and_then(|c| c.as_deref())))
+        };
+        let combine = |module: &[String], cfg_info: &mut Option<String>| {
+            *cfg_info = combine_cfg([effective(module).as_deref(), cfg_info.as_deref()]);
+        };
+        let parent = |path: &[String]| path[..path.len().saturating_sub(1)].to_vec();
+        for module in self.modules.iter_mut() {
+            module.cfg_info = effective(&module.path);
+        }
+        for struct_ in self.structs.iter_mut() {
+            combine(&parent(&struct_.path), &mut struct_.cfg_info);
+        }
+        for enum_ in self.enums.iter_mut() {
+            combine(&parent(&enum_.path), &mut enum_.cfg_info);
+        }
+        for function in self.functions.iter_mut() {
+            combine(&parent(&function.path), &mut function.cfg_info);
+        }
+        for trait_ in self.traits.iter_mut() {
+            let module = parent(&trait_.path);
+            combine(&module, &mut trait_.cfg_info);
+            for function in trait_.functions.iter_mut() {
+                combine(&module, &mut function.cfg_info);
+            }
+        }
+        for alias in self.type_aliases.iter_mut() {
+            combine(&parent(&alias.path), &mut alias.cfg_info);
+        }
+        for macro_ in self.macros.iter_mut() {
+            combine(&parent(&macro_.path), &mut macro_.cfg_info);
+        }
+        for const_ in self.consts.iter_mut() {
+            combine(&parent(&const_.path), &mut const_.cfg_info);
+        }
+        for static_ in self.statics.iter_mut() {
+            combine(&parent(&static_.path), &mut static_.cfg_info);
+        }
+        for impl_ in self.impls.iter_mut() {
+            for function in impl_.functions.iter_mut() {
+                combine(&impl_.parent, &mut function.cfg_info);
+            }
+        }
+        for block in self.extern_blocks.iter_mut() {
+            for function in block.functions.iter_mut() {
+                combine(&block.parent, &mut function.cfg_info);
+            }
+        }
+    }
+
+    /// Drop the fields and variants whose ``#[cfg(...)]`` predicate is not satisfied,
+    /// keeping their structs and enums, even if no fields or variants remain
+    pub fn retain_cfg(&mut self, cfg: &CfgSet) {
+        let enabled = |predicate: &Option<String>| {
+            predicate.as_deref().is_none_or(|predicate| {
+                CfgPredicate::parse_str(predicate).is_some_and(|p| cfg.evaluate(&p))
+            })
+        };
+        for struct_ in self.structs.iter_mut() {
+            struct_.fields.retain(|f| enabled(&f.cfg));
+        }
+        for enum_ in self.enums.iter_mut() {
+            enum_.variants.retain(|v| enabled(&v.cfg));
+            for variant in enum_.variants.iter_mut() {
+                variant.fields.retain(|f| enabled(&f.cfg));
+            }
+        }
+    }
+
+    /// Mark the public traits that are sealed,
+    /// i.e. have a supertrait that is a local trait that is not public.
+    ///
+    /// This recognises the pattern::
+    ///
+    ///     mod private {
+    ///         pub trait Sealed {}
+    ///     }
+    ///     pub trait MyTrait: private::Sealed {}
+    ///
+    /// and so should be called after ``resolve_visibility`` and before ``retain_public``.
+    pub fn resolve_sealed_traits(&mut self) {
+        let traits: BTreeMap<String, &Trait> =
+            self.traits.iter().map(|t| (t.path_str(), t)).collect();
+        let kinds = self.item_kinds();
+        let sealed: BTreeSet<String> = self
+            .traits
+            .iter()
+            .filter(|t| t.effectively_public)
+            .filter(|t| {
+                let parent = t.path[..t.path.len() - 1]
+                    .iter()
+                    .map(|s| s.as_str())
+                    .collect::<Vec<_>>();
+                t.supertraits.iter().any(|supertrait| {
+                    self.resolve_supertrait(&traits, &kinds, &parent, supertrait)
+                        .is_some_and(|s| !s.effectively_public)
+                })
+            })
+            .map(|t| t.path_str())
+            .collect();
+        for trait_ in self.traits.iter_mut() {
+            trait_.sealed = sealed.contains(&trait_.path_str());
+        }
+    }
+
+    /// Drop the modules and items that are not effectively public, and the private fields of structs.
+    ///
+    /// Impls are kept if the type they are attributed to is kept,
+    /// or, for types that are not part of the analysis, if the module they are declared in is kept.
+    /// Extern blocks are kept if the module they are declared in is kept.
+    pub fn retain_public(&mut self) {
+        let analyzed_types: BTreeSet<Vec<String>> = self
+            .structs
+            .iter()
+            .map(|s| s.path.clone())
+            .chain(self.enums.iter().map(|e| e.path.clone()))
+            .chain(self.traits.iter().map(|t| t.path.clone()))
+            .collect();
+        self.modules.retain(|m| m.effectively_public);
+        self.structs.retain(|s| s.effectively_public);
+        for struct_ in self.structs.iter_mut() {
+            struct_.fields.retain(|f| f.public);
+        }
+        self.enums.retain(|e| e.effectively_public);
+        self.functions.retain(|f| f.effectively_public);
+        self.traits.retain(|t| t.effectively_public);
+        self.type_aliases.retain(|a| a.effectively_public);
+        self.macros.retain(|m| m.effectively_public);
+        self.consts.retain(|c| c.effectively_public);
+        self.statics.retain(|s| s.effectively_public);
+
+        let kept_modules: BTreeSet<Vec<String>> =
+            self.modules.iter().map(|m| m.path.clone()).collect();
+        let kept_types: BTreeSet<Vec<String>> = self
+            .structs
+            .iter()
+            .map(|s| s.path.clone())
+            .chain(self.enums.iter().map(|e| e.path.clone()))
+            .chain(self.traits.iter().map(|t| t.path.clone()))
+            .collect();
+        self.impls.retain(|impl_| {
+            let parent = impl_.parent.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+            match impl_
+                .base_type
+                .as_ref()
+                .map(|base| resolve_relative_path(&parent, base))
+            {
+                Some(owner) if analyzed_types.contains(&owner) => kept_types.contains(&owner),
+                _ => kept_modules.contains(&impl_.parent),
+            }
+        });
+        self.extern_blocks
+            .retain(|block| kept_modules.contains(&block.parent));
+    }
+
+    /// A warning for each public module and item of the crate (excluding dependencies)
+    /// whose docstring is empty, with its file
+    pub fn undocumented_items(&self) -> Vec<AnalysisWarning> {
+        let mut items: Vec<(&str, String, &Option<String>, &str)> = vec![];
+        for module in self.modules.iter().filter(|m| m.effectively_public) {
+            items.push(("module", module.path_str(), &module.file, &module.docstring));
+        }
+        for struct_ in self.structs.iter().filter(|s| s.effectively_public) {
+            items.push((
+                "struct",
+                struct_.path_str(),
+                &struct_.file,
+                &struct_.docstring,
+            ));
+        }
+        for enum_ in self.enums.iter().filter(|e| e.effectively_public) {
+            items.push(("enum", enum_.path_str(), &enum_.file, &enum_.docstring));
+        }
+        for function in self.functions.iter().filter(|f| f.effectively_public) {
+            items.push((
+                "function",
+                function.path_str(),
+                &function.file,
+                &function.docstring,
+            ));
+        }
+        for trait_ in self.traits.iter().filter(|t| t.effectively_public) {
+            items.push(("trait", trait_.path_str(), &trait_.file, &trait_.docstring));
+        }
+        for alias in self.type_aliases.iter().filter(|a| a.effectively_public) {
+            items.push((
+                "type alias",
+                alias.path_str(),
+                &alias.file,
+                &alias.docstring,
+            ));
+        }
+        for macro_ in self.macros.iter().filter(|m| m.effectively_public) {
+            items.push(("macro", macro_.path_str(), &macro_.file, &macro_.docstring));
+        }
+        for const_ in self.consts.iter().filter(|c| c.effectively_public) {
+            items.push((
+                "constant",
+                const_.path_str(),
+                &const_.file,
+                &const_.docstring,
+            ));
+        }
+        for static_ in self.statics.iter().filter(|s| s.effectively_public) {
+            items.push((
+                "static",
+                static_.path_str(),
+                &static_.file,
+                &static_.docstring,
+            ));
+        }
+        let crate_prefix = format!("{}::", self.crate_.name);
+        items
+            .into_iter()
+            .filter(|(_, path, _, _)| *path == self.crate_.name || path.starts_with(&crate_prefix))
+            .filter(|(_, _, _, docstring)| docstring.trim().is_empty())
+            .map(|(kind, path, file, _)| AnalysisWarning {
+                message: format!("Public {} `{}` has no docstring", kind, path),
+                file: file.clone(),
+            })
+            .collect()
+    }
+
+    /// A warning for each public ``unsafe`` function, method (of a trait or inherent impl)
+    /// and trait of the crate (excluding dependencies) whose docstring has no ``# Safety`` section, with its file.
+    ///
+    /// Methods of trait impls are not checked, since their contract is documented on the trait.
+    pub fn missing_safety_sections(&self) -> Vec<AnalysisWarning> {
+        let functions = self.functions.iter().map(|f| ("function", f));
+        let methods = self
+            .traits
+            .iter()
+            .filter(|t| t.effectively_public)
+            .flat_map(|t| t.functions.iter())
+            .chain(
+                self.impls
+                    .iter()
+                    .filter(|i| i.trait_.is_none())
+                    .flat_map(|i| i.functions.iter()),
+            )
+            .map(|f| ("method", f));
+        let mut items: Vec<(&str, String, &Option<String>, &str)> = functions
+            .chain(methods)
+            .filter(|(_, f)| f.is_unsafe && f.effectively_public)
+            .map(|(kind, f)| (kind, f.path_str(), &f.file, f.docstring.as_str()))
+            .collect();
+        for trait_ in self
+            .traits
+            .iter()
+            .filter(|t| t.is_unsafe && t.effectively_public)
+        {
+            items.push(("trait", trait_.path_str(), &trait_.file, &trait_.docstring));
+        }
+        let crate_prefix = format!("{}::", self.crate_.name);
+        items
+            .into_iter()
+            .filter(|(_, path, _, _)| path.starts_with(&crate_prefix))
+            .filter(|(_, _, _, docstring)| DocSections::parse(docstring).safety.is_none())
+            .map(|(kind, path, file, _)| AnalysisWarning {
+                message: format!("Unsafe {} `{}` has no `# Safety` section", kind, path),
+                file: file.clone(),
+            })
+            .collect()
+    }
+
+    /// A warning for each Rust code example in the docstrings of the modules, items and methods of the crate
+    /// (excluding dependencies) that does not parse, with the index of the example (from 1) and its file
+    pub fn broken_examples(&self) -> Vec<AnalysisWarning> {
+        let mut items: Vec<(&str, String, &Option<String>, &str)> = vec![];
+        for module in &self.modules {
+            items.push(("module", module.path_str(), &module.file, &module.docstring));
+        }
+        for struct_ in &self.structs {
+            items.push((
+                "struct",
+                struct_.path_str(),
+                &struct_.file,
+                &struct_.docstring,
+            ));
+        }
+        for enum_ in &self.enums {
+            items.push(("enum", enum_.path_str(), &enum_.file, &enum_.docstring));
+        }
+        for function in &self.functions {
+            items.push((
+                "function",
+                function.path_str(),
+                &function.file,
+                &function.docstring,
+            ));
+        }
+        for trait_ in &self.traits {
+            items.push(("trait", trait_.path_str(), &trait_.file, &trait_.docstring));
+        }
+        let methods = self
+            .traits
+            .iter()
+            .flat_map(|t| t.functions.iter())
+            .chain(self.impls.iter().flat_map(|i| i.functions.iter()));
+        for method in methods {
+            items.push(("method", method.path_str(), &method.file, &method.docstring));
+        }
+        for alias in &self.type_aliases {
+            items.push((
+                "type alias",
+                alias.path_str(),
+                &alias.file,
+                &alias.docstring,
+            ));
+        }
+        for macro_ in &self.macros {
+            items.push(("macro", macro_.path_str(), &macro_.file, &macro_.docstring));
+        }
+        for const_ in &self.consts {
+            items.push((
+                "constant",
+                const_.path_str(),
+                &const_.file,
+                &const_.docstring,
+            ));
+        }
+        for static_ in &self.statics {
+            items.push((
+                "static",
+                static_.path_str(),
+                &static_.file,
+                &static_.docstring,
+            ));
+        }
+        let crate_prefix = format!("{}::", self.crate_.name);
+        items
+            .into_iter()
+            .filter(|(_, path, _, _)| *path == self.crate_.name || path.starts_with(&crate_prefix))
+            .flat_map(|(kind, path, file, docstring)| {
+                broken_examples(docstring)
+                    .into_iter()
+                    .map(move |(index, err)| AnalysisWarning {
+                        message: format!(
+                            "Code example {} of {} `{}` does not parse: {}",
+                            index + 1,
+                            kind,
+                            path,
+                            err
+                        ),
+                        file: file.clone(),
+                    })
+            })
+            .collect()
+    }
+
+    /// Drop the items that are not declared in one of the given files,
+    /// keeping the modules declared in the files and the ancestors of the kept modules and items.
+    ///
+    /// A file matches if it is the same as, or ends with, one of the given paths,
+    /// e.g. ``src/net/socket.rs`` matches ``/path/to/crate/src/net/socket.rs``.
+    pub fn retain_files(&mut self, files: &[PathBuf]) {
+        let matches = |file: &Option<String>| {
+            file.as_ref()
+                .is_some_and(|file| files.iter().any(|f| Path::new(file).ends_with(f)))
+        };
+        self.structs.retain(|s| matches(&s.file));
+        self.enums.retain(|e| matches(&e.file));
+        self.functions.retain(|f| matches(&f.file));
+        self.traits.retain(|t| matches(&t.file));
+        self.type_aliases.retain(|a| matches(&a.file));
+        self.macros.retain(|m| matches(&m.file));
+        self.consts.retain(|c| matches(&c.file));
+        self.statics.retain(|s| matches(&s.file));
+        self.impls.retain(|i| matches(&i.file));
+        self.extern_blocks.retain(|b| matches(&b.file));
+
+        let mut kept_modules: BTreeSet<&[String]> = BTreeSet::new();
+        let item_modules = self
+            .modules
+            .iter()
+            .filter(|m| matches(&m.file))
+            .map(|m| m.path.as_slice())
+            .chain(self.structs.iter().map(|s| &s.path[..s.path.len() - 1]))
+            .chain(self.enums.iter().map(|e| &e.path[..e.path.len() - 1]))
+            .chain(self.functions.iter().map(|f| &f.path[..f.path.len() - 1]))
+            .chain(self.traits.iter().map(|t| &t.path[..t.path.len() - 1]))
+            .chain(
+                self.type_aliases
+                    .iter()
+                    .map(|a| &a.path[..a.path.len() - 1]),
+            )
+            .chain(self.macros.iter().map(|m| &m.path[..m.path.len() - 1]))
+            .chain(self.consts.iter().map(|c| &c.path[..c.path.len() - 1]))
+            .chain(self.statics.iter().map(|s| &s.path[..s.path.len() - 1]))
+            .chain(self.impls.iter().map(|i| i.parent.as_slice()))
+            .chain(self.extern_blocks.iter().map(|b| b.parent.as_slice()));
+        for path in item_modules {
+            for end in 1..=path.len() {
+                kept_modules.insert(&path[..end]);
+            }
+        }
+        let kept_modules: BTreeSet<Vec<String>> =
+            kept_modules.into_iter().map(|p| p.to_vec()).collect();
+        self.modules.retain(|m| kept_modules.contains(&m.path));
+    }
+
+    /// Truncate all rendered values and signatures to at most ``limit`` characters
+    pub fn truncate_rendered(&mut self, limit: usize) {
+        self.map_signatures(|signature| truncate_signature(signature, limit));
+        let truncate_function = |func: &mut Function| {
+            for param in func.generics.params.iter_mut() {
+                *param = truncate(param, limit);
+            }
+            if let Some(source) = func.source_signature.as_mut() {
+                *source = truncate(source, limit);
+            }
+        };
+        for enum_ in self.enums.iter_mut() {
+            for variant in enum_.variants.iter_mut() {
+                if let Some(discriminant) = variant.discriminant.as_mut() {
+                    *discriminant = truncate(discriminant, limit);
+                }
+            }
+        }
+        self.functions.iter_mut().for_each(truncate_function);
+        for trait_ in self.traits.iter_mut() {
+            trait_.functions.iter_mut().for_each(truncate_function);
+        }
+        for impl_ in self.impls.iter_mut() {
+            impl_.functions.iter_mut().for_each(truncate_function);
+        }
+        for block in self.extern_blocks.iter_mut() {
+            block.functions.iter_mut().for_each(truncate_function);
+        }
+        for const_ in self.consts.iter_mut() {
+            const_.value = truncate(&const_.value, limit);
+        }
+        for static_ in self.statics.iter_mut() {
+            static_.value = truncate(&static_.value, limit);
+        }
+    }
+
+    /// Drop the signatures of all functions as written in the source,
+    /// so that only the re-serialized signatures are rendered
+    pub fn drop_source_signatures(&mut self) {
+        let drop_source = |func: &mut Function| func.source_signature = None;
+        self.functions.iter_mut().for_each(drop_source);
+        for trait_ in self.traits.iter_mut() {
+            trait_.functions.iter_mut().for_each(drop_source);
+        }
+        for impl_ in self.impls.iter_mut() {
+            impl_.functions.iter_mut().for_each(drop_source);
+        }
+        for block in self.extern_blocks.iter_mut() {
+            block.functions.iter_mut().for_each(drop_source);
+        }
+    }
+
+    /// Drop the initializers of all constants and statics as written in the source,
+    /// so that only their truncated previews are rendered
+    pub fn drop_full_initializers(&mut self) {
+        for const_ in self.consts.iter_mut() {
+            const_.full_value = None;
+        }
+        for static_ in self.statics.iter_mut() {
+            static_.full_value = None;
+        }
+    }
+
+    /// Drop the ``PhantomData<...>`` marker fields of all structs and enum variants
+    pub fn drop_phantom_fields(&mut self) {
+        for struct_ in self.structs.iter_mut() {
+            struct_.fields.retain(|f| !f.is_phantom());
+        }
+        for variant in self.enums.iter_mut().flat_map(|e| e.variants.iter_mut()) {
+            variant.fields.retain(|f| !f.is_phantom());
+        }
+    }
+
+    /// Truncate the content of all rendered types nested more than ``max_depth`` brackets deep
+    pub fn truncate_type_depth(&mut self, max_depth: usize) {
+        self.map_signatures(|signature| truncate_type_depth(signature, max_depth));
+    }
+
+    /// Replace the type aliases named in all rendered types, except the targets of the aliases,
+    /// with their target types
+    pub fn expand_type_aliases(&mut self) {
+        let aliases = self.type_aliases.clone();
+        let mut by_name: BTreeMap<String, Vec<&TypeAlias>> = BTreeMap::new();
+        for alias in &aliases {
+            by_name.entry(alias.name.clone()).or_default().push(alias);
+        }
+        self.map_signatures(|signature| expand_type_aliases(signature, &by_name));
+        for (alias, original) in self.type_aliases.iter_mut().zip(&aliases) {
+            alias.target = original.target.clone();
+        }
+    }
+
+    /// Replace the rendered types of fields, parameters, return types, resolved signatures, impl self types,
+    /// associated types and their defaults, deref targets, type alias targets, constants and statics
+    pub(super) fn map_signatures(&mut self, map: impl Fn(&TypeSignature) -> TypeSignature) {
+        self.map_signatures_in(|_, signature| map(signature));
+    }
+
+    /// Replace the rendered types as for ``map_signatures``,
+    /// given the fully qualified name of the module each type is written in
+    fn map_signatures_in(&mut self, map: impl Fn(&[String], &TypeSignature) -> TypeSignature) {
+        let map_function = |module: &[String], func: &mut Function| {
+            for param in func.params.iter_mut() {
+                param.type_ = map(module, &param.type_);
+            }
+            if let Some(output) = func.output.as_mut() {
+                *output = map(module, output);
+            }
+            if let Some(resolved) = func.resolved_signature.as_mut() {
+                *resolved = map(module, resolved);
+            }
+        };
+        let parent = |path: &[String]| path[..path.len() - 1].to_vec();
+        for struct_ in self.structs.iter_mut() {
+            let module = parent(&struct_.path);
+            for field in struct_.fields.iter_mut() {
+                field.type_ = map(&module, &field.type_);
+            }
+            if let Some(target) = struct_.deref_target.as_mut() {
+                *target = map(&module, target);
+            }
+        }
+        for enum_ in self.enums.iter_mut() {
+            let module = parent(&enum_.path);
+            for field in enum_.variants.iter_mut().flat_map(|v| v.fields.iter_mut()) {
+                field.type_ = map(&module, &field.type_);
+            }
+            if let Some(target) = enum_.deref_target.as_mut() {
+                *target = map(&module, target);
+            }
+        }
+        for function in self.functions.iter_mut() {
+            map_function(&parent(&function.path), function);
+        }
+        for trait_ in self.traits.iter_mut() {
+            let module = parent(&trait_.path);
+            for function in trait_.functions.iter_mut() {
+                map_function(&module, function);
+            }
+            for default in trait_
+                .assoc_types
+                .iter_mut()
+                .flat_map(|t| t.default.as_mut())
+            {
+                *default = map(&module, default);
+            }
+        }
+        for alias in self.type_aliases.iter_mut() {
+            alias.target = map(&parent(&alias.path), &alias.target);
+        }
+        for const_ in self.consts.iter_mut() {
+            const_.type_ = map(&parent(&const_.path), &const_.type_);
+        }
+        for static_ in self.statics.iter_mut() {
+            static_.type_ = map(&parent(&static_.path), &static_.type_);
+        }
+        for impl_ in self.impls.iter_mut() {
+            impl_.self_type = map(&impl_.parent, &impl_.self_type);
+            for assoc_type in impl_.assoc_types.iter_mut() {
+                assoc_type.type_ = map(&impl_.parent, &assoc_type.type_);
+            }
+            for function in impl_.functions.iter_mut() {
+                map_function(&impl_.parent, function);
+            }
+        }
+        for block in self.extern_blocks.iter_mut() {
+            for function in block.functions.iter_mut() {
+                map_function(&block.parent, function);
+            }
+            for static_ in block.statics.iter_mut() {
+                static_.type_ = map(&block.parent, &static_.type_);
+            }
+        }
+    }
+
+    /// Sort the items by their path, so that the output is independent of the traversal order.
+    ///
+    /// Impls and extern blocks are sorted by the module they are declared in,
+    /// retaining their declaration order within a module.
+    pub fn sort_items(&mut self) {
+        self.modules.sort_by(|a, b| a.path.cmp(&b.path));
+        self.structs.sort_by(|a, b| a.path.cmp(&b.path));
+        self.enums.sort_by(|a, b| a.path.cmp(&b.path));
+        self.functions.sort_by(|a, b| a.path.cmp(&b.path));
+        self.traits.sort_by(|a, b| a.path.cmp(&b.path));
+        self.type_aliases.sort_by(|a, b| a.path.cmp(&b.path));
+        self.macros.sort_by(|a, b| a.path.cmp(&b.path));
+        self.consts.sort_by(|a, b| a.path.cmp(&b.path));
+        self.statics.sort_by(|a, b| a.path.cmp(&b.path));
+        self.impls.sort_by(|a, b| a.parent.cmp(&b.parent));
+        self.extern_blocks.sort_by(|a, b| a.parent.cmp(&b.parent));
+        self.excluded_modules.sort();
+    }
+
+    /// Add the modules, items, excluded modules and warnings of another analysis, e.g. of a dependency
+    pub fn extend_analysis(&mut self, other: AnalysisResult) {
+        self.modules.extend(other.modules);
+        self.structs.extend(other.structs);
+        self.enums.extend(other.enums);
+        self.functions.extend(other.functions);
+        self.traits.extend(other.traits);
+        self.type_aliases.extend(other.type_aliases);
+        self.impls.extend(other.impls);
+        self.extern_blocks.extend(other.extern_blocks);
+        self.macros.extend(other.macros);
+        self.consts.extend(other.consts);
+        self.statics.extend(other.statics);
+        self.excluded_modules.extend(other.excluded_modules);
+        self.warnings.extend(other.warnings);
+    }
+
+    /// Add the items parsed from a module
+    pub fn extend_items(&mut self, items: ModuleItems) {
+        self.structs.extend(items.structs);
+        self.enums.extend(items.enums);
+        self.functions.extend(items.functions);
+        self.traits.extend(items.traits);
+        self.type_aliases.extend(items.type_aliases);
+        self.impls.extend(items.impls);
+        self.extern_blocks.extend(items.extern_blocks);
+        self.macros.extend(items.macros);
+        self.consts.extend(items.consts);
+        self.statics.extend(items.statics);
+    }
+}
+
+/// Rewrite a path as written in a module, e.g. ``internal::Foo``, that resolves to a known item or module,
+/// so that it resolves from the rewritten module to the rewritten name,
+/// or ``None`` if it still does as written
+fn rewrite_written_path(
+    rewriter: &impl Fn(&[String]) -> Vec<String>,
+    known: &BTreeSet<Vec<String>>,
+    module: &[String],
+    path: &str,
+) -> Option<String> {
+    let resolve = |module: &[String]| {
+        resolve_relative_path(&module.iter().map(|s| s.as_str()).collect::<Vec<_>>(), path)
+    };
+    let resolved = resolve(module);
+    if !known.contains(&resolved) {
+        return None;
+    }
+    let rewritten = rewriter(&resolved);
+    if resolve(&rewriter(module)) == rewritten {
+        return None;
+    }
+    // relative to the crate root, which the rewriter keeps as the first segment
+    let segments = Some("crate")
+        .into_iter()
+        .chain(rewritten.iter().skip(1).map(|s| s.as_str()));
+    Some(segments.collect::<Vec<_>>().join("::"))
+}
+
+/// Rewrite the leading path of a rendered path type, e.g. ``internal::Wrapper`` of ``internal::Wrapper<u8>``,
+/// keeping its generic arguments, or ``None`` if it is unchanged
+fn rewrite_leading_path(type_: &str, rewrite: impl Fn(&str) -> Option<String>) -> Option<String> {
+    let path: syn::Path = syn::parse_str(type_).ok()?;
+    // only the last segment may have generic arguments, e.g. not ``Vec::<u8>::new``
+    let mut qualified = path.segments.iter().rev().skip(1);
+    if path.leading_colon.is_some() || qualified.any(|s| !s.arguments.is_none()) {
+        return None;
+    }
+    let leading = path
+        .segments
+        .iter()
+        .map(|s| s.ident.to_string())
+        .collect::<Vec<_>>()
+        .join("::");
+    let rest = type_.strip_prefix(&leading)?;
+    Some(format!("{}{}", rewrite(&leading)?, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_model::TypeSegment;
+    use insta::assert_yaml_snapshot;
+    use tempfile::TempDir;
+
+    /// Write a crate named ``my_crate`` with a library target to a temporary directory,
+    /// with the given files, by their paths relative to ``src``, e.g. ``("a/b.rs", "pub struct B;")``
+    fn write_crate(files: &[(&str, &str)]) -> Result<TempDir> {
+        let temp_dir = tempfile::tempdir()?;
+        std::fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            r#"
+            [package]
+            name = "my_crate"
+            version = "0.1.0"
+
+            [lib]
+        "#,
+        )?;
+        let src = temp_dir.path().join("src");
+        for (path, content) in files {
+            let path = src.join(path);
+            std::fs::create_dir_all(path.parent().unwrap())?;
+            std::fs::write(path, content)?;
+        }
+        Ok(temp_dir)
+    }
+
+    #[test]
+    fn test_analyze_crate() -> Result<()> {
         // Create a temporary directory for the dummy crate
         let temp_dir = tempfile::tempdir()?;
         let temp_dir_path = temp_dir.path();
@@ -179,6 +2327,9 @@ mod tests {
             [package]
             name = "my_crate"
             version = "0.1.0"
+            description = "A dummy crate"
+            license = "MIT"
+            rust-version = "1.70"
 
             [lib]
         "#,
@@ -192,8 +2343,13 @@ mod tests {
             r#"
             //! The crate docstring
             pub mod my_module;
+            pub mod another_module;
         "#,
         )?;
+        std::fs::write(
+            temp_dir_path.join("src").join("another_module.rs"),
+            "pub struct AnotherStruct;",
+        )?;
 
         // Create a dummy module file
         let dummy_module_path = temp_dir_path.join("src").join("my_module.rs");
@@ -228,70 +2384,2313 @@ mod tests {
         )?;
 
         // Analyze the dummy crate
-        let mut result = analyze_crate(temp_dir_path.to_str().unwrap())?;
+        let mut result =
+            analyze_crate(temp_dir_path.to_str().unwrap(), &AnalysisOptions::default())?;
 
         // Remove the file paths for snapshot testing, as they are non-deterministic
         for module in result.modules.iter_mut() {
             module.file = None;
         }
+        for struct_ in result.structs.iter_mut() {
+            struct_.file = None;
+        }
+        for enum_ in result.enums.iter_mut() {
+            enum_.file = None;
+        }
 
         assert_yaml_snapshot!(result, @r###"
         ---
         crate_:
           name: my_crate
           version: 0.1.0
+          description: A dummy crate
+          authors: []
+          license: MIT
+          repository: ~
+          homepage: ~
+          rust_version: 1.70.0
+          no_std: Std
         modules:
-          - file: ~
+          - kind: module
+            file: ~
+            line_count: 5
+            byte_size: 112
+            name: my_crate
             path:
               - my_crate
             docstring: The crate docstring
             declarations:
               - my_module
-          - file: ~
+              - another_module
+            uses: []
+            is_generated: false
+            effectively_public: true
+          - kind: module
+            file: ~
+            line_count: 1
+            byte_size: 25
+            name: another_module
+            path:
+              - my_crate
+              - another_module
+            docstring: ""
+            declarations: []
+            uses: []
+            is_generated: false
+            effectively_public: true
+          - kind: module
+            file: ~
+            line_count: 8
+            byte_size: 226
+            name: my_module
             path:
               - my_crate
               - my_module
             docstring: The module docstring
             declarations:
               - my_submodule
-          - file: ~
+            uses: []
+            is_generated: false
+            effectively_public: true
+          - kind: module
+            file: ~
+            line_count: 7
+            byte_size: 196
+            name: my_submodule
             path:
               - my_crate
               - my_module
               - my_submodule
             docstring: The sub-module docstring
             declarations: []
+            uses: []
+            is_generated: false
+            effectively_public: true
         structs:
-          - path:
+          - kind: struct
+            name: AnotherStruct
+            path:
+              - my_crate
+              - another_module
+              - AnotherStruct
+            docstring: ""
+            doc_aliases: []
+            attributes: []
+            generics:
+              params: []
+              where_predicates: []
+            fields_kind: unit
+            fields: []
+            deref_target: ~
+            recursive: false
+            effectively_public: true
+          - kind: struct
+            name: DummyStruct1
+            path:
               - my_crate
               - my_module
               - DummyStruct1
             docstring: The struct1 docstring
+            doc_aliases: []
+            attributes: []
+            generics:
+              params: []
+              where_predicates: []
+            fields_kind: unit
             fields: []
-          - path:
+            deref_target: ~
+            recursive: false
+            effectively_public: true
+          - kind: struct
+            name: DummyStruct2
+            path:
               - my_crate
               - my_module
               - my_submodule
               - DummyStruct2
             docstring: The struct2 docstring
+            doc_aliases: []
+            attributes: []
+            generics:
+              params: []
+              where_predicates: []
+            fields_kind: unit
             fields: []
+            deref_target: ~
+            recursive: false
+            effectively_public: true
         enums:
-          - path:
+          - kind: enum
+            name: DummyEnum1
+            path:
               - my_crate
               - my_module
               - DummyEnum1
             docstring: The enum1 docstring
+            doc_aliases: []
+            attributes: []
+            generics:
+              params: []
+              where_predicates: []
             variants: []
-          - path:
+            deref_target: ~
+            recursive: false
+            effectively_public: true
+          - kind: enum
+            name: DummyEnum2
+            path:
               - my_crate
               - my_module
               - my_submodule
               - DummyEnum2
             docstring: The enum2 docstring
+            doc_aliases: []
+            attributes: []
+            generics:
+              params: []
+              where_predicates: []
             variants: []
+            deref_target: ~
+            recursive: false
+            effectively_public: true
         functions: []
+        traits: []
+        type_aliases: []
+        impls: []
+        extern_blocks: []
+        macros: []
+        consts: []
+        statics: []
+        excluded_modules: []
+        warnings: []
+        dependencies: []
         "###);
 
         Ok(())
     }
+
+    #[test]
+    fn test_normalize_path() -> Result<()> {
+        let cwd = std::env::current_dir()?;
+        assert_eq!(
+            normalize_path(Path::new("a/./b/../c"))?,
+            cwd.join("a").join("c")
+        );
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_analyze_crate_preserve_symlinks() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let real_dir = temp_dir.path().join("real");
+        std::fs::create_dir_all(real_dir.join("src"))?;
+        std::fs::write(
+            real_dir.join("Cargo.toml"),
+            r#"
+            [package]
+            name = "my_crate"
+            version = "0.1.0"
+
+            [lib]
+        "#,
+        )?;
+        std::fs::write(real_dir.join("src").join("lib.rs"), "pub mod my_module;")?;
+        std::fs::write(real_dir.join("src").join("my_module.rs"), "")?;
+        let link_dir = temp_dir.path().join("link");
+        std::os::unix::fs::symlink(&real_dir, &link_dir)?;
+
+        let options = AnalysisOptions {
+            preserve_symlinks: true,
+            ..Default::default()
+        };
+        let result = analyze_crate(link_dir.to_str().unwrap(), &options)?;
+        assert_eq!(result.modules.len(), 2);
+        let link_dir = normalize_path(&link_dir)?;
+        for module in &result.modules {
+            assert!(
+                Path::new(module.file.as_ref().unwrap()).starts_with(&link_dir),
+                "{:?} is not in {:?}",
+                module.file,
+                link_dir
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("internal", "internal"));
+        assert!(!glob_match("internal", "internal2"));
+        assert!(glob_match("*::generated", "my_crate::a::generated"));
+        assert!(!glob_match("*::generated", "my_crate::generated::a"));
+        assert!(glob_match("my_crate::*", "my_crate::a::b"));
+        assert!(glob_match("a?c", "abc"));
+        assert!(glob_match("*a*b*", "xxaxxbxx"));
+        assert!(!glob_match("*a*b", "xxbxxa"));
+    }
+
+    #[test]
+    fn test_analyze_crate_exclude() -> Result<()> {
+        let temp_dir = write_crate(&[
+            ("lib.rs", "pub mod a; pub mod internal;"),
+            ("a.rs", "pub mod generated; pub struct A;"),
+            ("a/generated.rs", "pub struct Generated;"),
+            ("internal.rs", "pub struct Internal;"),
+        ])?;
+        let temp_dir_path = temp_dir.path();
+
+        let options = AnalysisOptions {
+            exclude_patterns: vec!["*::generated".to_string(), "internal".to_string()],
+            ..Default::default()
+        };
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &options)?;
+
+        assert_eq!(
+            result
+                .modules
+                .iter()
+                .map(|m| m.path_str())
+                .collect::<Vec<_>>(),
+            vec!["my_crate", "my_crate::a"]
+        );
+        assert_eq!(
+            result
+                .structs
+                .iter()
+                .map(|s| s.path_str())
+                .collect::<Vec<_>>(),
+            vec!["my_crate::a::A"]
+        );
+        let mut excluded = result.excluded_modules.clone();
+        excluded.sort();
+        assert_eq!(
+            excluded,
+            vec!["my_crate::a::generated", "my_crate::internal"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_cfg_attr_path() -> Result<()> {
+        let temp_dir = write_crate(&[
+            (
+                "lib.rs",
+                r#"
+            #[cfg_attr(unix, path = "platform/unix.rs")]
+            #[cfg_attr(windows, path = "platform/windows.rs")]
+            pub mod imp;
+            "#,
+            ),
+            ("imp.rs", "pub struct Fallback;"),
+            (
+                "platform/unix.rs",
+                "//! The unix docstring\npub mod nested; pub struct Unix;",
+            ),
+            ("platform/nested.rs", "pub struct Nested;"),
+            ("platform/windows.rs", "pub struct Windows;"),
+        ])?;
+        let temp_dir_path = temp_dir.path();
+        let src = temp_dir_path.join("src");
+        std::fs::create_dir_all(src.join("platform").join("nested"))?;
+
+        let structs = |cfg: &[&str]| -> Result<Vec<String>> {
+            let options = AnalysisOptions {
+                cfg: cfg.iter().map(|s| s.to_string()).collect(),
+                ..Default::default()
+            };
+            let result = analyze_crate(temp_dir_path.to_str().unwrap(), &options)?;
+            Ok(result.structs.iter().map(|s| s.path_str()).collect())
+        };
+        assert_eq!(
+            structs(&["unix"])?,
+            vec!["my_crate::imp::Unix", "my_crate::imp::nested::Nested"]
+        );
+        assert_eq!(structs(&["windows"])?, vec!["my_crate::imp::Windows"]);
+        assert_eq!(structs(&[])?, vec!["my_crate::imp::Fallback"]);
+
+        // the inner docstring of the file the path resolves to belongs to the declared module
+        let options = AnalysisOptions {
+            cfg: vec!["unix".to_string()],
+            ..Default::default()
+        };
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &options)?;
+        let module = result
+            .modules
+            .iter()
+            .find(|m| m.path_str() == "my_crate::imp")
+            .unwrap();
+        assert_eq!(module.docstring, "The unix docstring");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_cfg_variants() -> Result<()> {
+        let temp_dir = write_crate(&[
+            (
+                "lib.rs",
+                r#"
+            #[cfg(unix)]
+            #[path = "unix.rs"]
+            pub mod imp;
+            #[cfg(windows)]
+            #[path = "windows.rs"]
+            pub mod imp;
+            pub mod common;
+            "#,
+            ),
+            ("unix.rs", "pub struct Unix;"),
+            ("windows.rs", "pub struct Windows;"),
+            ("common.rs", "pub struct Common;"),
+        ])?;
+        let temp_dir_path = temp_dir.path();
+
+        let analyze = |cfg: &[&str]| -> Result<(Vec<String>, Vec<String>)> {
+            let options = AnalysisOptions {
+                cfg: cfg.iter().map(|s| s.to_string()).collect(),
+                ..Default::default()
+            };
+            let result = analyze_crate(temp_dir_path.to_str().unwrap(), &options)?;
+            let structs = result.structs.iter().map(|s| s.path_str()).collect();
+            let variants = result
+                .modules
+                .iter()
+                .find(|m| m.path_str() == "my_crate::imp")
+                .map(|m| m.cfg_variants.clone())
+                .unwrap_or_default();
+            Ok((structs, variants))
+        };
+        let variants = vec!["unix".to_string(), "windows".to_string()];
+        assert_eq!(
+            analyze(&["unix"])?,
+            (
+                vec![
+                    "my_crate::common::Common".to_string(),
+                    "my_crate::imp::Unix".to_string()
+                ],
+                variants.clone()
+            )
+        );
+        assert_eq!(
+            analyze(&["windows"])?,
+            (
+                vec![
+                    "my_crate::common::Common".to_string(),
+                    "my_crate::imp::Windows".to_string()
+                ],
+                variants.clone()
+            )
+        );
+        // without a configuration, the first declaration is read
+        assert_eq!(
+            analyze(&[])?,
+            (
+                vec![
+                    "my_crate::common::Common".to_string(),
+                    "my_crate::imp::Unix".to_string()
+                ],
+                variants
+            )
+        );
+        assert_eq!(
+            analyze(&["target_os=\"wasi\""])?,
+            (vec!["my_crate::common::Common".to_string()], vec![])
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_shared_path() -> Result<()> {
+        let temp_dir = write_crate(&[(
+            "lib.rs",
+            r#"
+            #[path = "shared.rs"]
+            pub mod u32_impl;
+            #[path = "shared.rs"]
+            pub mod u64_impl;
+            "#,
+        )])?;
+        let temp_dir_path = temp_dir.path();
+        let src = temp_dir_path.join("src");
+        // a module that includes its own file is not read again
+        std::fs::write(
+            src.join("shared.rs"),
+            "pub struct Value;\n#[path = \"shared.rs\"]\npub mod again;",
+        )?;
+
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &Default::default())?;
+        assert_eq!(
+            result
+                .modules
+                .iter()
+                .map(|m| m.path_str())
+                .collect::<Vec<_>>(),
+            vec!["my_crate", "my_crate::u32_impl", "my_crate::u64_impl"]
+        );
+        assert_eq!(
+            result
+                .structs
+                .iter()
+                .map(|s| s.path_str())
+                .collect::<Vec<_>>(),
+            vec!["my_crate::u32_impl::Value", "my_crate::u64_impl::Value"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_generated_modules() -> Result<()> {
+        let temp_dir = write_crate(&[
+            ("lib.rs", "pub mod proto; pub mod bindings; pub mod manual; pub mod build;"),
+            ("proto.rs", "// This file is @generated by prost-build.\npub struct Message;"),
+            ("bindings.rs", "/* automatically generated by rust-bindgen 0.69.4 */\n\npub fn ffi() {}"),
+            ("manual.rs", "// Copyright 2024\n//! Types that are not generated by a tool\npub struct Manual; // @generated"),
+            ("build.rs", r#"include!(concat!(env!("OUT_DIR"), "/built.rs"));"#),
+        ])?;
+        let temp_dir_path = temp_dir.path();
+        let out_dir = temp_dir_path.join("out");
+        std::fs::create_dir_all(&out_dir)?;
+        std::fs::write(out_dir.join("built.rs"), "pub struct Built;")?;
+
+        let generated = |result: &AnalysisResult| {
+            result
+                .modules
+                .iter()
+                .filter(|m| m.is_generated)
+                .map(|m| m.path_str())
+                .collect::<Vec<_>>()
+        };
+        let options = AnalysisOptions {
+            out_dir: Some(out_dir.to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &options)?;
+        assert_eq!(
+            generated(&result),
+            vec!["my_crate::bindings", "my_crate::build", "my_crate::proto"]
+        );
+
+        let options = AnalysisOptions {
+            out_dir: Some(out_dir.to_string_lossy().to_string()),
+            generated_markers: Some(vec!["RUST-BINDGEN".to_string()]),
+            ..Default::default()
+        };
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &options)?;
+        assert_eq!(
+            generated(&result),
+            vec!["my_crate::bindings", "my_crate::build"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_includes() -> Result<()> {
+        let temp_dir = write_crate(&[
+            (
+                "lib.rs",
+                r#"
+            //! The crate docstring
+            include!("included.rs");
+            include!(concat!(env!("OUT_DIR"), "/generated.rs"));
+            "#,
+            ),
+            (
+                "included.rs",
+                "//! The included docstring\npub struct Included;",
+            ),
+        ])?;
+        let temp_dir_path = temp_dir.path();
+        let out_dir = temp_dir_path.join("out");
+        std::fs::create_dir_all(&out_dir)?;
+        std::fs::write(out_dir.join("generated.rs"), "pub struct Generated;")?;
+
+        let options = AnalysisOptions {
+            out_dir: Some(out_dir.to_string_lossy().to_string()),
+            ..Default::default()
+        };
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &options)?;
+        assert_eq!(
+            result
+                .structs
+                .iter()
+                .map(|s| s.path_str())
+                .collect::<Vec<_>>(),
+            vec!["my_crate::Generated", "my_crate::Included"]
+        );
+        assert!(result.warnings.is_empty());
+        // the inner docstring of the included file belongs to the including module
+        assert_eq!(
+            result.modules[0].docstring,
+            "The crate docstring\n\nThe included docstring"
+        );
+
+        // without an OUT_DIR the generated file is skipped with a warning
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &Default::default())?;
+        assert_eq!(
+            result
+                .structs
+                .iter()
+                .map(|s| s.path_str())
+                .collect::<Vec<_>>(),
+            vec!["my_crate::Included"]
+        );
+        assert_eq!(
+            result
+                .warnings
+                .iter()
+                .map(|w| w.message.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Cannot include \"generated.rs\" in my_crate, as the OUT_DIR is not known"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_out_dir_from_messages() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let out_dir = temp_dir.path().join("out");
+        let messages = [
+            serde_json::json!({
+                "reason": "build-script-executed",
+                "package_id": "other 0.1.0 (path+file:///other)",
+                "linked_libs": [],
+                "linked_paths": [],
+                "cfgs": [],
+                "env": [],
+                "out_dir": temp_dir.path().join("other").to_string_lossy(),
+            }),
+            serde_json::json!({
+                "reason": "build-script-executed",
+                "package_id": "my_crate 0.1.0 (path+file:///my_crate)",
+                "linked_libs": [],
+                "linked_paths": [],
+                "cfgs": [],
+                "env": [],
+                "out_dir": out_dir.to_string_lossy(),
+            }),
+            serde_json::json!({"reason": "build-finished", "success": true}),
+        ]
+        .iter()
+        .map(|m| m.to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+        let package_id = |repr: &str| PackageId {
+            repr: repr.to_string(),
+        };
+
+        assert_eq!(
+            out_dir_from_messages(
+                messages.as_bytes(),
+                &package_id("my_crate 0.1.0 (path+file:///my_crate)")
+            )?,
+            Some(out_dir)
+        );
+        // a package without a build script has no OUT_DIR
+        assert_eq!(
+            out_dir_from_messages(
+                messages.as_bytes(),
+                &package_id("no_build 0.1.0 (path+file:///no_build)")
+            )?,
+            None
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_out_dir_over_run_build() -> Result<()> {
+        let temp_dir = write_crate(&[(
+            "lib.rs",
+            r#"include!(concat!(env!("OUT_DIR"), "/generated.rs"));"#,
+        )])?;
+        let temp_dir_path = temp_dir.path();
+        // the build script would fail if it were run
+        std::fs::write(
+            temp_dir_path.join("build.rs"),
+            r#"fn main() { panic!("not expected to run"); }"#,
+        )?;
+        let out_dir = temp_dir_path.join("out");
+        std::fs::create_dir_all(&out_dir)?;
+        std::fs::write(out_dir.join("generated.rs"), "pub struct Generated;")?;
+
+        // a given OUT_DIR is used as is, without building the crate
+        let options = AnalysisOptions {
+            out_dir: Some(out_dir.to_string_lossy().to_string()),
+            run_build: true,
+            ..Default::default()
+        };
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &options)?;
+        assert_eq!(
+            result
+                .structs
+                .iter()
+                .map(|s| s.path_str())
+                .collect::<Vec<_>>(),
+            vec!["my_crate::Generated"]
+        );
+        assert!(result.warnings.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    #[ignore = "runs `cargo build` on a temporary crate"]
+    fn test_analyze_crate_run_build() -> Result<()> {
+        let temp_dir = write_crate(&[(
+            "lib.rs",
+            r#"include!(concat!(env!("OUT_DIR"), "/generated.rs"));"#,
+        )])?;
+        let temp_dir_path = temp_dir.path();
+        std::fs::write(
+            temp_dir_path.join("build.rs"),
+            r#"
+            fn main() {
+                let out_dir = std::env::var("OUT_DIR").unwrap();
+                std::fs::write(format!("{}/generated.rs", out_dir), "pub struct Generated;").unwrap();
+            }
+            "#,
+        )?;
+
+        let options = AnalysisOptions {
+            run_build: true,
+            ..Default::default()
+        };
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &options)?;
+        assert_eq!(
+            result
+                .structs
+                .iter()
+                .map(|s| s.path_str())
+                .collect::<Vec<_>>(),
+            vec!["my_crate::Generated"]
+        );
+        assert!(result.warnings.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_path_rewriter() -> Result<()> {
+        let temp_dir = write_crate(&[
+            ("lib.rs", "pub mod internal;"),
+            (
+                "internal.rs",
+                r#"
+            pub struct Foo { pub field: u8 }
+            impl Foo {
+                pub fn new() -> Self {}
+            }
+            pub trait Bar {
+                fn bar(&self);
+            }
+            "#,
+            ),
+        ])?;
+        let temp_dir_path = temp_dir.path();
+
+        let options = AnalysisOptions {
+            path_rewriter: Some(PathRewriter::new(|path| {
+                path.iter().filter(|s| *s != "internal").cloned().collect()
+            })),
+            ..Default::default()
+        };
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &options)?;
+        assert_eq!(
+            result.public_paths(),
+            vec![
+                "my_crate",
+                "my_crate::Bar",
+                "my_crate::Bar::bar",
+                "my_crate::Foo",
+                "my_crate::Foo::new"
+            ]
+        );
+        assert_eq!(
+            result.structs[0].fields[0].path,
+            vec!["my_crate", "Foo", "field"]
+        );
+        assert_eq!(result.impls[0].parent, vec!["my_crate"]);
+        assert_eq!(
+            result.impls[0].functions[0].owner,
+            FunctionOwner::Inherent("my_crate::Foo".to_string())
+        );
+        assert_eq!(
+            result.traits[0].functions[0].owner,
+            FunctionOwner::Trait("my_crate::Bar".to_string())
+        );
+        // cross-links resolve to the rewritten paths
+        assert_eq!(
+            result.resolve_type("Foo").and_then(|r| r.path),
+            Some("my_crate::Foo".to_string())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_path_rewriter_written_paths() -> Result<()> {
+        let temp_dir = write_crate(&[
+            ("lib.rs", "pub mod internal;\npub mod api;"),
+            (
+                "internal.rs",
+                r#"
+            pub struct Wrapper<T>(pub T);
+            impl<T> Wrapper<T> {
+                pub fn get(&self) -> &T { &self.0 }
+            }
+            pub trait Base {
+                fn base(&self);
+            }
+            pub trait Named: crate::internal::Base {
+                fn name(&self) -> String;
+            }
+            "#,
+            ),
+            (
+                "api.rs",
+                r#"
+            use crate::internal::Base;
+            impl Base for crate::internal::Wrapper<u8> {
+                fn base(&self) {}
+            }
+            impl super::internal::Named for crate::internal::Wrapper<u8> {
+                fn name(&self) -> String { String::new() }
+            }
+            pub fn make() -> crate::internal::Wrapper<u8> { todo!() }
+            "#,
+            ),
+        ])?;
+        let temp_dir_path = temp_dir.path();
+
+        let options = AnalysisOptions {
+            path_rewriter: Some(PathRewriter::new(|path| {
+                path.iter()
+                    .map(|s| if s == "internal" { "types" } else { s })
+                    .map(String::from)
+                    .collect()
+            })),
+            ..Default::default()
+        };
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &options)?;
+        let modules = result
+            .modules
+            .iter()
+            .map(|m| m.path_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            modules,
+            vec!["my_crate", "my_crate::api", "my_crate::types"]
+        );
+        assert_eq!(result.modules[0].declarations, vec!["types", "api"]);
+        assert_eq!(
+            result.modules[1].uses[0].path,
+            vec!["crate", "types", "Base"]
+        );
+
+        let kinds = result.item_kinds();
+        let owners = result
+            .impls
+            .iter()
+            .map(|i| result.impl_owner(&kinds, i))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            owners,
+            vec![Some("my_crate::types::Wrapper".to_string()); 3]
+        );
+        assert_eq!(result.impls_for("my_crate::types::Wrapper").len(), 3);
+        for trait_ in ["my_crate::types::Base", "my_crate::types::Named"] {
+            let implementors = result.implementors(trait_);
+            assert_eq!(implementors.len(), 1, "{}", trait_);
+            assert_eq!(
+                implementors[0].type_path.as_deref(),
+                Some("my_crate::types::Wrapper")
+            );
+        }
+        assert_eq!(
+            result.impls[1].functions[0].owner,
+            FunctionOwner::TraitImpl {
+                type_: "my_crate::types::Wrapper".to_string(),
+                trait_: "crate::types::Named".to_string()
+            }
+        );
+
+        let supertraits = result.supertrait_methods("my_crate::types::Named");
+        assert_eq!(supertraits.len(), 1);
+        assert_eq!(supertraits[0].trait_, "my_crate::types::Base");
+        assert!(supertraits[0].local);
+
+        let output = result.functions[0].output.as_ref().unwrap();
+        let rendered = output.iter().map(|s| s.content()).collect::<String>();
+        assert_eq!(rendered, "crate::types::Wrapper<u8>");
+        assert_eq!(
+            result.resolve_type(&rendered).and_then(|r| r.path),
+            Some("my_crate::types::Wrapper".to_string())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_doc_url_resolver() -> Result<()> {
+        let temp_dir = write_crate(&[(
+            "lib.rs",
+            r#"
+            pub struct Wrapper {
+                pub value: serde_json::Value,
+                pub client: internal_api::Client,
+                pub stream: tokio::net::TcpStream,
+            }
+            "#,
+        )])?;
+        let temp_dir_path = temp_dir.path();
+
+        let options = AnalysisOptions {
+            doc_url_resolver: Some(DocUrlResolver::new(|crate_name, path| {
+                (crate_name == "internal_api")
+                    .then(|| format!("https://docs.example.com/{}", path.join("/")))
+            })),
+            link_docs_rs: true,
+            ..Default::default()
+        }
+        .with_external_docs("serde_json", "https://docs.rs/serde_json/1.0.0/serde_json/");
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &options)?;
+        let types = result.structs[0]
+            .fields
+            .iter()
+            .map(|f| f.type_.clone())
+            .collect::<Vec<_>>();
+        assert_yaml_snapshot!(types, @r###"
+        ---
+        - - ExternalPath:
+              path: "serde_json::Value"
+              url: "https://docs.rs/serde_json/1.0.0/serde_json/?search=Value"
+        - - ExternalPath:
+              path: "internal_api::Client"
+              url: "https://docs.example.com/Client"
+        - - ExternalPath:
+              path: "tokio::net::TcpStream"
+              url: "https://docs.rs/tokio/latest/tokio/?search=net::TcpStream"
+        "###);
+
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_exec_metadata_timeout() -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::tempdir()?;
+        let stalled = temp_dir.path().join("stalled");
+        let failing = temp_dir.path().join("failing");
+        std::fs::write(&stalled, "#!/bin/sh\nsleep 10\n")?;
+        std::fs::write(
+            &failing,
+            "#!/bin/sh\necho 'invalid manifest' >&2\nexit 101\n",
+        )?;
+        for script in [&stalled, &failing] {
+            std::fs::set_permissions(script, std::fs::Permissions::from_mode(0o755))?;
+        }
+        let mut command = MetadataCommand::new();
+        command.cargo_path(&stalled);
+
+        let start = Instant::now();
+        let err = exec_metadata(&command, Duration::from_millis(100), 2).unwrap_err();
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert_eq!(
+            err.downcast_ref::<MetadataTimeoutError>(),
+            Some(&MetadataTimeoutError {
+                timeout: Duration::from_millis(100),
+                attempts: 2
+            })
+        );
+
+        // failures other than a timeout are not retried
+        command.cargo_path(&failing);
+        let err = exec_metadata(&command, Duration::from_secs(10), 2).unwrap_err();
+        assert!(format!("{:#}", err).contains("invalid manifest"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_metadata_error() {
+        let err = metadata_error(cargo_metadata::Error::Io(std::io::Error::from(
+            std::io::ErrorKind::NotFound,
+        )));
+        assert_eq!(
+            err.downcast_ref::<CargoNotFoundError>(),
+            Some(&CargoNotFoundError)
+        );
+        let err = metadata_error(cargo_metadata::Error::NoJson);
+        assert!(err.downcast_ref::<CargoNotFoundError>().is_none());
+        assert_eq!(err.to_string(), "Failed to run `cargo metadata`");
+    }
+
+    #[test]
+    fn test_analyze_crate_items_in_file() -> Result<()> {
+        let temp_dir = write_crate(&[
+            ("lib.rs", "pub mod net; pub fn root() {}"),
+            (
+                "net.rs",
+                r#"
+            pub mod socket;
+            include!("generated.rs");
+            "#,
+            ),
+            ("generated.rs", "pub struct Generated;"),
+            (
+                "net/socket.rs",
+                r#"
+            pub struct Socket;
+            impl Socket {
+                pub fn connect() {}
+            }
+            pub trait Stream {}
+            extern "C" {
+                pub static ERRNO: i32;
+            }
+            "#,
+            ),
+        ])?;
+        let temp_dir_path = temp_dir.path();
+
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &AnalysisOptions::default())?;
+        let items = |path: &str| {
+            result
+                .items_in_file(path)
+                .into_iter()
+                .map(|item| format!("{:?} {}", item.kind.unwrap(), item.path.unwrap()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            items("src/net/socket.rs"),
+            vec![
+                "Module my_crate::net::socket",
+                "Static my_crate::net::socket::ERRNO",
+                "Struct my_crate::net::socket::Socket",
+                "Trait my_crate::net::socket::Stream",
+            ]
+        );
+        // items of included files are recorded with the included file
+        assert_eq!(items("net.rs"), vec!["Module my_crate::net"]);
+        assert_eq!(
+            items("generated.rs"),
+            vec!["Struct my_crate::net::Generated"]
+        );
+        // only whole path components match
+        assert!(items("et.rs").is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_name_override() -> Result<()> {
+        let temp_dir = write_crate(&[
+            ("lib.rs", "pub mod inner; pub fn func() {}"),
+            ("inner.rs", "pub struct Foo;"),
+        ])?;
+        let temp_dir_path = temp_dir.path();
+
+        let options = AnalysisOptions {
+            crate_name_override: Some("facade".to_string()),
+            ..Default::default()
+        };
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &options)?;
+        assert_eq!(result.crate_.name, "facade");
+        assert_eq!(
+            result.public_paths(),
+            vec![
+                "facade",
+                "facade::func",
+                "facade::inner",
+                "facade::inner::Foo"
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_effective_visibility() -> Result<()> {
+        let temp_dir = write_crate(&[
+            (
+                "lib.rs",
+                r#"
+            mod inner;
+            pub mod outer;
+            pub use inner::Reexported;
+            pub use self::inner::nested::*;
+            "#,
+            ),
+            (
+                "inner.rs",
+                r#"
+            pub struct Reexported;
+            pub struct Hidden;
+            impl Hidden {
+                pub fn new() -> Self {}
+            }
+            pub mod nested;
+            "#,
+            ),
+            ("inner/nested.rs", "pub struct Globbed; pub mod deep;"),
+            ("inner/nested/deep.rs", ""),
+            (
+                "outer.rs",
+                r#"
+            pub struct Outer;
+            struct Private;
+            pub(crate) fn internal() {}
+            "#,
+            ),
+        ])?;
+        let temp_dir_path = temp_dir.path();
+
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &AnalysisOptions::default())?;
+        assert_eq!(
+            result.public_paths(),
+            vec![
+                "my_crate",
+                "my_crate::inner::Reexported",
+                "my_crate::inner::nested::Globbed",
+                "my_crate::inner::nested::deep",
+                "my_crate::outer",
+                "my_crate::outer::Outer"
+            ]
+        );
+        assert!(result.impls.is_empty());
+
+        let options = AnalysisOptions {
+            include_private: true,
+            ..Default::default()
+        };
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &options)?;
+        let visibility = |paths: Vec<(String, bool)>| {
+            paths
+                .into_iter()
+                .map(|(path, public)| format!("{} {}", path, public))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            visibility(
+                result
+                    .modules
+                    .iter()
+                    .map(|m| (m.path_str(), m.effectively_public))
+                    .chain(
+                        result
+                            .structs
+                            .iter()
+                            .map(|s| (s.path_str(), s.effectively_public))
+                    )
+                    .chain(
+                        result
+                            .functions
+                            .iter()
+                            .map(|f| (f.path_str(), f.effectively_public))
+                    )
+                    .collect()
+            ),
+            vec![
+                "my_crate true",
+                "my_crate::inner false",
+                "my_crate::inner::nested false",
+                "my_crate::inner::nested::deep true",
+                "my_crate::outer true",
+                "my_crate::inner::Hidden false",
+                "my_crate::inner::Reexported true",
+                "my_crate::inner::nested::Globbed true",
+                "my_crate::outer::Outer true",
+                "my_crate::outer::Private false",
+                "my_crate::outer::internal false"
+            ]
+        );
+        assert_eq!(result.impls.len(), 1);
+        assert!(!result
+            .public_paths()
+            .contains(&"my_crate::inner::Hidden::new".to_string()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_dependencies() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let crate_dir = temp_dir.path().join("my_crate");
+        let dep_dir = temp_dir.path().join("my-dep");
+        std::fs::create_dir_all(crate_dir.join("src"))?;
+        std::fs::create_dir_all(dep_dir.join("src"))?;
+        std::fs::write(
+            crate_dir.join("Cargo.toml"),
+            r#"
+            [package]
+            name = "my_crate"
+            version = "0.1.0"
+
+            [dependencies]
+            my-dep = { path = "../my-dep", features = ["extra"] }
+        "#,
+        )?;
+        std::fs::write(crate_dir.join("src").join("lib.rs"), "pub struct Own;")?;
+        std::fs::write(
+            dep_dir.join("Cargo.toml"),
+            r#"
+            [package]
+            name = "my-dep"
+            version = "0.2.0"
+
+            [features]
+            extra = []
+        "#,
+        )?;
+        std::fs::write(
+            dep_dir.join("src").join("lib.rs"),
+            r#"
+            pub struct DepStruct;
+            #[cfg_attr(feature = "extra", path = "extra.rs")]
+            pub mod gated;
+            "#,
+        )?;
+        std::fs::write(dep_dir.join("src").join("extra.rs"), "pub struct Extra;")?;
+
+        let result = analyze_crate(crate_dir.to_str().unwrap(), &Default::default())?;
+        assert_eq!(
+            result
+                .structs
+                .iter()
+                .map(|s| s.path_str())
+                .collect::<Vec<_>>(),
+            vec!["my_crate::Own"]
+        );
+        assert!(result.dependencies.is_empty());
+
+        let options = AnalysisOptions {
+            dependency_depth: 1,
+            ..Default::default()
+        };
+        let result = analyze_crate(crate_dir.to_str().unwrap(), &options)?;
+        assert_eq!(
+            result
+                .structs
+                .iter()
+                .map(|s| s.path_str())
+                .collect::<Vec<_>>(),
+            vec!["my_crate::Own", "my_dep::DepStruct", "my_dep::gated::Extra"]
+        );
+        assert_eq!(
+            result
+                .dependencies
+                .iter()
+                .map(|c| format!("{} {}", c.name, c.version))
+                .collect::<Vec<_>>(),
+            vec!["my_dep 0.2.0"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_unreadable_dependency() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let crate_dir = temp_dir.path().join("my_crate");
+        std::fs::create_dir_all(crate_dir.join("src"))?;
+        std::fs::write(
+            crate_dir.join("Cargo.toml"),
+            r#"
+            [package]
+            name = "my_crate"
+            version = "0.1.0"
+
+            [dependencies]
+            good-dep = { path = "../good-dep" }
+            bad-dep = { path = "../bad-dep" }
+        "#,
+        )?;
+        std::fs::write(crate_dir.join("src").join("lib.rs"), "pub struct Own;")?;
+        for name in ["good-dep", "bad-dep"] {
+            let dep_dir = temp_dir.path().join(name);
+            std::fs::create_dir_all(dep_dir.join("src"))?;
+            std::fs::write(
+                dep_dir.join("Cargo.toml"),
+                format!("[package]\nname = \"{}\"\nversion = \"0.1.0\"\n", name),
+            )?;
+            std::fs::write(
+                dep_dir.join("src").join("lib.rs"),
+                "pub struct DepStruct;\npub mod inner;",
+            )?;
+        }
+        std::fs::write(
+            temp_dir.path().join("good-dep/src/inner.rs"),
+            "pub struct Inner;",
+        )?;
+        // the root module of the bad dependency is not valid Rust, so it cannot be analyzed
+        std::fs::write(
+            temp_dir.path().join("bad-dep/src/lib.rs"),
+            "pub struct DepStruct;\npub fn broken(",
+        )?;
+
+        let options = AnalysisOptions {
+            dependency_depth: 1,
+            ..Default::default()
+        };
+        let result = analyze_crate(crate_dir.to_str().unwrap(), &options)?;
+        // nothing of the bad dependency is added
+        assert_eq!(
+            result
+                .structs
+                .iter()
+                .map(|s| s.path_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "good_dep::DepStruct",
+                "good_dep::inner::Inner",
+                "my_crate::Own"
+            ]
+        );
+        assert_eq!(
+            result
+                .modules
+                .iter()
+                .map(|m| m.path_str())
+                .collect::<Vec<_>>(),
+            vec!["good_dep", "good_dep::inner", "my_crate"]
+        );
+        assert_eq!(
+            result
+                .dependencies
+                .iter()
+                .map(|c| c.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["good_dep"]
+        );
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0]
+            .message
+            .starts_with("Could not analyze dependency bad_dep: "));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_cfg_info() -> Result<()> {
+        let temp_dir = write_crate(&[
+            (
+                "lib.rs",
+                r#"
+            #[cfg(feature = "net")]
+            pub mod net;
+            #[cfg(unix)]
+            pub fn unix_only() {}
+            pub fn always() {}
+            "#,
+            ),
+            (
+                "net.rs",
+                r#"
+            #![cfg(not(windows))]
+            pub struct Socket;
+            #[cfg(unix)]
+            impl Socket {
+                #[cfg(feature = "tls")]
+                pub fn tls(&self) {}
+            }
+            #[cfg(feature = "net")]
+            pub trait Connect { fn connect(&self); }
+            "#,
+            ),
+        ])?;
+        let temp_dir_path = temp_dir.path();
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &AnalysisOptions::default())?;
+        let cfg_info = result
+            .modules
+            .iter()
+            .map(|m| (m.path_str(), &m.cfg_info))
+            .chain(result.structs.iter().map(|s| (s.path_str(), &s.cfg_info)))
+            .chain(result.traits.iter().map(|t| (t.path_str(), &t.cfg_info)))
+            .chain(
+                result
+                    .functions
+                    .iter()
+                    .chain(result.traits.iter().flat_map(|t| &t.functions))
+                    .chain(result.impls.iter().flat_map(|i| &i.functions))
+                    .map(|f| (f.path_str(), &f.cfg_info)),
+            )
+            .collect::<Vec<_>>();
+        assert_yaml_snapshot!(cfg_info, @r###"
+        ---
+        - - my_crate
+          - ~
+        - - "my_crate::net"
+          - "all(feature = \"net\", not(windows))"
+        - - "my_crate::net::Socket"
+          - "all(feature = \"net\", not(windows))"
+        - - "my_crate::net::Connect"
+          - "all(feature = \"net\", not(windows))"
+        - - "my_crate::always"
+          - ~
+        - - "my_crate::unix_only"
+          - unix
+        - - "my_crate::net::Connect::connect"
+          - "all(feature = \"net\", not(windows))"
+        - - "my_crate::net::Socket::tls"
+          - "all(feature = \"net\", not(windows), unix, feature = \"tls\")"
+        "###);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_target_name() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let temp_dir_path = temp_dir.path();
+        let src = temp_dir_path.join("src");
+        std::fs::create_dir_all(&src)?;
+        std::fs::write(src.join("lib.rs"), "pub fn func() {}")?;
+        std::fs::write(src.join("main.rs"), "pub fn run() {}")?;
+        let path = temp_dir_path.to_str().unwrap();
+        let paths = |result: &AnalysisResult| {
+            result
+                .functions
+                .iter()
+                .map(|f| f.path_str())
+                .collect::<Vec<_>>()
+        };
+
+        let manifest = r#"
+            [package]
+            name = "my-crate"
+            version = "0.1.0"
+        "#;
+        std::fs::write(temp_dir_path.join("Cargo.toml"), manifest)?;
+        let result = analyze_crate(path, &AnalysisOptions::default())?;
+        assert_eq!(result.crate_.name, "my_crate");
+        assert_eq!(paths(&result), vec!["my_crate::func"]);
+
+        std::fs::write(
+            temp_dir_path.join("Cargo.toml"),
+            format!("{}\n[lib]\nname = \"foo\"\n", manifest),
+        )?;
+        let result = analyze_crate(path, &AnalysisOptions::default())?;
+        assert_eq!(result.crate_.name, "foo");
+        assert_eq!(paths(&result), vec!["foo::func"]);
+
+        // without a lib target, the bin target is analyzed
+        std::fs::remove_file(src.join("lib.rs"))?;
+        std::fs::write(temp_dir_path.join("Cargo.toml"), manifest)?;
+        let result = analyze_crate(path, &AnalysisOptions::default())?;
+        assert_eq!(result.crate_.name, "my_crate");
+        assert_eq!(paths(&result), vec!["my_crate::run"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_no_std() -> Result<()> {
+        let temp_dir = write_crate(&[("lib.rs", "pub fn func() {}")])?;
+        let lib = temp_dir.path().join("src").join("lib.rs");
+        let path = temp_dir.path().to_str().unwrap();
+
+        let result = analyze_crate(path, &AnalysisOptions::default())?;
+        assert_eq!(result.crate_.no_std, NoStd::Std);
+
+        std::fs::write(&lib, "#![no_std]\npub fn func() {}")?;
+        let result = analyze_crate(path, &AnalysisOptions::default())?;
+        assert_eq!(result.crate_.no_std, NoStd::Always);
+
+        std::fs::write(
+            &lib,
+            "#![cfg_attr(not(feature = \"std\"), no_std)]\npub fn func() {}",
+        )?;
+        let result = analyze_crate(path, &AnalysisOptions::default())?;
+        assert_eq!(
+            result.crate_.no_std,
+            NoStd::Conditional {
+                condition: "not(feature = \"std\")".to_string(),
+                active: true
+            }
+        );
+        let options = AnalysisOptions {
+            cfg: vec!["feature=\"std\"".to_string()],
+            ..Default::default()
+        };
+        let result = analyze_crate(path, &options)?;
+        assert_eq!(
+            result.crate_.no_std,
+            NoStd::Conditional {
+                condition: "not(feature = \"std\")".to_string(),
+                active: false
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_sealed_traits() -> Result<()> {
+        let temp_dir = write_crate(&[
+            (
+                "lib.rs",
+                r#"
+            mod private;
+            pub mod public;
+            pub trait MySealed: private::Sealed {}
+            pub trait MyOpen: public::Open + Clone {}
+            "#,
+            ),
+            ("private.rs", "pub trait Sealed {}"),
+            ("public.rs", "pub trait Open {}"),
+        ])?;
+        let temp_dir_path = temp_dir.path();
+
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &AnalysisOptions::default())?;
+        let sealed = result
+            .traits
+            .iter()
+            .map(|t| (t.path_str(), t.sealed))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            sealed,
+            vec![
+                ("my_crate::MyOpen".to_string(), false),
+                ("my_crate::MySealed".to_string(), true),
+                ("my_crate::public::Open".to_string(), false),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_changed_files() -> Result<()> {
+        let temp_dir = write_crate(&[
+            ("lib.rs", "pub mod a; pub mod b; pub struct Root;"),
+            ("a.rs", "pub mod inner; pub struct A;"),
+            (
+                "a/inner.rs",
+                "pub struct Inner; impl Inner { pub fn new() -> Self {} }",
+            ),
+            ("b.rs", "pub fn b() {}"),
+        ])?;
+        let temp_dir_path = temp_dir.path();
+
+        let options = AnalysisOptions {
+            changed_files: Some(vec![PathBuf::from("src/a/inner.rs")]),
+            ..Default::default()
+        };
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &options)?;
+        assert_eq!(
+            result.public_paths(),
+            vec![
+                "my_crate",
+                "my_crate::a",
+                "my_crate::a::inner",
+                "my_crate::a::inner::Inner",
+                "my_crate::a::inner::Inner::new",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_expand_type_aliases() -> Result<()> {
+        let temp_dir = write_crate(&[(
+            "lib.rs",
+            r#"
+            pub type Bytes = Vec<u8>;
+            pub type Result<T> = std::result::Result<T, Error>;
+            pub type Chunks = Vec<Bytes>;
+            pub struct Error;
+            pub struct Frame {
+                pub payload: Bytes,
+                pub chunks: Chunks,
+            }
+            pub fn read(frames: &[Frame]) -> Result<Option<crate::Bytes>> {}
+        "#,
+        )])?;
+        let temp_dir_path = temp_dir.path();
+
+        let options = AnalysisOptions {
+            expand_type_aliases: true,
+            ..Default::default()
+        };
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &options)?;
+        let render =
+            |signature: &TypeSignature| signature.iter().map(|s| s.content()).collect::<String>();
+        assert_eq!(
+            result.structs[1]
+                .fields
+                .iter()
+                .map(|f| render(&f.type_))
+                .collect::<Vec<_>>(),
+            vec!["Vec<u8>", "Vec<Bytes>"]
+        );
+        assert_eq!(
+            render(result.functions[0].output.as_ref().unwrap()),
+            "std::result::Result<Option<Vec<u8>>, Error>"
+        );
+        assert_eq!(
+            result
+                .type_aliases
+                .iter()
+                .map(|a| a.target_str())
+                .collect::<Vec<_>>(),
+            vec!["Vec<u8>", "Vec<Bytes>", "std::result::Result<T, Error>"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_private_fields() -> Result<()> {
+        let temp_dir = write_crate(&[(
+            "lib.rs",
+            r#"
+            pub struct MyStruct {
+                /// The length
+                pub len: usize,
+                /// Always at least ``len``
+                capacity: usize,
+            }
+            pub enum MyEnum {
+                Variant { field: u8 },
+            }
+            "#,
+        )])?;
+        let temp_dir_path = temp_dir.path();
+        let fields = |result: &AnalysisResult| {
+            result
+                .structs
+                .iter()
+                .flat_map(|s| &s.fields)
+                .chain(
+                    result
+                        .enums
+                        .iter()
+                        .flat_map(|e| e.variants.iter().flat_map(|v| &v.fields)),
+                )
+                .map(|f| (f.path.join("::"), f.public))
+                .collect::<Vec<_>>()
+        };
+
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &AnalysisOptions::default())?;
+        assert_eq!(
+            fields(&result),
+            vec![
+                ("my_crate::MyStruct::len".to_string(), true),
+                ("my_crate::MyEnum::Variant::field".to_string(), true),
+            ]
+        );
+
+        let options = AnalysisOptions {
+            include_private: true,
+            ..Default::default()
+        };
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &options)?;
+        assert_eq!(
+            fields(&result),
+            vec![
+                ("my_crate::MyStruct::len".to_string(), true),
+                ("my_crate::MyStruct::capacity".to_string(), false),
+                ("my_crate::MyEnum::Variant::field".to_string(), true),
+            ]
+        );
+        assert_eq!(
+            result.structs[0].fields[1].docstring,
+            "Always at least ``len``"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_strict() -> Result<()> {
+        let temp_dir = write_crate(&[
+            (
+                "lib.rs",
+                "pub mod missing; pub mod broken; pub mod unreadable; pub mod ok;",
+            ),
+            ("broken.rs", "pub fn broken("),
+            ("ok.rs", "pub struct Ok;"),
+        ])?;
+        let temp_dir_path = temp_dir.path();
+        let src = temp_dir_path.join("src");
+        std::fs::write(src.join("unreadable.rs"), [0xff, 0xfe])?;
+        let path = temp_dir_path.to_str().unwrap();
+
+        // by default, the problems are reported as warnings
+        let result = analyze_crate(path, &AnalysisOptions::default())?;
+        assert_eq!(
+            result
+                .warnings
+                .iter()
+                .map(|w| w.message.split(": ").next().unwrap())
+                .collect::<Vec<_>>(),
+            vec![
+                "Could not read module my_crate::unreadable",
+                "Error parsing module my_crate::broken",
+                "Could not find the file of module my_crate::missing",
+            ]
+        );
+        assert_eq!(
+            result.public_paths(),
+            vec!["my_crate", "my_crate::ok", "my_crate::ok::Ok"]
+        );
+
+        let options = AnalysisOptions {
+            strict: true,
+            ..Default::default()
+        };
+        let err = analyze_crate(path, &options).unwrap_err();
+        let err = err.downcast_ref::<StrictModeError>().unwrap();
+        assert_eq!(err.warnings.len(), 3);
+        assert!(err
+            .to_string()
+            .starts_with("the analysis produced 3 warning(s) in strict mode:\n- "));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crates() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let workspace = temp_dir.path().join("workspace");
+        let standalone = temp_dir.path().join("standalone");
+        std::fs::create_dir_all(&workspace)?;
+        std::fs::write(
+            workspace.join("Cargo.toml"),
+            r#"
+            [workspace]
+            members = ["first", "second"]
+        "#,
+        )?;
+        for (dir, name) in [
+            (workspace.join("first"), "first"),
+            (workspace.join("second"), "second"),
+            (standalone, "standalone"),
+        ] {
+            std::fs::create_dir_all(dir.join("src"))?;
+            std::fs::write(
+                dir.join("Cargo.toml"),
+                format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\n"),
+            )?;
+            std::fs::write(dir.join("src").join("lib.rs"), "pub struct Item;")?;
+        }
+
+        let paths = ["workspace/second", "standalone", "workspace/first"]
+            .map(|p| temp_dir.path().join(p).to_string_lossy().to_string());
+        let paths = paths.iter().map(|p| p.as_str()).collect::<Vec<_>>();
+        let results = analyze_crates(&paths, &AnalysisOptions::default())?;
+        assert_eq!(
+            results
+                .iter()
+                .map(|r| r.structs[0].path_str())
+                .collect::<Vec<_>>(),
+            vec!["second::Item", "standalone::Item", "first::Item"]
+        );
+
+        let missing = temp_dir
+            .path()
+            .join("missing")
+            .to_string_lossy()
+            .to_string();
+        assert!(analyze_crates(&[paths[0], &missing], &AnalysisOptions::default()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_warn_undocumented() -> Result<()> {
+        let temp_dir = write_crate(&[
+            (
+                "lib.rs",
+                r#"
+            //! The crate
+            pub mod net;
+            mod private;
+            /// Documented
+            pub struct Documented;
+            pub enum Undocumented { A }
+            ///
+            pub fn blank() {}
+        "#,
+            ),
+            ("net.rs", "pub trait Socket {}"),
+            ("private.rs", "pub fn hidden() {}"),
+        ])?;
+        let temp_dir_path = temp_dir.path();
+        let path = temp_dir_path.to_str().unwrap();
+
+        let options = AnalysisOptions {
+            warn_undocumented: true,
+            ..Default::default()
+        };
+        let result = analyze_crate(path, &options)?;
+        assert_eq!(
+            result
+                .warnings
+                .iter()
+                .map(|w| {
+                    let file = Path::new(w.file.as_ref().unwrap()).file_name().unwrap();
+                    format!("{}: {}", file.to_string_lossy(), w.message)
+                })
+                .collect::<Vec<_>>(),
+            vec![
+                "net.rs: Public module `my_crate::net` has no docstring",
+                "lib.rs: Public enum `my_crate::Undocumented` has no docstring",
+                "lib.rs: Public function `my_crate::blank` has no docstring",
+                "net.rs: Public trait `my_crate::net::Socket` has no docstring",
+            ]
+        );
+
+        let options = AnalysisOptions {
+            warn_undocumented: true,
+            strict: true,
+            ..Default::default()
+        };
+        let err = analyze_crate(path, &options).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<StrictModeError>()
+                .unwrap()
+                .warnings
+                .len(),
+            4
+        );
+        assert!(analyze_crate(path, &AnalysisOptions::default())?
+            .warnings
+            .is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_cfg_fields() -> Result<()> {
+        let temp_dir = write_crate(&[(
+            "lib.rs",
+            r#"
+            pub struct Config {
+                pub name: String,
+                #[cfg(feature = "std")]
+                pub path: std::path::PathBuf,
+                #[cfg(not(feature = "std"))]
+                pub path: &'static str,
+            }
+            pub struct Gated {
+                #[cfg(feature = "serde")]
+                pub value: serde_json::Value,
+            }
+            pub enum Backend {
+                #[cfg(unix)]
+                Epoll,
+                #[cfg(windows)]
+                Iocp,
+                Poll { #[cfg(feature = "std")] timeout: u64 },
+            }
+        "#,
+        )])?;
+        let temp_dir_path = temp_dir.path();
+        let path = temp_dir_path.to_str().unwrap();
+        let members = |result: &AnalysisResult| {
+            let fields = result.structs.iter().flat_map(|s| {
+                s.fields
+                    .iter()
+                    .map(|f| format!("{} ({})", f.path.join("::"), f.cfg.as_deref().unwrap_or("")))
+            });
+            let variants = result.enums.iter().flat_map(|e| {
+                e.variants.iter().map(|v| {
+                    format!(
+                        "{} {:?}",
+                        v.name(),
+                        v.fields.iter().map(|f| &f.name).collect::<Vec<_>>()
+                    )
+                })
+            });
+            fields.chain(variants).collect::<Vec<_>>()
+        };
+
+        // without a configured set, all fields and variants are kept
+        let result = analyze_crate(path, &AnalysisOptions::default())?;
+        assert_eq!(members(&result).len(), 7);
+
+        let options = AnalysisOptions {
+            cfg: vec!["unix".to_string(), "feature=\"std\"".to_string()],
+            ..Default::default()
+        };
+        let result = analyze_crate(path, &options)?;
+        assert_eq!(
+            result
+                .structs
+                .iter()
+                .map(|s| s.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Config", "Gated"]
+        );
+        assert_eq!(
+            members(&result),
+            vec![
+                "my_crate::Config::name ()",
+                "my_crate::Config::path (feature = \"std\")",
+                "my_crate::Backend::Epoll []",
+                "my_crate::Backend::Poll [\"timeout\"]",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_license_file() -> Result<()> {
+        let temp_dir = tempfile::tempdir()?;
+        let temp_dir_path = temp_dir.path();
+        std::fs::write(
+            temp_dir_path.join("Cargo.toml"),
+            r#"
+            [package]
+            name = "my_crate"
+            version = "0.1.0"
+            license-file = "LICENSE.txt"
+
+            [lib]
+        "#,
+        )?;
+        std::fs::write(temp_dir_path.join("LICENSE.txt"), "All rights reserved")?;
+        std::fs::create_dir_all(temp_dir_path.join("src"))?;
+        std::fs::write(temp_dir_path.join("src").join("lib.rs"), "")?;
+
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &AnalysisOptions::default())?;
+        assert_eq!(result.crate_.license, None);
+        assert_eq!(result.crate_.license_file.as_deref(), Some("LICENSE.txt"));
+        assert_eq!(result.crate_.license_or_file(), Some("LICENSE.txt"));
+        let serialized = serde_json::to_value(&result.crate_)?;
+        assert!(serialized.get("license").is_none());
+        assert_eq!(serialized["license_file"], "LICENSE.txt");
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_warn_missing_safety() -> Result<()> {
+        let temp_dir = write_crate(&[(
+            "lib.rs",
+            r#"
+            /// Reads a value
+            ///
+            /// # Safety
+            ///
+            /// The pointer must be valid
+            pub unsafe fn read(ptr: *const u8) -> u8 { *ptr }
+            /// Writes a value
+            pub unsafe fn write(ptr: *mut u8) {}
+            unsafe fn private() {}
+            /// A buffer
+            pub struct Buffer;
+            impl Buffer {
+                /// Gets a byte without bounds checks
+                pub unsafe fn get_unchecked(&self, index: usize) -> u8 { 0 }
+            }
+            /// Marks types that are safe to zero
+            pub unsafe trait Zeroable {
+                /// # Safety
+                /// Only call once
+                unsafe fn init(&mut self);
+                unsafe fn reset(&mut self);
+            }
+            unsafe impl Zeroable for Buffer {
+                unsafe fn init(&mut self) {}
+                unsafe fn reset(&mut self) {}
+            }
+        "#,
+        )])?;
+        let temp_dir_path = temp_dir.path();
+        let path = temp_dir_path.to_str().unwrap();
+
+        let options = AnalysisOptions {
+            warn_missing_safety: true,
+            ..Default::default()
+        };
+        let result = analyze_crate(path, &options)?;
+        assert_eq!(
+            result
+                .warnings
+                .iter()
+                .map(|w| w.message.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "Unsafe function `my_crate::write` has no `# Safety` section",
+                "Unsafe method `my_crate::Zeroable::reset` has no `# Safety` section",
+                "Unsafe method `my_crate::Buffer::get_unchecked` has no `# Safety` section",
+                "Unsafe trait `my_crate::Zeroable` has no `# Safety` section",
+            ]
+        );
+        assert!(result.warnings.iter().all(|w| w.file.is_some()));
+        assert!(analyze_crate(path, &AnalysisOptions::default())?
+            .warnings
+            .is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_warn_broken_examples() -> Result<()> {
+        let temp_dir = write_crate(&[(
+            "lib.rs",
+            r#"
+            //! ```
+            //! let socket = my_crate::Socket::new();
+            //! ```
+            pub struct Socket;
+            impl Socket {
+                /// ```
+                /// let socket = ;
+                /// ```
+                ///
+                /// ```compile_fail
+                /// let socket = Socket::new(;
+                /// ```
+                ///
+                /// ```
+                /// let socket = Socket::new(]
+                /// ```
+                pub fn new() -> Self { Socket }
+            }
+        "#,
+        )])?;
+        let temp_dir_path = temp_dir.path();
+        let path = temp_dir_path.to_str().unwrap();
+
+        let options = AnalysisOptions {
+            warn_broken_examples: true,
+            ..Default::default()
+        };
+        let result = analyze_crate(path, &options)?;
+        assert_eq!(
+            result
+                .warnings
+                .iter()
+                .map(|w| w.message.as_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "Code example 1 of method `my_crate::Socket::new` does not parse: expected an expression",
+                "Code example 3 of method `my_crate::Socket::new` does not parse: cannot parse string into token stream",
+            ]
+        );
+        assert!(result.warnings.iter().all(|w| w.file.is_some()));
+        assert!(analyze_crate(path, &AnalysisOptions::default())?
+            .warnings
+            .is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_extra_targets() -> Result<()> {
+        let temp_dir = write_crate(&[("lib.rs", "pub fn run() {}")])?;
+        let temp_dir_path = temp_dir.path();
+        let example_dir = temp_dir_path.join("examples").join("hello-world");
+        std::fs::create_dir_all(&example_dir)?;
+        std::fs::write(
+            example_dir.join("main.rs"),
+            "//! Prints a greeting\npub mod greeting;\nfn main() {}",
+        )?;
+        std::fs::write(example_dir.join("greeting.rs"), "pub fn hello() {}")?;
+        std::fs::create_dir_all(temp_dir_path.join("tests"))?;
+        std::fs::write(
+            temp_dir_path.join("tests").join("api.rs"),
+            "//! Tests the API",
+        )?;
+
+        let modules = |options: &AnalysisOptions| -> Result<Vec<(String, Option<TargetKind>)>> {
+            let result = analyze_crate(temp_dir_path.to_str().unwrap(), options)?;
+            Ok(result
+                .modules
+                .iter()
+                .map(|m| (m.path_str(), m.target_kind))
+                .collect())
+        };
+        assert_eq!(
+            modules(&AnalysisOptions::default())?,
+            vec![("my_crate".to_string(), None)]
+        );
+        assert_eq!(
+            modules(&AnalysisOptions {
+                include_examples: true,
+                include_tests: true,
+                ..Default::default()
+            })?,
+            vec![
+                ("api".to_string(), Some(TargetKind::Test)),
+                ("hello_world".to_string(), Some(TargetKind::Example)),
+                (
+                    "hello_world::greeting".to_string(),
+                    Some(TargetKind::Example)
+                ),
+                ("my_crate".to_string(), None),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_macros() -> Result<()> {
+        let temp_dir = write_crate(&[
+            (
+                "lib.rs",
+                r#"
+            mod internal;
+            macro_rules! local {
+                () => {};
+            }
+            pub(crate) use local;
+            pub fn run() {
+                macro_rules! inner {
+                    ($x:expr) => { $x };
+                }
+                inner!(local!());
+            }
+            "#,
+            ),
+            (
+                "internal.rs",
+                r#"
+            /// Makes a map
+            #[macro_export]
+            macro_rules! map {
+                ($($k:expr => $v:expr),* $(,)?) => {};
+            }
+            "#,
+            ),
+        ])?;
+        let temp_dir_path = temp_dir.path();
+
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &AnalysisOptions::default())?;
+        assert_eq!(
+            result
+                .macros
+                .iter()
+                .map(|m| m.path_str())
+                .collect::<Vec<_>>(),
+            vec!["my_crate::map"]
+        );
+        assert!(result.macros[0]
+            .file
+            .as_ref()
+            .unwrap()
+            .ends_with("internal.rs"));
+        assert!(result.public_paths().contains(&"my_crate::map".to_string()));
+
+        let result = analyze_crate(
+            temp_dir_path.to_str().unwrap(),
+            &AnalysisOptions {
+                include_private: true,
+                ..Default::default()
+            },
+        )?;
+        assert_eq!(
+            result
+                .macros
+                .iter()
+                .map(|m| (m.path_str(), m.effectively_public))
+                .collect::<Vec<_>>(),
+            vec![
+                ("my_crate::local".to_string(), false),
+                ("my_crate::map".to_string(), true)
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_preserve_signature_formatting() -> Result<()> {
+        let temp_dir = write_crate(&[(
+            "lib.rs",
+            r#"
+pub fn connect(
+    host: &str, // the host name
+    port: u16,
+) -> std::io::Result<()> {
+    Ok(())
+}
+pub struct Socket;
+impl Socket {
+    pub fn send(&self, data: &[u8]) -> usize where Self: Sized { 0 }
+}
+"#,
+        )])?;
+        let temp_dir_path = temp_dir.path();
+
+        let sources = |result: &AnalysisResult| {
+            result
+                .functions
+                .iter()
+                .chain(result.impls.iter().flat_map(|i| &i.functions))
+                .map(|f| f.source_signature.clone())
+                .collect::<Vec<_>>()
+        };
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &Default::default())?;
+        assert_eq!(sources(&result), vec![None, None]);
+
+        let options = AnalysisOptions {
+            preserve_signature_formatting: true,
+            ..Default::default()
+        };
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &options)?;
+        assert_eq!(
+            sources(&result),
+            vec![
+                Some(
+                    "fn connect(\n    host: &str, // the host name\n    port: u16,\n) -> std::io::Result<()>"
+                        .to_string()
+                ),
+                Some("fn send(&self, data: &[u8]) -> usize where Self: Sized".to_string()),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_max_parse_depth() -> Result<()> {
+        // deeply nested types are parsed on the stack of the analysis thread
+        let temp_dir = write_crate(&[(
+            "lib.rs",
+            &format!(
+                "pub struct Deep(pub {}u8{});\npub fn refs(a: {}u8) {{}}",
+                "Vec<".repeat(200),
+                ">".repeat(200),
+                "&".repeat(200)
+            ),
+        )])?;
+        let temp_dir_path = temp_dir.path();
+        let param = |result: &AnalysisResult| {
+            result.functions[0].params[0]
+                .type_
+                .iter()
+                .map(|s| s.content())
+                .collect::<String>()
+        };
+
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &Default::default())?;
+        assert_eq!(
+            param(&result),
+            format!("{}…", "&".repeat(DEFAULT_MAX_PARSE_DEPTH + 1))
+        );
+
+        let options = AnalysisOptions {
+            max_parse_depth: Some(300),
+            ..Default::default()
+        };
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &options)?;
+        assert_eq!(param(&result), format!("{}u8", "&".repeat(200)));
+        assert_eq!(
+            result.structs[0].fields[0].type_,
+            vec![TypeSegment::Path(format!(
+                "{}u8{}",
+                "Vec<".repeat(200),
+                ">".repeat(200)
+            ))]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_full_initializers() -> Result<()> {
+        let temp_dir = write_crate(&[(
+            "lib.rs",
+            r#"
+pub const LIMITS: [(u8, u16); 2] = [
+    (1, 100), // the first tier
+    (2, 1000),
+];
+pub static NAME: &str = "my_crate";
+"#,
+        )])?;
+        let temp_dir_path = temp_dir.path();
+
+        let values = |result: &AnalysisResult| {
+            result
+                .consts
+                .iter()
+                .map(|c| (c.value.clone(), c.full_value.clone()))
+                .chain(
+                    result
+                        .statics
+                        .iter()
+                        .map(|s| (s.value.clone(), s.full_value.clone())),
+                )
+                .collect::<Vec<_>>()
+        };
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &Default::default())?;
+        assert_eq!(
+            values(&result),
+            vec![
+                ("[(1, 100), (2, 1000),]".to_string(), None),
+                ("\"my_crate\"".to_string(), None),
+            ]
+        );
+
+        let options = AnalysisOptions {
+            full_initializers: true,
+            ..Default::default()
+        };
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &options)?;
+        assert_eq!(
+            values(&result),
+            vec![
+                (
+                    "[(1, 100), (2, 1000),]".to_string(),
+                    Some("[\n    (1, 100), // the first tier\n    (2, 1000),\n]".to_string())
+                ),
+                ("\"my_crate\"".to_string(), Some("\"my_crate\"".to_string())),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_analyze_crate_hide_phantom_fields() -> Result<()> {
+        let temp_dir = write_crate(&[(
+            "lib.rs",
+            r#"
+            use std::marker::PhantomData;
+            pub struct Id<T>(pub u64, pub PhantomData<T>);
+            pub enum Token<'a> {
+                Word { text: String, _source: PhantomData<&'a str> },
+            }
+        "#,
+        )])?;
+        let temp_dir_path = temp_dir.path();
+
+        let field_names = |result: &AnalysisResult| {
+            result
+                .structs
+                .iter()
+                .flat_map(|s| &s.fields)
+                .chain(result.enums.iter().flat_map(|e| &e.variants[0].fields))
+                .map(|f| {
+                    format!(
+                        "{}{}",
+                        f.name,
+                        if f.is_phantom() { " (phantom)" } else { "" }
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &Default::default())?;
+        assert_eq!(
+            field_names(&result),
+            vec!["0", "1 (phantom)", "text", "_source (phantom)"]
+        );
+
+        let options = AnalysisOptions {
+            hide_phantom_fields: true,
+            ..Default::default()
+        };
+        let result = analyze_crate(temp_dir_path.to_str().unwrap(), &options)?;
+        assert_eq!(field_names(&result), vec!["0", "text"]);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,802 @@
+//! Analyze the structure of docstrings, which are written in Markdown
+use std::borrow::Cow;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::data_model::{CodeBlock, DocLink, DocSections, Heading};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// An ATX heading in a Markdown docstring, e.g. ``## Title``
+pub(crate) struct MarkdownHeading {
+    /// The index of the line the heading is on
+    pub line: usize,
+    /// The heading level, from 1 to 6
+    pub level: usize,
+    pub text: String,
+}
+
+/// Find the ATX headings in a Markdown text, ignoring lines in fenced code blocks
+pub(crate) fn markdown_headings(text: &str) -> Vec<MarkdownHeading> {
+    let mut headings = vec![];
+    // the fence that opened the current code block, if any
+    let mut fence: Option<String> = None;
+    for (line, content) in text.lines().enumerate() {
+        let trimmed = content.trim_start();
+        if let Some(open) = &fence {
+            if is_closing_fence(trimmed, open) {
+                fence = None;
+            }
+            continue;
+        }
+        if let Some((open, _)) = opening_fence(trimmed) {
+            fence = Some(open);
+            continue;
+        }
+        let level = trimmed.chars().take_while(|c| *c == '#').count();
+        if !(1..=6).contains(&level) {
+            continue;
+        }
+        let rest = &trimmed[level..];
+        if !rest.is_empty() && !rest.starts_with([' ', '\t']) {
+            continue;
+        }
+        headings.push(MarkdownHeading {
+            line,
+            level,
+            text: rest.trim().trim_end_matches('#').trim_end().to_string(),
+        });
+    }
+    headings
+}
+
+/// Extract the table of contents of a Markdown text, i.e. its ATX headings, in order, with their level.
+///
+/// Slugs follow the GitHub (and MyST) convention, so that they match the anchors of the rendered headings:
+/// the text is lowercased, characters other than letters, digits, spaces, ``-`` and ``_`` are removed,
+/// and spaces are replaced with ``-``, with ``-1``, ``-2``, etc. appended to repeated slugs.
+pub fn table_of_contents(markdown: &str) -> Vec<Heading> {
+    let mut used = BTreeSet::new();
+    markdown_headings(markdown)
+        .into_iter()
+        .map(|heading| {
+            let base = slugify(&heading.text);
+            let mut slug = base.clone();
+            let mut count = 0;
+            while !used.insert(slug.clone()) {
+                count += 1;
+                slug = format!("{}-{}", base, count);
+            }
+            Heading {
+                level: heading.level,
+                text: heading.text,
+                slug,
+            }
+        })
+        .collect()
+}
+
+/// The anchor of a heading, e.g. ``the-foo-type`` for ``The `Foo` type``
+fn slugify(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The fence and info string of a line that opens a fenced code block, e.g. ``("```", "rust")``
+fn opening_fence(trimmed: &str) -> Option<(String, &str)> {
+    for marker in ['`', '~'] {
+        let count = trimmed.chars().take_while(|c| *c == marker).count();
+        if count >= 3 {
+            return Some((marker.to_string().repeat(count), trimmed[count..].trim()));
+        }
+    }
+    None
+}
+
+/// Whether a line closes the fenced code block opened with the given fence
+fn is_closing_fence(trimmed: &str, open: &str) -> bool {
+    trimmed.starts_with(open) && trimmed[open.len()..].trim().is_empty()
+}
+
+/// The rustdoc attributes that may be given in the info string of a Rust code block
+const RUST_BLOCK_ATTRIBUTES: &[&str] = &[
+    "rust",
+    "ignore",
+    "should_panic",
+    "no_run",
+    "compile_fail",
+    "test_harness",
+    "standalone_crate",
+    "edition2015",
+    "edition2018",
+    "edition2021",
+    "edition2024",
+];
+
+/// Whether a code block with the given info string is Rust code, as determined by rustdoc,
+/// i.e. it has no info string, or one made up of rustdoc attributes, such as ``no_run``
+fn is_rust_block(info: &str) -> bool {
+    info.split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|token| !token.is_empty())
+        .all(|token| RUST_BLOCK_ATTRIBUTES.contains(&token) || token.starts_with("ignore-"))
+}
+
+/// A line of a Rust code block, either shown or hidden in the displayed code
+enum CodeLine<'a> {
+    Shown(Cow<'a, str>),
+    Hidden(&'a str),
+}
+
+impl<'a> CodeLine<'a> {
+    /// Classify a line in the same way as rustdoc:
+    /// a line is hidden if it is ``#`` or starts with ``# `` (after any indentation),
+    /// and a leading ``##`` is an escaped ``#`` that is shown, e.g. ``##[derive(Debug)]``.
+    ///
+    /// Any other ``#``, such as in ``#[derive(Debug)]`` or ``#!/bin/sh``, is shown as written.
+    fn parse(line: &'a str) -> Self {
+        let trimmed = line.trim();
+        if trimmed.starts_with("##") {
+            Self::Shown(Cow::Owned(line.replacen("##", "#", 1)))
+        } else if let Some(hidden) = trimmed.strip_prefix("# ") {
+            Self::Hidden(hidden)
+        } else if trimmed == "#" {
+            Self::Hidden("")
+        } else {
+            Self::Shown(Cow::Borrowed(line))
+        }
+    }
+
+    /// The line as it is run
+    fn code(&self) -> &str {
+        match self {
+            Self::Shown(line) => line,
+            Self::Hidden(line) => line,
+        }
+    }
+}
+
+/// A fenced code block, by the indexes of its lines
+struct Fenced<'a> {
+    info: &'a str,
+    /// The index of the opening fence line
+    open: usize,
+    /// The index of the closing fence line, or the number of lines if the block is not closed
+    close: usize,
+}
+
+/// Find the fenced code blocks in a Markdown text
+fn fenced_blocks<'a>(lines: &[&'a str]) -> Vec<Fenced<'a>> {
+    let mut blocks = vec![];
+    let mut index = 0;
+    while index < lines.len() {
+        let Some((fence, info)) = opening_fence(lines[index].trim_start()) else {
+            index += 1;
+            continue;
+        };
+        let close = (index + 1..lines.len())
+            .find(|&i| is_closing_fence(lines[i].trim_start(), &fence))
+            .unwrap_or(lines.len());
+        blocks.push(Fenced {
+            info,
+            open: index,
+            close,
+        });
+        index = close + 1;
+    }
+    blocks
+}
+
+/// Extract the fenced code blocks from a Markdown text, e.g. the examples of a docstring.
+///
+/// For Rust code blocks, the code as run includes the lines hidden with a leading ``# ``,
+/// whereas the code as displayed excludes them, as rendered by rustdoc.
+/// For other code blocks, the two are the same.
+pub fn code_blocks(markdown: &str) -> Vec<CodeBlock> {
+    let lines: Vec<&str> = markdown.lines().collect();
+    fenced_blocks(&lines)
+        .into_iter()
+        .map(|block| {
+            let content = &lines[block.open + 1..block.close];
+            let rust = is_rust_block(block.info);
+            let (code, display) = if rust {
+                let parsed = content
+                    .iter()
+                    .map(|l| CodeLine::parse(l))
+                    .collect::<Vec<_>>();
+                let code = parsed.iter().map(CodeLine::code).collect::<Vec<_>>();
+                let display = parsed
+                    .iter()
+                    .filter_map(|line| match line {
+                        CodeLine::Shown(line) => Some(line.as_ref()),
+                        CodeLine::Hidden(_) => None,
+                    })
+                    .collect::<Vec<_>>();
+                (code.join("\n"), display.join("\n"))
+            } else {
+                (content.join("\n"), content.join("\n"))
+            };
+            CodeBlock {
+                info: block.info.to_string(),
+                rust,
+                code,
+                display,
+            }
+        })
+        .collect()
+}
+
+/// The Rust code examples of a Markdown text that do not parse, with their index among its Rust code blocks
+/// (from 0) and the parse error.
+///
+/// An example parses if its code, including hidden lines, is a file of items, e.g. with its own ``fn main``,
+/// or else the body of the function that rustdoc wraps it in.
+/// Examples marked ``ignore`` or ``compile_fail`` are not checked, as they are not expected to compile.
+pub fn broken_examples(markdown: &str) -> Vec<(usize, syn::Error)> {
+    code_blocks(markdown)
+        .into_iter()
+        .filter(|block| block.rust)
+        .enumerate()
+        .filter(|(_, block)| {
+            !block
+                .info
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .any(|token| {
+                    token == "ignore" || token.starts_with("ignore-") || token == "compile_fail"
+                })
+        })
+        .filter_map(|(index, block)| {
+            if syn::parse_str::<syn::File>(&block.code).is_ok() {
+                return None;
+            }
+            syn::parse_str::<syn::Block>(&format!("{{\n{}\n}}", block.code))
+                .err()
+                .map(|err| (index, err))
+        })
+        .collect()
+}
+
+/// Remove the hidden lines from the Rust code blocks of a Markdown text,
+/// so that it renders as it would with rustdoc
+pub fn strip_hidden_lines(markdown: &str) -> String {
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut output: Vec<Cow<str>> = vec![];
+    let mut next = 0;
+    for block in fenced_blocks(&lines) {
+        if !is_rust_block(block.info) {
+            continue;
+        }
+        output.extend(lines[next..=block.open].iter().map(|l| Cow::Borrowed(*l)));
+        for line in &lines[block.open + 1..block.close] {
+            if let CodeLine::Shown(line) = CodeLine::parse(line) {
+                output.push(line);
+            }
+        }
+        next = block.close;
+    }
+    output.extend(
+        lines[next.min(lines.len())..]
+            .iter()
+            .map(|l| Cow::Borrowed(*l)),
+    );
+    output.join("\n")
+}
+
+/// Extract the intra-doc links from a Markdown text, in the order they are written.
+///
+/// Inline (``[text](target)``), reference (``[text][label]``) and shortcut (``[target]``) links
+/// are recognized, with reference labels resolved through link definitions (``[label]: target``).
+/// Links in code blocks and inline code, images, and links whose target is not an item path,
+/// e.g. URLs, footnotes and task list markers, are ignored.
+pub fn intra_doc_links(markdown: &str) -> Vec<DocLink> {
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut in_block = vec![false; lines.len()];
+    for block in fenced_blocks(&lines) {
+        for flag in &mut in_block[block.open..=block.close.min(lines.len() - 1)] {
+            *flag = true;
+        }
+    }
+    let text_lines = lines
+        .iter()
+        .zip(&in_block)
+        .filter(|(_, in_block)| !**in_block)
+        .map(|(line, _)| *line)
+        .collect::<Vec<_>>();
+
+    let mut definitions = BTreeMap::new();
+    let mut content = vec![];
+    for line in text_lines {
+        match link_definition(line) {
+            Some((label, target)) => {
+                definitions.entry(label.to_lowercase()).or_insert(target);
+            }
+            None => content.push(line),
+        }
+    }
+
+    let mut links = vec![];
+    for line in content {
+        for (text, reference) in markdown_links(line) {
+            let target = match reference {
+                LinkReference::Inline(target) => target,
+                LinkReference::Label(label) => definitions
+                    .get(&label.to_lowercase())
+                    .copied()
+                    .unwrap_or(label),
+            };
+            if let Some(path) = intra_doc_path(target) {
+                links.push(DocLink {
+                    text: text.to_string(),
+                    target: target.to_string(),
+                    path,
+                });
+            }
+        }
+    }
+    links
+}
+
+/// The label and target of a link reference definition, e.g. ``[label]: target``
+fn link_definition(line: &str) -> Option<(&str, &str)> {
+    let rest = line.trim_start().strip_prefix('[')?;
+    let (label, rest) = rest.split_once("]:")?;
+    let target = rest.split_whitespace().next()?;
+    Some((label, target.trim_start_matches('<').trim_end_matches('>')))
+}
+
+/// How the target of a Markdown link is given
+enum LinkReference<'a> {
+    /// ``[text](target)``
+    Inline(&'a str),
+    /// ``[text][label]``, ``[text][]`` or ``[text]``, which may refer to a link definition
+    Label(&'a str),
+}
+
+/// Find the links in a line of Markdown, skipping inline code, images and escaped brackets
+fn markdown_links(line: &str) -> Vec<(&str, LinkReference<'_>)> {
+    let mut links = vec![];
+    let bytes = line.as_bytes();
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'\\' => index += 2,
+            b'`' => {
+                // skip a code span, which is closed by a run of backticks of the same length
+                let run = bytes[index..].iter().take_while(|b| **b == b'`').count();
+                let fence = &line[index..index + run];
+                index = line[index + run..]
+                    .find(fence)
+                    .map_or(index + run, |end| index + run + end + run);
+            }
+            b'[' => {
+                let Some(close) = line[index + 1..].find(']').map(|i| index + 1 + i) else {
+                    break;
+                };
+                let text = &line[index + 1..close];
+                let image = index > 0 && bytes[index - 1] == b'!';
+                let task_marker = matches!(text, " " | "x" | "X")
+                    && matches!(line[..index].trim(), "-" | "*" | "+");
+                let mut end = close + 1;
+                let reference = match bytes.get(end) {
+                    Some(b'(') => {
+                        let mut depth = 0;
+                        let close = line[end..].char_indices().find_map(|(i, c)| {
+                            match c {
+                                '(' => depth += 1,
+                                ')' if depth == 1 => return Some(end + i),
+                                ')' => depth -= 1,
+                                _ => {}
+                            }
+                            None
+                        });
+                        match close {
+                            Some(close) => {
+                                let target = line[end + 1..close].split_whitespace().next();
+                                end = close + 1;
+                                LinkReference::Inline(target.unwrap_or_default())
+                            }
+                            None => LinkReference::Label(text),
+                        }
+                    }
+                    Some(b'[') => match line[end + 1..].find(']') {
+                        Some(close) => {
+                            let label = &line[end + 1..end + 1 + close];
+                            end += close + 2;
+                            LinkReference::Label(if label.is_empty() { text } else { label })
+                        }
+                        None => LinkReference::Label(text),
+                    },
+                    _ => LinkReference::Label(text),
+                };
+                if !image && !task_marker {
+                    links.push((text, reference));
+                }
+                index = end;
+            }
+            _ => index += 1,
+        }
+    }
+    links
+}
+
+/// The item path of an intra-doc link target, or ``None`` if the target is not an item path.
+///
+/// Surrounding backticks, a disambiguator (``struct@``, ``fn@``, ...),
+/// a trailing ``()`` or ``!``, generic arguments and a ``#fragment`` are removed,
+/// e.g. ``Vec::new`` for ``[`fn@Vec<T>::new()`]``.
+fn intra_doc_path(target: &str) -> Option<String> {
+    let target = target.trim().trim_matches('`');
+    let target = target.split('#').next()?;
+    let target = match target.split_once('@') {
+        Some((_, path)) => path,
+        None => target,
+    };
+    let target = target
+        .strip_suffix("()")
+        .or_else(|| target.strip_suffix('!'))
+        .unwrap_or(target);
+    // generic arguments, which may be nested, e.g. ``Vec<Vec<T>>::new``
+    let mut path = String::new();
+    let mut depth = 0;
+    for c in target.chars() {
+        match c {
+            '<' => depth += 1,
+            '>' if depth > 0 => depth -= 1,
+            _ if depth == 0 => path.push(c),
+            _ => {}
+        }
+    }
+    let is_ident = |s: &str| {
+        s.chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_')
+            && s.chars().all(|c| c.is_alphanumeric() || c == '_')
+    };
+    path.split("::").all(is_ident).then_some(path)
+}
+
+/// A recognized docstring section
+#[derive(Debug, Clone, Copy)]
+enum Section<'a> {
+    Panics,
+    Errors,
+    Safety,
+    Examples,
+    /// A project-specific section, with the heading as it was requested
+    Custom(&'a str),
+}
+
+impl DocSections {
+    /// Split a docstring into its conventional sections.
+    ///
+    /// Headings are matched case-insensitively, and a section ends at the next heading
+    /// of the same or a higher level.
+    /// Unrecognized sections are left in the description.
+    pub fn parse(docstring: &str) -> Self {
+        Self::parse_with(docstring, &[])
+    }
+
+    /// Split a docstring into its conventional sections,
+    /// as well as the project-specific sections with the given headings, e.g. ``Complexity``.
+    ///
+    /// The conventional sections take precedence over project-specific sections with the same heading.
+    pub fn parse_with(docstring: &str, custom_headings: &[String]) -> Self {
+        let lines: Vec<&str> = docstring.lines().collect();
+        let headings = markdown_headings(docstring);
+        let mut sections = Self::default();
+        let mut description = vec![];
+        // the recognized section and heading level that the current line belongs to
+        let mut current: Option<(Section, usize)> = None;
+        let mut section_lines: Vec<&str> = vec![];
+        let mut headings = headings.iter().peekable();
+        for (index, line) in lines.iter().enumerate() {
+            let heading = headings.next_if(|h| h.line == index);
+            if let Some(heading) = heading {
+                if current.is_some_and(|(_, level)| heading.level > level) {
+                    // a sub-heading of a recognized section
+                    section_lines.push(line);
+                    continue;
+                }
+                if let Some((name, _)) = current.take() {
+                    sections.add(name, &section_lines);
+                    section_lines.clear();
+                }
+                let name = heading.text.to_lowercase();
+                let section = match name.as_str() {
+                    "panics" => Some(Section::Panics),
+                    "errors" => Some(Section::Errors),
+                    "safety" => Some(Section::Safety),
+                    "examples" => Some(Section::Examples),
+                    _ => custom_headings
+                        .iter()
+                        .find(|h| h.to_lowercase() == name)
+                        .map(|h| Section::Custom(h)),
+                };
+                if let Some(section) = section {
+                    current = Some((section, heading.level));
+                    continue;
+                }
+            }
+            if current.is_some() {
+                section_lines.push(line);
+            } else {
+                description.push(*line);
+            }
+        }
+        if let Some((name, _)) = current {
+            sections.add(name, &section_lines);
+        }
+        sections.description = description.join("\n").trim().to_string();
+        sections
+    }
+
+    /// Add the content of a recognized section,
+    /// appending to any previous section with the same name
+    fn add(&mut self, name: Section, lines: &[&str]) {
+        let section = match name {
+            Section::Panics => &mut self.panics,
+            Section::Errors => &mut self.errors,
+            Section::Safety => &mut self.safety,
+            Section::Examples => &mut self.examples,
+            Section::Custom(heading) => {
+                let previous = self.custom.remove(heading);
+                let content = merge_section(previous, lines);
+                self.custom.insert(heading.to_string(), content);
+                return;
+            }
+        };
+        *section = Some(merge_section(section.take(), lines));
+    }
+}
+
+/// Append the content of a section to any previous content of the same section
+fn merge_section(previous: Option<String>, lines: &[&str]) -> String {
+    let content = lines.join("\n").trim().to_string();
+    match previous {
+        Some(previous) => format!("{}\n\n{}", previous, content),
+        None => content,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_yaml_snapshot;
+
+    #[test]
+    fn test_markdown_headings() {
+        let text = "# Title\n```\n# not a heading\n```\n#not a heading\n### Sub ###";
+        assert_eq!(
+            markdown_headings(text),
+            vec![
+                MarkdownHeading {
+                    line: 0,
+                    level: 1,
+                    text: "Title".to_string()
+                },
+                MarkdownHeading {
+                    line: 5,
+                    level: 3,
+                    text: "Sub".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_table_of_contents() {
+        let text = "# Overview\n\n## The `Socket` type\n```\n# not a heading\n```\n## Errors & panics\n\n## Errors & panics\n### Ünïcode ###";
+        let toc = table_of_contents(text)
+            .into_iter()
+            .map(|h| format!("{} {} ({})", "#".repeat(h.level), h.text, h.slug))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            toc,
+            vec![
+                "# Overview (overview)",
+                "## The `Socket` type (the-socket-type)",
+                "## Errors & panics (errors--panics)",
+                "## Errors & panics (errors--panics-1)",
+                "### Ünïcode (ünïcode)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_broken_examples() {
+        let text = r#"
+```
+# fn main() -> Result<(), Error> {
+let x = parse("1")?;
+# Ok(())
+# }
+```
+```rust
+let x = ;
+```
+```text
+not rust
+```
+```ignore
+let y = ;
+```
+```
+use my_crate::Thing;
+let thing = Thing::new();
+assert!(thing.is_ok())
+```
+```no_run
+fn broken( {}
+```
+"#;
+        let broken = broken_examples(text)
+            .into_iter()
+            .map(|(index, err)| format!("{}: {}", index, err))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            broken,
+            vec![
+                "1: expected an expression",
+                "4: cannot parse string into token stream"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_code_blocks() {
+        let text = r#"Examples:
+
+```
+# use my_crate::thing;
+#
+##[derive(Debug)]
+#[derive(Clone)]
+struct Thing;
+    # let hidden = 1;
+```
+
+```sh
+# this is a comment
+$ cargo run
+```
+
+```rust,no_run
+#![allow(unused)]
+# fn main() {}
+```"#;
+        assert_yaml_snapshot!(code_blocks(text), @r###"
+        ---
+        - info: ""
+          rust: true
+          code: "use my_crate::thing;\n\n#[derive(Debug)]\n#[derive(Clone)]\nstruct Thing;\nlet hidden = 1;"
+          display: "#[derive(Debug)]\n#[derive(Clone)]\nstruct Thing;"
+        - info: sh
+          rust: false
+          code: "# this is a comment\n$ cargo run"
+          display: "# this is a comment\n$ cargo run"
+        - info: "rust,no_run"
+          rust: true
+          code: "#![allow(unused)]\nfn main() {}"
+          display: "#![allow(unused)]"
+        "###);
+        assert_eq!(
+            strip_hidden_lines(text),
+            r#"Examples:
+
+```
+#[derive(Debug)]
+#[derive(Clone)]
+struct Thing;
+```
+
+```sh
+# this is a comment
+$ cargo run
+```
+
+```rust,no_run
+#![allow(unused)]
+```"#
+        );
+    }
+
+    #[test]
+    fn test_doc_sections_parse() {
+        let docstring = r#"Do the thing.
+
+# panics
+
+If the thing is missing.
+
+# Notes
+
+Unknown sections stay in the description.
+
+# Examples
+
+```
+# use my_crate::thing;
+thing();
+```
+
+## Advanced
+
+More examples.
+
+# Errors
+If the thing fails."#;
+        assert_yaml_snapshot!(DocSections::parse(docstring), @r###"
+        ---
+        description: "Do the thing.\n\n# Notes\n\nUnknown sections stay in the description."
+        panics: If the thing is missing.
+        errors: If the thing fails.
+        safety: ~
+        examples: "```\n# use my_crate::thing;\nthing();\n```\n\n## Advanced\n\nMore examples."
+        "###);
+    }
+
+    #[test]
+    fn test_doc_sections_parse_with() {
+        let docstring = r#"Sort the items.
+
+# Complexity
+
+O(n log n)
+
+# Thread safety
+
+Not thread safe.
+
+# Notes
+
+Unknown sections stay in the description.
+
+# complexity
+
+Linear if already sorted."#;
+        let headings = vec!["Complexity".to_string(), "Thread Safety".to_string()];
+        assert_yaml_snapshot!(DocSections::parse_with(docstring, &headings), @r###"
+        ---
+        description: "Sort the items.\n\n# Notes\n\nUnknown sections stay in the description."
+        panics: ~
+        errors: ~
+        safety: ~
+        examples: ~
+        custom:
+          Complexity: "O(n log n)\n\nLinear if already sorted."
+          Thread Safety: Not thread safe.
+        "###);
+    }
+
+    #[test]
+    fn test_intra_doc_links() {
+        let docstring = r#"Uses [`Vec<u8>`], [a socket](net::Socket) and [fn@Foo::bar()][bar].
+
+See [`Self::new`][], [macro vec!](vec!) and [the docs](https://docs.rs#top).
+
+```
+let x = [not_a_link];
+```
+
+[bar]: crate::Foo::bar"#;
+        let links = intra_doc_links(docstring)
+            .into_iter()
+            .map(|l| format!("[{}]({}) -> {}", l.text, l.target, l.path))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            links,
+            vec![
+                "[`Vec<u8>`](`Vec<u8>`) -> Vec",
+                "[a socket](net::Socket) -> net::Socket",
+                "[fn@Foo::bar()](crate::Foo::bar) -> crate::Foo::bar",
+                "[`Self::new`](`Self::new`) -> Self::new",
+                "[macro vec!](vec!) -> vec",
+            ]
+        );
+    }
+}
@@ -2,15 +2,32 @@
 use quote::quote;
 use syn::ItemEnum;
 
-use crate::data_model::{Enum, Field, Variant};
+use crate::data_model::{Enum, Field, Variant, VariantKind};
 
-use super::docstring_from_attrs;
+use super::{
+    anchor_id, attributes_from_attrs, cfg::cfg_from_attrs, doc_aliases_from_attrs,
+    docstring_from_attrs, serde_from_attrs, stability_from_attrs, struct_::fields_kind,
+    type_::convert_generics,
+};
 
 impl Enum {
     /// Fully qualified name of the variant
     pub fn path_str(&self) -> String {
         self.path.join("::")
     }
+    /// A stable, URL-safe identifier for the enum, e.g. for anchor links
+    pub fn anchor_id(&self) -> String {
+        anchor_id("enum", &self.path)
+    }
+    /// Whether the enum is C-like, i.e. has variants, all without fields,
+    /// e.g. ``enum Color { Red = 1, Green = 2 }``, so that its variants can be documented as a table of values
+    pub fn is_c_like(&self) -> bool {
+        !self.variants.is_empty()
+            && self
+                .variants
+                .iter()
+                .all(|v| v.fields_kind == VariantKind::Unit)
+    }
     /// Extract the relevant information from the AST
     pub fn parse(parent: &[&str], ast: &ItemEnum) -> Self {
         let name = ast.ident.to_string();
@@ -22,9 +39,19 @@ impl Enum {
             .map(|v| Variant::parse(&path, v))
             .collect::<Vec<_>>();
         Self {
+            name: name.clone(),
             path: path.iter().map(|s| s.to_string()).collect(),
+            file: None,
             docstring,
+            doc_aliases: doc_aliases_from_attrs(&ast.attrs),
+            attributes: attributes_from_attrs(&ast.attrs),
+            stability: stability_from_attrs(&ast.attrs),
+            generics: convert_generics(&ast.generics),
             variants,
+            deref_target: None,
+            recursive: false,
+            effectively_public: matches!(ast.vis, syn::Visibility::Public(_)),
+            cfg_info: cfg_from_attrs(&ast.attrs).map(|p| p.to_string()),
         }
     }
 }
@@ -34,6 +61,10 @@ impl Variant {
     pub fn name(&self) -> String {
         self.path.join("::")
     }
+    /// A stable, URL-safe identifier for the variant, e.g. for anchor links
+    pub fn anchor_id(&self) -> String {
+        anchor_id("variant", &self.path)
+    }
     /// Extract the relevant information from the AST
     pub fn parse(parent: &[&str], ast: &syn::Variant) -> Self {
         let name = ast.ident.to_string();
@@ -47,17 +78,25 @@ impl Variant {
             .discriminant
             .as_ref()
             .map(|(_, e)| quote! {#e}.to_string());
+        // the fields of a variant are as visible as the enum, and so have no visibility of their own
         let fields = ast
             .fields
             .iter()
             .enumerate()
-            .map(|(i, f)| Field::parse(&path, i, f))
+            .map(|(i, f)| Field {
+                public: true,
+                ..Field::parse(&path, i, f)
+            })
             .collect::<Vec<_>>();
         Self {
+            name: name.clone(),
             path: path.iter().map(|s| s.to_string()).collect(),
             docstring,
             discriminant,
+            fields_kind: fields_kind(&ast.fields),
             fields,
+            cfg: cfg_from_attrs(&ast.attrs).map(|p| p.to_string()),
+            serde: serde_from_attrs(&ast.attrs),
         }
     }
 }
@@ -90,33 +129,47 @@ mod tests {
         let enum_ = Enum::parse(&["crate"], &ast);
         assert_yaml_snapshot!(enum_, @r###"
         ---
+        kind: enum
+        name: MyEnum
         path:
           - crate
           - MyEnum
         docstring: "Multi-line\ndocstring"
+        doc_aliases: []
+        attributes: []
+        generics:
+          params: []
+          where_predicates: []
         variants:
-          - path:
+          - name: MyVariant1
+            path:
               - crate
               - MyEnum
               - MyVariant1
             docstring: variant without fields
             discriminant: ~
+            fields_kind: unit
             fields: []
-          - path:
+          - name: MyVariant2
+            path:
               - crate
               - MyEnum
               - MyVariant2
             docstring: variant with discriminant
             discriminant: "1"
+            fields_kind: unit
             fields: []
-          - path:
+          - name: MyVariant3
+            path:
               - crate
               - MyEnum
               - MyVariant3
             docstring: variant with unnamed fields
             discriminant: ~
+            fields_kind: tuple
             fields:
-              - path:
+              - name: "0"
+                path:
                   - crate
                   - MyEnum
                   - MyVariant3
@@ -124,14 +177,18 @@ mod tests {
                 docstring: ""
                 type_:
                   - Path: u8
-          - path:
+                public: true
+          - name: MyVariant3
+            path:
               - crate
               - MyEnum
               - MyVariant3
             docstring: variant with named fields
             discriminant: ~
+            fields_kind: struct
             fields:
-              - path:
+              - name: field
+                path:
                   - crate
                   - MyEnum
                   - MyVariant3
@@ -139,6 +196,88 @@ mod tests {
                 docstring: field docstring
                 type_:
                   - Path: u8
+                public: true
+        deref_target: ~
+        recursive: false
+        effectively_public: true
+        "###);
+    }
+
+    #[test]
+    fn test_enum_is_c_like() {
+        let is_c_like = [
+            parse_quote! {
+                #[repr(u8)]
+                pub enum Color { Red = 1, Green = 2, Blue }
+            },
+            parse_quote! { pub enum Shape { Point, Circle(f64) } },
+            parse_quote! { pub enum Empty {} },
+            parse_quote! { pub enum Named { Unit, Struct {} } },
+        ]
+        .map(|ast: ItemEnum| Enum::parse(&["crate"], &ast).is_c_like());
+        assert_eq!(is_c_like, [true, false, false, false]);
+    }
+
+    #[test]
+    fn test_parse_enum_serde() {
+        let ast: ItemEnum = parse_quote! {
+            #[derive(Serialize, Deserialize)]
+            pub enum Message {
+                #[serde(rename = "ping")]
+                Ping {
+                    #[serde(rename = "ts", default)]
+                    timestamp: u64,
+                    nonce: u32,
+                },
+                #[serde(skip)]
+                Internal,
+            }
+        };
+        let enum_ = Enum::parse(&["crate"], &ast);
+        let serde = enum_
+            .variants
+            .iter()
+            .flat_map(|v| {
+                Some((v.name(), &v.serde))
+                    .into_iter()
+                    .chain(v.fields.iter().map(|f| (f.path.join("::"), &f.serde)))
+            })
+            .collect::<Vec<_>>();
+        assert_yaml_snapshot!(serde, @r###"
+        ---
+        - - "crate::Message::Ping"
+          - rename: ping
+            aliases: []
+            skip_serializing: false
+            skip_deserializing: false
+            skip_serializing_if: ~
+            default: ~
+            flatten: false
+            arguments:
+              - "rename = \"ping\""
+        - - "crate::Message::Ping::timestamp"
+          - rename: ts
+            aliases: []
+            skip_serializing: false
+            skip_deserializing: false
+            skip_serializing_if: ~
+            default: "Default::default"
+            flatten: false
+            arguments:
+              - "rename = \"ts\""
+              - default
+        - - "crate::Message::Ping::nonce"
+          - ~
+        - - "crate::Message::Internal"
+          - rename: ~
+            aliases: []
+            skip_serializing: true
+            skip_deserializing: true
+            skip_serializing_if: ~
+            default: ~
+            flatten: false
+            arguments:
+              - skip
         "###);
     }
 }
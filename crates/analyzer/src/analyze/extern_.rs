@@ -0,0 +1,133 @@
+//! Analyze extern blocks
+use crate::data_model::{ExternBlock, ForeignStatic, Function};
+
+use super::{anchor_id, docstring_from_attrs, type_::convert_type};
+
+impl ExternBlock {
+    /// Extract the relevant information from the AST
+    pub fn parse(parent: &[&str], ast: &syn::ItemForeignMod) -> Self {
+        let mut functions = vec![];
+        let mut statics = vec![];
+        for item in &ast.items {
+            match item {
+                syn::ForeignItem::Fn(fn_item) => {
+                    if let syn::Visibility::Public(_) = fn_item.vis {
+                        functions.push(Function::parse_signature(
+                            parent,
+                            &fn_item.attrs,
+                            &fn_item.sig,
+                        ));
+                    }
+                }
+                syn::ForeignItem::Static(static_item) => {
+                    if let syn::Visibility::Public(_) = static_item.vis {
+                        statics.push(ForeignStatic {
+                            name: static_item.ident.to_string(),
+                            path: parent
+                                .iter()
+                                .map(|s| s.to_string())
+                                .chain(Some(static_item.ident.to_string()))
+                                .collect(),
+                            docstring: docstring_from_attrs(&static_item.attrs),
+                            mutable: matches!(
+                                static_item.mutability,
+                                syn::StaticMutability::Mut(_)
+                            ),
+                            type_: convert_type(&static_item.ty),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+        Self {
+            parent: parent.iter().map(|s| s.to_string()).collect(),
+            file: None,
+            docstring: docstring_from_attrs(&ast.attrs),
+            abi: ast.abi.name.as_ref().map(|name| name.value()),
+            functions,
+            statics,
+        }
+    }
+}
+
+impl ForeignStatic {
+    /// Fully qualified name of the static
+    pub fn path_str(&self) -> String {
+        self.path.join("::")
+    }
+    /// A stable, URL-safe identifier for the static, e.g. for anchor links
+    pub fn anchor_id(&self) -> String {
+        anchor_id("static", &self.path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_yaml_snapshot;
+
+    #[test]
+    fn test_parse_extern_block() {
+        let ast: syn::ItemForeignMod = syn::parse_quote! {
+            /// Bindings to libc
+            extern "C" {
+                /// Print formatted output
+                pub fn printf(format: *const c_char, ...) -> c_int;
+                fn private();
+                /// The error number
+                pub static mut errno: c_int;
+            }
+        };
+        let block = ExternBlock::parse(&["crate"], &ast);
+        assert_yaml_snapshot!(block, @r###"
+        ---
+        kind: extern_block
+        parent:
+          - crate
+        docstring: Bindings to libc
+        abi: C
+        functions:
+          - kind: function
+            name: printf
+            path:
+              - crate
+              - printf
+            docstring: Print formatted output
+            doc_aliases: []
+            attributes: []
+            generics:
+              params: []
+              where_predicates: []
+            receiver: ~
+            params:
+              - name: format
+                type_:
+                  - String: "*const "
+                  - Path: c_char
+            output:
+              - Path: c_int
+            owner: Free
+            variadic: true
+            is_async: false
+            is_unsafe: false
+            effectively_public: true
+        statics:
+          - name: errno
+            path:
+              - crate
+              - errno
+            docstring: The error number
+            mutable: true
+            type_:
+              - Path: c_int
+        "###);
+        assert_yaml_snapshot!(block.functions[0].signature(), @r###"
+        ---
+        - String: "fn printf(format: *const "
+        - Path: c_char
+        - String: ", ...) -> "
+        - Path: c_int
+        "###);
+    }
+}
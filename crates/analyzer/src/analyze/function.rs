@@ -1,6 +1,19 @@
-use crate::data_model::Function;
+use std::collections::BTreeSet;
 
-use super::docstring_from_attrs;
+use syn::spanned::Spanned;
+
+use crate::data_model::{
+    DocSections, Function, FunctionKind, FunctionOwner, Param, Receiver, ReceiverKind,
+    SignatureChange, TypeSegment, TypeSignature,
+};
+
+use super::{
+    anchor_id, attributes_from_attrs,
+    cfg::cfg_from_attrs,
+    doc_aliases_from_attrs, docstring_from_attrs, stability_from_attrs,
+    type_::{convert_generics, convert_type, merge_strings, render_tokens},
+    type_alias::param_default,
+};
 
 impl Function {
     /// Fully qualified name of the variant
@@ -8,14 +21,326 @@ impl Function {
         self.path.join("::")
     }
     pub fn parse(parent: &[&str], ast: &syn::ItemFn) -> Self {
-        let name = ast.sig.ident.to_string();
+        let mut function = Self::parse_signature(parent, &ast.attrs, &ast.sig);
+        function.effectively_public = matches!(ast.vis, syn::Visibility::Public(_));
+        function
+    }
+    /// Extract the relevant information from the signature of a function or method.
+    ///
+    /// The owner is set to ``Free``, and should be updated by the caller for methods.
+    pub fn parse_signature(
+        parent: &[&str],
+        attrs: &[syn::Attribute],
+        sig: &syn::Signature,
+    ) -> Self {
+        let name = sig.ident.to_string();
         let path: Vec<&str> = parent.iter().copied().chain(Some(name.as_str())).collect();
-        let docstring = docstring_from_attrs(&ast.attrs);
+        let docstring = docstring_from_attrs(attrs);
+        let mut receiver = None;
+        let mut params = vec![];
+        for input in &sig.inputs {
+            match input {
+                syn::FnArg::Receiver(recv) => receiver = Some(parse_receiver(recv)),
+                syn::FnArg::Typed(typed) => params.push(Param {
+                    name: render_tokens(&typed.pat),
+                    type_: convert_type(&typed.ty),
+                }),
+            }
+        }
+        let output = match &sig.output {
+            syn::ReturnType::Default => None,
+            syn::ReturnType::Type(_, ty) => Some(convert_type(ty)),
+        };
         Self {
+            name: name.clone(),
             path: path.iter().map(|s| s.to_string()).collect(),
+            file: None,
             docstring,
+            doc_aliases: doc_aliases_from_attrs(attrs),
+            attributes: attributes_from_attrs(attrs),
+            stability: stability_from_attrs(attrs),
+            generics: convert_generics(&sig.generics),
+            receiver,
+            params,
+            output,
+            owner: FunctionOwner::Free,
+            variadic: sig.variadic.is_some(),
+            is_async: sig.asyncness.is_some(),
+            is_unsafe: sig.unsafety.is_some(),
+            effectively_public: true,
+            cfg_info: cfg_from_attrs(attrs).map(|p| p.to_string()),
+            source_signature: sig.span().source_text(),
+            resolved_signature: None,
+        }
+    }
+    /// A stable, URL-safe identifier for the function, e.g. for anchor links.
+    ///
+    /// Methods of trait impls include the trait, since a type may implement several traits
+    /// with methods of the same name, e.g. ``fmt`` for ``Debug`` and ``Display``.
+    pub fn anchor_id(&self) -> String {
+        match &self.owner {
+            FunctionOwner::Free => anchor_id("fn", &self.path),
+            FunctionOwner::Inherent(_) | FunctionOwner::Trait(_) => anchor_id("method", &self.path),
+            FunctionOwner::TraitImpl { trait_, .. } => {
+                let (name, owner) = self.path.split_last().unwrap_or((&self.name, &[]));
+                let segments = owner
+                    .iter()
+                    .cloned()
+                    .chain(["impl".to_string(), trait_.clone(), name.clone()])
+                    .collect::<Vec<_>>();
+                anchor_id("method", &segments)
+            }
+        }
+    }
+    /// Classify the function as free, or, for a function of an impl or trait,
+    /// as a method if it has a ``self`` receiver, or else as an associated function
+    pub fn kind(&self) -> FunctionKind {
+        match (&self.owner, &self.receiver) {
+            (FunctionOwner::Free, _) => FunctionKind::Free,
+            (_, Some(_)) => FunctionKind::Method,
+            (_, None) => FunctionKind::AssociatedFunction,
         }
     }
+    /// Whether the function is a method, i.e. a function of an impl or trait with a ``self`` receiver
+    pub fn is_method(&self) -> bool {
+        self.kind() == FunctionKind::Method
+    }
+    /// The name of the function
+    pub fn name(&self) -> &str {
+        self.path.last().map(|s| s.as_str()).unwrap_or_default()
+    }
+    /// Name the parameters given only as ``_``, as are the anonymous parameters of trait methods,
+    /// by their position, e.g. ``arg1`` for ``fn get(&self, _: u32, _: bool)``
+    pub fn name_anonymous_params(&mut self) {
+        for (i, param) in self.params.iter_mut().enumerate() {
+            if param.name == "_" {
+                param.name = format!("arg{}", i);
+            }
+        }
+    }
+    /// Split the docstring into its conventional sections, e.g. ``# Panics`` and ``# Examples``
+    pub fn doc_sections(&self) -> DocSections {
+        DocSections::parse(&self.docstring)
+    }
+    /// Split the docstring into its conventional sections,
+    /// as well as the project-specific sections with the given headings, e.g. ``Complexity``
+    pub fn doc_sections_with(&self, custom_headings: &[String]) -> DocSections {
+        DocSections::parse_with(&self.docstring, custom_headings)
+    }
+    /// The names of the generic parameters of the function
+    /// that are referenced in its receiver, parameters or return type
+    pub fn used_generic_params(&self) -> Vec<String> {
+        let types = self.params.iter().map(|p| &p.type_).chain(&self.output);
+        self.generics.referenced_in(
+            self.receiver
+                .iter()
+                .map(|r| r.rendered.as_str())
+                .chain(types.flatten().map(TypeSegment::content)),
+        )
+    }
+    /// Render the signature of the function, with ``Self`` substituted by the self type of the impl
+    /// for methods of impls, e.g. ``fn new() -> MyType<T>`` for ``fn new() -> Self`` in ``impl<T> MyType<T>``.
+    ///
+    /// Methods declared in traits, and free functions, are rendered as in ``signature``.
+    pub fn resolved_signature(&self) -> TypeSignature {
+        self.resolved_signature
+            .clone()
+            .unwrap_or_else(|| self.signature())
+    }
+    /// Classify how the signature of ``other``, e.g. a newer version of the function, differs from this one,
+    /// as seen by callers of the function.
+    ///
+    /// Changes to the receiver, the number or types of the parameters, the return type,
+    /// ``async``, adding ``unsafe``, or the generic parameters are breaking.
+    /// Removing ``unsafe``, adding a generic parameter with a default after the others,
+    /// and removing bounds (or adding ``?Sized``), whether declared on the parameter or in the ``where`` clause,
+    /// are compatible.
+    /// Types are compared as rendered, so equivalent types written differently, e.g. through an alias, differ.
+    pub fn signature_compatible(&self, other: &Function) -> SignatureChange {
+        let rendered = |sig: &TypeSignature| sig.iter().map(|s| s.content()).collect::<String>();
+        if self.receiver != other.receiver
+            || self.params.len() != other.params.len()
+            || self
+                .params
+                .iter()
+                .zip(&other.params)
+                .any(|(a, b)| rendered(&a.type_) != rendered(&b.type_))
+            || self.output.as_ref().map(rendered) != other.output.as_ref().map(rendered)
+            || self.variadic != other.variadic
+            || self.is_async != other.is_async
+            || (other.is_unsafe && !self.is_unsafe)
+        {
+            return SignatureChange::Breaking;
+        }
+        let mut change = SignatureChange::Unchanged;
+        if self
+            .params
+            .iter()
+            .zip(&other.params)
+            .any(|(a, b)| a.name != b.name)
+        {
+            change = SignatureChange::ParamNamesOnly;
+        }
+        if self.is_unsafe && !other.is_unsafe {
+            change = SignatureChange::Compatible;
+        }
+
+        // generic parameters may only be added after the others, with a default,
+        // and const parameters must keep their type
+        let old_params = &self.generics.params;
+        let new_params = &other.generics.params;
+        let without_default = |param: &String| match param_default(param) {
+            Some(default) => param[..param.len() - default.len()]
+                .trim_end()
+                .trim_end_matches('=')
+                .trim_end()
+                .to_string(),
+            None => param.clone(),
+        };
+        let old_names = self.generics.param_names();
+        let new_names = other.generics.param_names();
+        if new_params.len() < old_params.len()
+            || !new_names.starts_with(&old_names)
+            || old_params
+                .iter()
+                .zip(new_params)
+                .any(|(a, b)| a.starts_with("const ") && without_default(a) != without_default(b))
+        {
+            return SignatureChange::Breaking;
+        }
+        let added = &new_names[old_names.len()..];
+        if new_params[old_params.len()..]
+            .iter()
+            .any(|param| param_default(param).is_none())
+        {
+            return SignatureChange::Breaking;
+        }
+        if !added.is_empty() {
+            change = SignatureChange::Compatible;
+        }
+
+        // bounds may be removed, and ``?Sized`` relaxations added, but not the reverse
+        let old_bounds = self.generics.bounds();
+        let new_bounds = other.generics.bounds();
+        let keys: BTreeSet<&String> = old_bounds.keys().chain(new_bounds.keys()).collect();
+        for key in keys.into_iter().filter(|key| !added.contains(key)) {
+            let old = old_bounds.get(key).cloned().unwrap_or_default();
+            let new = new_bounds.get(key).cloned().unwrap_or_default();
+            let relaxed = |bound: &String| bound.starts_with('?');
+            if new.difference(&old).any(|b| !relaxed(b)) || old.difference(&new).any(relaxed) {
+                return SignatureChange::Breaking;
+            }
+            if old != new {
+                change = SignatureChange::Compatible;
+            }
+        }
+        change
+    }
+    /// Render the signature of the function, e.g. ``fn name<T>(&self, a: T) -> u8``
+    pub fn signature(&self) -> TypeSignature {
+        let asyncness = if self.is_async { "async " } else { "" };
+        let unsafety = if self.is_unsafe { "unsafe " } else { "" };
+        let mut sig: TypeSignature =
+            vec![format!("{}{}fn {}", asyncness, unsafety, self.name()).into()];
+        if !self.generics.params.is_empty() {
+            sig.push(format!("<{}>", self.generics.params.join(", ")).into());
+        }
+        sig.push("(".into());
+        let mut first = true;
+        if let Some(receiver) = &self.receiver {
+            sig.push(receiver.rendered.as_str().into());
+            first = false;
+        }
+        for param in &self.params {
+            if !first {
+                sig.push(", ".into());
+            }
+            first = false;
+            sig.push(format!("{}: ", param.name).into());
+            sig.extend(param.type_.iter().cloned());
+        }
+        if self.variadic {
+            sig.push(if first { "..." } else { ", ..." }.into());
+        }
+        sig.push(")".into());
+        if let Some(output) = &self.output {
+            sig.push(" -> ".into());
+            sig.extend(output.iter().cloned());
+        }
+        if !self.generics.where_predicates.is_empty() {
+            sig.push(format!(" where {}", self.generics.where_predicates.join(", ")).into());
+        }
+        merge_strings(sig)
+    }
+}
+
+/// Extract the form of a method receiver, and render it as written
+fn parse_receiver(recv: &syn::Receiver) -> Receiver {
+    let mut lifetime = None;
+    let kind = receiver_kind(&recv.ty, &mut lifetime);
+    Receiver {
+        rendered: render_receiver(recv),
+        kind,
+        lifetime,
+    }
+}
+
+/// Classify the type of ``self`` by its outermost reference or smart pointer,
+/// recording the lifetime of the first reference, e.g. ``'a`` for ``Pin<&'a mut Self>``.
+///
+/// Smart pointers are recognized by the last segment of their path, e.g. ``std::rc::Rc<Self>``.
+fn receiver_kind(ty: &syn::Type, lifetime: &mut Option<String>) -> ReceiverKind {
+    match ty {
+        syn::Type::Reference(ref_) => {
+            *lifetime = ref_.lifetime.as_ref().map(|l| l.to_string());
+            match ref_.mutability {
+                Some(_) => ReceiverKind::RefMut,
+                None => ReceiverKind::Ref,
+            }
+        }
+        syn::Type::Paren(paren) => receiver_kind(&paren.elem, lifetime),
+        syn::Type::Group(group) => receiver_kind(&group.elem, lifetime),
+        syn::Type::Path(path) if path.qself.is_none() => {
+            if path.path.is_ident("Self") {
+                return ReceiverKind::Value;
+            }
+            let Some(last) = path.path.segments.last() else {
+                return ReceiverKind::Other;
+            };
+            let inner = match &last.arguments {
+                syn::PathArguments::AngleBracketed(args) => {
+                    args.args.iter().find_map(|arg| match arg {
+                        syn::GenericArgument::Type(ty) => Some(ty),
+                        _ => None,
+                    })
+                }
+                _ => None,
+            };
+            match (last.ident.to_string().as_str(), inner) {
+                ("Box", Some(_)) => ReceiverKind::Box,
+                ("Rc", Some(_)) => ReceiverKind::Rc,
+                ("Arc", Some(_)) => ReceiverKind::Arc,
+                ("Pin", Some(inner)) => ReceiverKind::Pin(Box::new(receiver_kind(inner, lifetime))),
+                _ => ReceiverKind::Other,
+            }
+        }
+        _ => ReceiverKind::Other,
+    }
+}
+
+/// Render a method receiver as written, e.g. ``&'a mut self`` or ``self: Box<Self>``
+fn render_receiver(recv: &syn::Receiver) -> String {
+    if recv.colon_token.is_some() {
+        let mutability = if recv.mutability.is_some() {
+            "mut "
+        } else {
+            ""
+        };
+        return format!("{}self: {}", mutability, render_tokens(&recv.ty));
+    }
+    let mut recv = recv.clone();
+    recv.attrs.clear();
+    render_tokens(&recv)
 }
 
 #[cfg(test)]
@@ -33,10 +358,323 @@ mod tests {
         let func = Function::parse(&["my_module"], &item);
         assert_yaml_snapshot!(func, @r###"
         ---
+        kind: function
+        name: my_function
         path:
           - my_module
           - my_function
         docstring: This is a docstring
+        doc_aliases: []
+        attributes: []
+        generics:
+          params: []
+          where_predicates: []
+        receiver: ~
+        params: []
+        output: ~
+        owner: Free
+        variadic: false
+        is_async: false
+        is_unsafe: false
+        effectively_public: true
+        "###);
+    }
+
+    #[test]
+    fn test_function_parse_signature() {
+        let item: syn::ImplItemFn = syn::parse_quote! {
+            pub fn my_method<'a, T: Clone>(&'a mut self, a: &'a T, (b, c): (u8, u8)) -> Option<T> {}
+        };
+        let func = Function::parse_signature(&["MyType"], &item.attrs, &item.sig);
+        assert_yaml_snapshot!(func, @r###"
+        ---
+        kind: function
+        name: my_method
+        path:
+          - MyType
+          - my_method
+        docstring: ""
+        doc_aliases: []
+        attributes: []
+        generics:
+          params:
+            - "'a"
+            - "T: Clone"
+          where_predicates: []
+        receiver:
+          rendered: "&'a mut self"
+          kind: ref_mut
+          lifetime: "'a"
+        params:
+          - name: a
+            type_:
+              - String: "&'a "
+              - Path: T
+          - name: "(b, c)"
+            type_:
+              - String: (
+              - Path: u8
+              - String: ", "
+              - Path: u8
+              - String: )
+        output:
+          - Path: Option<T>
+        owner: Free
+        variadic: false
+        is_async: false
+        is_unsafe: false
+        effectively_public: true
+        "###);
+        assert_yaml_snapshot!(func.signature(), @r###"
+        ---
+        - String: "fn my_method<'a, T: Clone>(&'a mut self, a: &'a "
+        - Path: T
+        - String: ", (b, c): ("
+        - Path: u8
+        - String: ", "
+        - Path: u8
+        - String: ")) -> "
+        - Path: Option<T>
+        "###);
+    }
+
+    #[test]
+    fn test_function_anchor_id() {
+        let item: syn::ItemFn = syn::parse_quote! { pub fn my_function() {} };
+        let func = Function::parse(&["my_crate"], &item);
+        assert_eq!(func.anchor_id(), "fn.my_crate.my_function");
+
+        let impls = [
+            syn::parse_quote! { impl MyType { pub fn fmt(&self) {} } },
+            syn::parse_quote! { impl Display for MyType { fn fmt(&self) {} } },
+            syn::parse_quote! { impl fmt::Debug for MyType { fn fmt(&self) {} } },
+            syn::parse_quote! { impl From<u8> for MyType { fn from(v: u8) -> Self {} } },
+            syn::parse_quote! { impl From<&str> for MyType { fn from(v: &str) -> Self {} } },
+        ]
+        .map(|item: syn::ItemImpl| crate::data_model::Impl::parse(&["my_crate"], &item));
+        let ids = impls
+            .iter()
+            .flat_map(|i| i.functions.iter().map(|f| f.anchor_id()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            vec![
+                "method.my_crate.MyType.fmt",
+                "method.my_crate.MyType.impl.Display.fmt",
+                "method.my_crate.MyType.impl.fmt-3a--3a-Debug.fmt",
+                "method.my_crate.MyType.impl.From-3c-u8-3e-.from",
+                "method.my_crate.MyType.impl.From-3c--26-str-3e-.from",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_function_parse_typed_receiver() {
+        let item: syn::ImplItemFn = syn::parse_quote! {
+            fn my_method(self: Box<Self>) {}
+        };
+        let func = Function::parse_signature(&["MyType"], &item.attrs, &item.sig);
+        assert_eq!(
+            func.receiver.map(|r| r.rendered).as_deref(),
+            Some("self: Box<Self>")
+        );
+    }
+
+    #[test]
+    fn test_function_parse_receiver_kinds() {
+        let item: syn::ItemImpl = syn::parse_quote! {
+            impl MyType {
+                pub fn by_value(self) {}
+                pub fn by_mut_value(mut self) {}
+                pub fn by_ref(&'a self) {}
+                pub fn by_mut_ref(&mut self) {}
+                pub fn typed_ref(self: &Self) {}
+                pub fn boxed(self: Box<Self>) {}
+                pub fn counted(self: std::rc::Rc<Self>) {}
+                pub fn shared(self: Arc<Self>) {}
+                pub fn pinned(self: Pin<&'a mut Self>) {}
+                pub fn pinned_box(self: Pin<Box<Self>>) {}
+                pub fn other(self: Cow<'_, Self>) {}
+                pub fn associated() {}
+            }
+        };
+        let impl_ = crate::data_model::Impl::parse(&["my_crate"], &item);
+        let receivers = impl_
+            .functions
+            .iter()
+            .map(|f| (f.name.clone(), f.receiver.clone()))
+            .collect::<Vec<_>>();
+        assert_yaml_snapshot!(receivers, @r###"
+        ---
+        - - by_value
+          - rendered: self
+            kind: value
+        - - by_mut_value
+          - rendered: mut self
+            kind: value
+        - - by_ref
+          - rendered: "&'a self"
+            kind: ref
+            lifetime: "'a"
+        - - by_mut_ref
+          - rendered: "&mut self"
+            kind: ref_mut
+        - - typed_ref
+          - rendered: "self: &Self"
+            kind: ref
+        - - boxed
+          - rendered: "self: Box<Self>"
+            kind: box
+        - - counted
+          - rendered: "self: std::rc::Rc<Self>"
+            kind: rc
+        - - shared
+          - rendered: "self: Arc<Self>"
+            kind: arc
+        - - pinned
+          - rendered: "self: Pin<&'a mut Self>"
+            kind:
+              pin: ref_mut
+            lifetime: "'a"
+        - - pinned_box
+          - rendered: "self: Pin<Box<Self>>"
+            kind:
+              pin: box
+        - - other
+          - rendered: "self: Cow<'_, Self>"
+            kind: other
+        - - associated
+          - ~
+        "###);
+        assert_yaml_snapshot!(impl_.functions[8].resolved_signature(), @r###"
+        ---
+        - String: "fn pinned(self: Pin<&'a mut MyType>)"
+        "###);
+    }
+
+    #[test]
+    fn test_function_used_generic_params() {
+        let item: syn::ImplItemFn = syn::parse_quote! {
+            fn my_method<'a, 'b, T: Clone, U, V, const N: usize>(&'a self, a: [T; N], b: V::Item) -> U::Output
+            where
+                U: Add<Output = T>,
+            {}
+        };
+        let func = Function::parse_signature(&["MyType"], &item.attrs, &item.sig);
+        assert_eq!(
+            func.generics.param_names(),
+            vec!["'a", "'b", "T", "U", "V", "N"]
+        );
+        assert_eq!(func.used_generic_params(), vec!["'a", "T", "U", "V", "N"]);
+    }
+
+    #[test]
+    fn test_function_parse_assoc_bindings() {
+        let item: syn::ItemFn = syn::parse_quote! {
+            pub fn my_function<I: Iterator<Item = u8>, T>(iter: I, other: Box<dyn Iterator<Item = T>>) -> impl Iterator<Item = u8>
+            where
+                I::Item: Display,
+                T: Shape<SIDES = 4>,
+            {}
+        };
+        let func = Function::parse(&["my_module"], &item);
+        assert_yaml_snapshot!(func.signature(), @r###"
+        ---
+        - String: "fn my_function<I: Iterator<Item = u8>, T>(iter: "
+        - Path: I
+        - String: ", other: "
+        - Path: Box<dyn Iterator<Item = T>>
+        - String: ") -> impl "
+        - Path: Iterator<Item = u8>
+        - String: " where I::Item: Display, T: Shape<SIDES = 4>"
         "###);
     }
+
+    #[test]
+    fn test_function_signature_compatible() {
+        let parse =
+            |item: syn::ItemFn| Function::parse_signature(&["crate"], &item.attrs, &item.sig);
+        let cases: Vec<(syn::ItemFn, syn::ItemFn, SignatureChange)> = vec![
+            (
+                syn::parse_quote! { fn f(a: u8) -> u8 {} },
+                syn::parse_quote! { fn f(a: u8) -> u8 {} },
+                SignatureChange::Unchanged,
+            ),
+            (
+                syn::parse_quote! { fn f(a: u8) {} },
+                syn::parse_quote! { fn f(value: u8) {} },
+                SignatureChange::ParamNamesOnly,
+            ),
+            (
+                syn::parse_quote! { fn f<T: Clone + Debug>(a: T) {} },
+                syn::parse_quote! { fn f<T>(a: T) where T: Clone {} },
+                SignatureChange::Compatible,
+            ),
+            (
+                syn::parse_quote! { fn f<T: Clone>(a: T) {} },
+                syn::parse_quote! { fn f<T>(a: T) where T: Clone {} },
+                SignatureChange::Unchanged,
+            ),
+            (
+                syn::parse_quote! { fn f<T>(a: &T) {} },
+                syn::parse_quote! { fn f<T: ?Sized, U: Default = u8>(a: &T) {} },
+                SignatureChange::Compatible,
+            ),
+            (
+                syn::parse_quote! { unsafe fn f() {} },
+                syn::parse_quote! { fn f() {} },
+                SignatureChange::Compatible,
+            ),
+            (
+                syn::parse_quote! { fn f<T>(a: T) {} },
+                syn::parse_quote! { fn f<T: Send>(a: T) {} },
+                SignatureChange::Breaking,
+            ),
+            (
+                syn::parse_quote! { fn f<T: ?Sized>(a: &T) {} },
+                syn::parse_quote! { fn f<T>(a: &T) {} },
+                SignatureChange::Breaking,
+            ),
+            (
+                syn::parse_quote! { fn f<T>(a: T) {} },
+                syn::parse_quote! { fn f<T, U>(a: T) {} },
+                SignatureChange::Breaking,
+            ),
+            (
+                syn::parse_quote! { fn f<const N: usize>() {} },
+                syn::parse_quote! { fn f<const N: u32>() {} },
+                SignatureChange::Breaking,
+            ),
+            (
+                syn::parse_quote! { fn f(a: u8) {} },
+                syn::parse_quote! { fn f(a: u16) {} },
+                SignatureChange::Breaking,
+            ),
+            (
+                syn::parse_quote! { fn f(&self) {} },
+                syn::parse_quote! { fn f(&mut self) {} },
+                SignatureChange::Breaking,
+            ),
+            (
+                syn::parse_quote! { fn f() {} },
+                syn::parse_quote! { fn f() -> u8 {} },
+                SignatureChange::Breaking,
+            ),
+            (
+                syn::parse_quote! { fn f() {} },
+                syn::parse_quote! { async fn f() {} },
+                SignatureChange::Breaking,
+            ),
+        ];
+        for (old, new, expected) in cases {
+            let (old, new) = (parse(old), parse(new));
+            assert_eq!(
+                old.signature_compatible(&new),
+                expected,
+                "{:?} -> {:?}",
+                old.signature(),
+                new.signature()
+            );
+        }
+    }
 }
@@ -0,0 +1,784 @@
+//! Analyze impl blocks
+use syn::ItemImpl;
+
+use crate::data_model::{AssocType, Function, FunctionOwner, Impl, TypeSegment, TypeSignature};
+
+use super::{
+    cfg::{cfg_from_attrs, combine_cfg},
+    docstring_from_attrs,
+    type_::{convert_generics, convert_type, merge_strings},
+};
+
+/// Wrapper types that are "fundamental" to their contents,
+/// i.e. an impl on ``Box<T>`` is documented as an impl on ``T``
+const FUNDAMENTAL_WRAPPERS: &[&str] = &["Box", "Pin"];
+
+impl Impl {
+    /// Extract the relevant information from the AST
+    pub fn parse(parent: &[&str], ast: &ItemImpl) -> Self {
+        let docstring = docstring_from_attrs(&ast.attrs);
+        let self_type = convert_type(&ast.self_ty);
+        let trait_ = ast.trait_.as_ref().map(|(bang, path, _)| {
+            let mut trait_ = vec![];
+            if bang.is_some() {
+                trait_.push("!".into());
+            }
+            trait_.extend(convert_type(&syn::Type::Path(syn::TypePath {
+                qself: None,
+                path: path.clone(),
+            })));
+            trait_
+        });
+        let base_type = base_type(&ast.self_ty, &ast.generics);
+
+        // methods are namespaced by the base type if there is one, otherwise the rendered self type
+        let owner = match &base_type {
+            Some(base) => resolve_relative_path(parent, base),
+            None => [
+                parent.iter().map(|s| s.to_string()).collect(),
+                vec![self_type.iter().map(|s| s.content()).collect::<String>()],
+            ]
+            .concat(),
+        };
+        let owner = owner.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+        let function_owner = match &trait_ {
+            Some(trait_) => FunctionOwner::TraitImpl {
+                type_: owner.join("::"),
+                trait_: trait_.iter().map(|s| s.content()).collect(),
+            },
+            None => FunctionOwner::Inherent(owner.join("::")),
+        };
+        let cfg = cfg_from_attrs(&ast.attrs).map(|p| p.to_string());
+        let mut functions = vec![];
+        let mut assoc_types = vec![];
+        for item in &ast.items {
+            match item {
+                // trait impl methods are public if the trait is
+                syn::ImplItem::Fn(fn_item)
+                    if trait_.is_some() || matches!(fn_item.vis, syn::Visibility::Public(_)) =>
+                {
+                    let mut function =
+                        Function::parse_signature(&owner, &fn_item.attrs, &fn_item.sig);
+                    function.owner = function_owner.clone();
+                    function.resolved_signature =
+                        Some(substitute_self(&function.signature(), &self_type));
+                    function.cfg_info = combine_cfg([cfg.as_deref(), function.cfg_info.as_deref()]);
+                    functions.push(function);
+                }
+                syn::ImplItem::Type(type_item) => assoc_types.push(AssocType {
+                    name: type_item.ident.to_string(),
+                    generics: convert_generics(&type_item.generics),
+                    type_: convert_type(&type_item.ty),
+                }),
+                _ => {}
+            }
+        }
+
+        Self {
+            parent: parent.iter().map(|s| s.to_string()).collect(),
+            file: None,
+            docstring,
+            self_type,
+            trait_,
+            base_type,
+            generics: convert_generics(&ast.generics),
+            functions,
+            assoc_types,
+        }
+    }
+
+    /// The ``Target`` type, if this is an impl of ``Deref``
+    pub fn deref_target(&self) -> Option<&TypeSignature> {
+        let trait_ = self
+            .trait_
+            .as_ref()?
+            .iter()
+            .map(|s| s.content())
+            .collect::<String>();
+        let name = trait_.split('<').next()?.rsplit("::").next()?;
+        if name != "Deref" {
+            return None;
+        }
+        self.assoc_types
+            .iter()
+            .find(|t| t.name == "Target")
+            .map(|t| &t.type_)
+    }
+
+    /// Whether this is a blanket impl of a trait, i.e. over a generic parameter of the impl,
+    /// or a reference to one, e.g. ``impl<T: Display> ToString for T`` or ``impl<T> Foo for &T``
+    pub fn is_blanket(&self) -> bool {
+        if self.trait_.is_none() {
+            return false;
+        }
+        let self_type = self
+            .self_type
+            .iter()
+            .map(|s| s.content())
+            .collect::<String>();
+        let Ok(mut ty) = syn::parse_str::<syn::Type>(&self_type) else {
+            return false;
+        };
+        while let syn::Type::Reference(ref_) = ty {
+            ty = *ref_.elem;
+        }
+        match ty {
+            syn::Type::Path(path) if path.qself.is_none() => path
+                .path
+                .get_ident()
+                .is_some_and(|ident| self.generics.param_names().contains(&ident.to_string())),
+            _ => false,
+        }
+    }
+
+    /// Render the header of the impl, e.g. ``impl<const N: usize> Foo for [u8; N] where [(); N]: Sized``
+    pub fn signature(&self) -> TypeSignature {
+        // the generics are already rendered, and may contain const expressions such as ``{ N > 0 }``,
+        // so are not normalised by converting them with ``into``
+        let mut sig: TypeSignature = vec!["impl".into()];
+        if !self.generics.params.is_empty() {
+            sig.push(TypeSegment::String(format!(
+                "<{}>",
+                self.generics.params.join(", ")
+            )));
+        }
+        sig.push(" ".into());
+        if let Some(trait_) = &self.trait_ {
+            sig.extend(trait_.iter().cloned());
+            sig.push(" for ".into());
+        }
+        sig.extend(self.self_type.iter().cloned());
+        if !self.generics.where_predicates.is_empty() {
+            sig.push(TypeSegment::String(format!(
+                " where {}",
+                self.generics.where_predicates.join(", ")
+            )));
+        }
+        merge_strings(sig)
+    }
+
+    /// Substitute ``Self`` in a signature with the concrete self type of the impl,
+    /// and ``Self``-qualified associated types with their types in the impl,
+    /// e.g. ``fn new() -> Self`` becomes ``fn new() -> MyType<T>``
+    pub fn monomorphize(&self, signature: &TypeSignature) -> TypeSignature {
+        substitute_self(&self.resolve_assoc_types(signature), &self.self_type)
+    }
+
+    /// Substitute the ``Self``-qualified associated types in a signature with their types
+    /// in the impl, e.g. ``fn next(&mut self) -> Option<Self::Item>``
+    /// becomes ``fn next(&mut self) -> Option<u8>`` for ``type Item = u8;``.
+    ///
+    /// Generic associated types, e.g. ``type Item<'a> = &'a T;``, are left untouched.
+    pub fn resolve_assoc_types(&self, signature: &TypeSignature) -> TypeSignature {
+        let mut signature = signature.clone();
+        for assoc_type in &self.assoc_types {
+            if !assoc_type.generics.params.is_empty() {
+                continue;
+            }
+            let qualified = format!("Self::{}", assoc_type.name);
+            let rendered = assoc_type
+                .type_
+                .iter()
+                .map(|s| s.content())
+                .collect::<String>();
+            let mut output = vec![];
+            for segment in signature {
+                match segment {
+                    TypeSegment::Path(path) if path == qualified => {
+                        output.extend(assoc_type.type_.iter().cloned())
+                    }
+                    segment => output.push(segment.with_content(replace_assoc_type(
+                        segment.content(),
+                        &qualified,
+                        &rendered,
+                    ))),
+                }
+            }
+            signature = merge_strings(output);
+        }
+        signature
+    }
+
+    /// The signatures of the impl functions, with ``Self`` substituted by the concrete self type
+    pub fn monomorphized_signatures(&self) -> Vec<TypeSignature> {
+        self.functions
+            .iter()
+            .map(|func| self.monomorphize(&func.signature()))
+            .collect()
+    }
+}
+
+/// Substitute standalone ``Self`` identifiers in a signature with the given type,
+/// leaving associated paths such as ``Self::Item`` untouched
+pub(super) fn substitute_self(
+    signature: &TypeSignature,
+    self_type: &TypeSignature,
+) -> TypeSignature {
+    let self_type_str = self_type.iter().map(|s| s.content()).collect::<String>();
+    let mut output = vec![];
+    for segment in signature {
+        match segment {
+            TypeSegment::Path(path) if path == "Self" => output.extend(self_type.iter().cloned()),
+            segment => {
+                output.push(segment.with_content(replace_self(segment.content(), &self_type_str)))
+            }
+        }
+    }
+    merge_strings(output)
+}
+
+/// Replace standalone ``Self`` identifiers in a string,
+/// leaving associated paths such as ``Self::Item`` untouched
+fn replace_self(text: &str, self_type: &str) -> String {
+    let mut output = String::new();
+    let mut rest = text;
+    while let Some(index) = rest.find("Self") {
+        let (before, after) = rest.split_at(index);
+        let after = &after[4..];
+        output.push_str(before);
+        let is_ident_char = |c: char| c.is_alphanumeric() || c == '_';
+        let standalone = !before.ends_with(is_ident_char)
+            && !after.starts_with(is_ident_char)
+            && !after.starts_with("::");
+        output.push_str(if standalone { self_type } else { "Self" });
+        rest = after;
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Replace a ``Self``-qualified associated type in a string, e.g. ``Self::Item``,
+/// unless it is part of a longer identifier or path, such as ``Self::Items`` or ``Self::Item::Key``
+fn replace_assoc_type(text: &str, qualified: &str, type_: &str) -> String {
+    let mut output = String::new();
+    let mut rest = text;
+    while let Some(index) = rest.find(qualified) {
+        let (before, after) = rest.split_at(index);
+        let after = &after[qualified.len()..];
+        output.push_str(before);
+        let is_ident_char = |c: char| c.is_alphanumeric() || c == '_';
+        let standalone = !before.ends_with(is_ident_char)
+            && !before.ends_with("::")
+            && !after.starts_with(is_ident_char)
+            && !after.starts_with("::");
+        output.push_str(if standalone { type_ } else { qualified });
+        rest = after;
+    }
+    output.push_str(rest);
+    output
+}
+
+/// Resolve a path as written in a module to a fully qualified path,
+/// handling the ``crate``, ``self`` and ``super`` prefixes.
+///
+/// Paths without a prefix are assumed to be relative to the module.
+pub(crate) fn resolve_relative_path(parent: &[&str], path: &str) -> Vec<String> {
+    let mut resolved: Vec<String> = parent.iter().map(|s| s.to_string()).collect();
+    for (i, segment) in path.split("::").enumerate() {
+        match segment {
+            "crate" if i == 0 => resolved.truncate(1),
+            "self" if i == 0 => {}
+            "super" => {
+                if resolved.len() > 1 {
+                    resolved.pop();
+                }
+            }
+            _ => resolved.push(segment.to_string()),
+        }
+    }
+    resolved
+}
+
+/// Find the path of the type that an impl should be attributed to
+fn base_type(ty: &syn::Type, generics: &syn::Generics) -> Option<String> {
+    match ty {
+        syn::Type::Reference(ref_) => base_type(&ref_.elem, generics),
+        syn::Type::Ptr(ptr) => base_type(&ptr.elem, generics),
+        syn::Type::Paren(paren) => base_type(&paren.elem, generics),
+        syn::Type::Group(group) => base_type(&group.elem, generics),
+        syn::Type::Path(path) if path.qself.is_none() => {
+            let last = path.path.segments.last()?;
+            if path.path.segments.len() == 1
+                && generics
+                    .type_params()
+                    .any(|param| param.ident == last.ident)
+            {
+                // a blanket impl over a generic parameter
+                return None;
+            }
+            if FUNDAMENTAL_WRAPPERS.contains(&last.ident.to_string().as_str()) {
+                if let syn::PathArguments::AngleBracketed(args) = &last.arguments {
+                    if let [syn::GenericArgument::Type(inner)] =
+                        args.args.iter().collect::<Vec<_>>().as_slice()
+                    {
+                        return base_type(inner, generics);
+                    }
+                }
+            }
+            Some(
+                path.path
+                    .segments
+                    .iter()
+                    .map(|s| s.ident.to_string())
+                    .collect::<Vec<_>>()
+                    .join("::"),
+            )
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_yaml_snapshot;
+    use syn::parse_quote;
+
+    #[test]
+    fn test_parse_impl_inherent() {
+        let ast: ItemImpl = parse_quote! {
+            /// Docstring
+            impl MyType {}
+        };
+        let impl_ = Impl::parse(&["crate"], &ast);
+        assert_yaml_snapshot!(impl_, @r###"
+        ---
+        kind: impl
+        parent:
+          - crate
+        docstring: Docstring
+        self_type:
+          - Path: MyType
+        trait_: ~
+        base_type: MyType
+        generics:
+          params: []
+          where_predicates: []
+        functions: []
+        assoc_types: []
+        "###);
+    }
+
+    #[test]
+    fn test_parse_impl_where_clause() {
+        let ast: ItemImpl = parse_quote! {
+            impl<T> Foo for Bar<T> where T: Clone, Vec<T>: Debug {
+                fn foo<U>(&self, u: U) where U: Into<T> {}
+            }
+        };
+        let impl_ = Impl::parse(&["crate"], &ast);
+        assert_eq!(impl_.generics.params, vec!["T"]);
+        assert_eq!(
+            impl_.generics.where_predicates,
+            vec!["T: Clone", "Vec<T>: Debug"]
+        );
+        assert_yaml_snapshot!(impl_.functions[0].signature(), @r###"
+        ---
+        - String: "fn foo<U>(&self, u: "
+        - Path: U
+        - String: ") where U: Into<T>"
+        "###);
+    }
+
+    #[test]
+    fn test_parse_impl_functions() {
+        let ast: ItemImpl = parse_quote! {
+            impl<T: Clone> MyType<T> {
+                /// Create a new instance
+                pub fn new(value: T) -> Self {}
+                pub fn get(&self) -> &Self {}
+                pub fn items(&self) -> Self::Item {}
+                fn private() {}
+            }
+        };
+        let impl_ = Impl::parse(&["crate", "module"], &ast);
+        assert_eq!(impl_.generics.params, vec!["T: Clone"]);
+        assert_eq!(
+            impl_
+                .functions
+                .iter()
+                .map(|f| f.path_str())
+                .collect::<Vec<_>>(),
+            vec![
+                "crate::module::MyType::new",
+                "crate::module::MyType::get",
+                "crate::module::MyType::items"
+            ]
+        );
+        assert_eq!(
+            impl_.functions[0].owner,
+            FunctionOwner::Inherent("crate::module::MyType".to_string())
+        );
+        assert_yaml_snapshot!(impl_.monomorphized_signatures(), @r###"
+        ---
+        - - String: "fn new(value: "
+          - Path: T
+          - String: ") -> "
+          - Path: MyType<T>
+        - - String: fn get(&self) -> &
+          - Path: MyType<T>
+        - - String: "fn items(&self) -> "
+          - Path: "Self::Item"
+        "###);
+    }
+
+    #[test]
+    fn test_parse_impl_trait_functions() {
+        let ast: ItemImpl = parse_quote! {
+            impl Clone for crate::other::MyType {
+                fn clone(&self) -> Self {}
+            }
+        };
+        let impl_ = Impl::parse(&["crate", "module"], &ast);
+        assert_eq!(
+            impl_
+                .functions
+                .iter()
+                .map(|f| f.path_str())
+                .collect::<Vec<_>>(),
+            vec!["crate::other::MyType::clone"]
+        );
+        assert_eq!(
+            impl_.functions[0].owner,
+            FunctionOwner::TraitImpl {
+                type_: "crate::other::MyType".to_string(),
+                trait_: "Clone".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_resolve_relative_path() {
+        assert_eq!(resolve_relative_path(&["a", "b"], "C"), vec!["a", "b", "C"]);
+        assert_eq!(
+            resolve_relative_path(&["a", "b"], "crate::C"),
+            vec!["a", "C"]
+        );
+        assert_eq!(
+            resolve_relative_path(&["a", "b"], "self::C"),
+            vec!["a", "b", "C"]
+        );
+        assert_eq!(
+            resolve_relative_path(&["a", "b"], "super::C"),
+            vec!["a", "C"]
+        );
+    }
+
+    #[test]
+    fn test_replace_self() {
+        assert_eq!(replace_self("Option<Self>", "A"), "Option<A>");
+        assert_eq!(replace_self("Self::Item", "A"), "Self::Item");
+        assert_eq!(replace_self("SelfRef", "A"), "SelfRef");
+    }
+
+    #[test]
+    fn test_parse_impl_reference() {
+        let ast: ItemImpl = parse_quote! {
+            impl<'a> std::fmt::Display for &'a mut MyType {}
+        };
+        let impl_ = Impl::parse(&["crate"], &ast);
+        assert_yaml_snapshot!(impl_, @r###"
+        ---
+        kind: impl
+        parent:
+          - crate
+        docstring: ""
+        self_type:
+          - String: "&'a mut "
+          - Path: MyType
+        trait_:
+          - Path: "std::fmt::Display"
+        base_type: MyType
+        generics:
+          params:
+            - "'a"
+          where_predicates: []
+        functions: []
+        assoc_types: []
+        "###);
+    }
+
+    #[test]
+    fn test_parse_impl_box() {
+        let ast: ItemImpl = parse_quote! {
+            impl Foo for Box<module::MyType> {}
+        };
+        let impl_ = Impl::parse(&["crate"], &ast);
+        assert_yaml_snapshot!(impl_, @r###"
+        ---
+        kind: impl
+        parent:
+          - crate
+        docstring: ""
+        self_type:
+          - Path: "Box<module::MyType>"
+        trait_:
+          - Path: Foo
+        base_type: "module::MyType"
+        generics:
+          params: []
+          where_predicates: []
+        functions: []
+        assoc_types: []
+        "###);
+    }
+
+    #[test]
+    fn test_parse_impl_deref() {
+        let ast: ItemImpl = parse_quote! {
+            impl<T> std::ops::Deref for MyBox<T> {
+                type Target = T;
+                fn deref(&self) -> &T {}
+            }
+        };
+        let impl_ = Impl::parse(&["crate"], &ast);
+        assert_eq!(
+            impl_.deref_target(),
+            Some(&vec![TypeSegment::Path("T".to_string())])
+        );
+
+        let ast: ItemImpl = parse_quote! {
+            impl Iterator for MyIter {
+                type Item = u8;
+            }
+        };
+        let impl_ = Impl::parse(&["crate"], &ast);
+        assert_eq!(impl_.assoc_types.len(), 1);
+        assert_eq!(impl_.deref_target(), None);
+    }
+
+    #[test]
+    fn test_impl_signature_const_generics() {
+        let ast: ItemImpl = parse_quote! {
+            impl<const N: usize, T: Default + Copy> Buffer for [T; N]
+            where
+                [(); N * 2]: Sized,
+                Assert<{ N > 0 }>: IsTrue,
+            {}
+        };
+        let impl_ = Impl::parse(&["crate"], &ast);
+        assert_eq!(
+            impl_.generics.params,
+            vec!["const N: usize", "T: Default + Copy"]
+        );
+        assert_yaml_snapshot!(impl_.signature(), @r###"
+        ---
+        - String: "impl<const N: usize, T: Default + Copy> "
+        - Path: Buffer
+        - String: " for ["
+        - Path: T
+        - String: "; N] where [(); N * 2]: Sized, Assert<{ N > 0 }>: IsTrue"
+        "###);
+
+        let ast: ItemImpl = parse_quote! {
+            impl<const N: usize> Matrix<N, { N + 1 }> {}
+        };
+        let impl_ = Impl::parse(&["crate"], &ast);
+        assert_yaml_snapshot!(impl_.signature(), @r###"
+        ---
+        - String: "impl<const N: usize> "
+        - Path: "Matrix<N, { N + 1 }>"
+        "###);
+    }
+
+    #[test]
+    fn test_impl_resolve_assoc_types() {
+        let ast: ItemImpl = parse_quote! {
+            impl<'a, T> Iterator for Windows<'a, T> {
+                type Item = &'a [T];
+                type Items = Vec<Self::Item>;
+                type Lent<'b> = &'b T where Self: 'b;
+                fn next(&mut self) -> Option<Self::Item> {}
+                fn nth(&mut self, n: usize) -> Self::Item {}
+                fn collect(self) -> (Self::Items, Self::Item::Key, Self::Lent<'a>) {}
+            }
+        };
+        let impl_ = Impl::parse(&["crate"], &ast);
+        assert_yaml_snapshot!(impl_.monomorphized_signatures(), @r###"
+        ---
+        - - String: "fn next(&mut self) -> "
+          - Path: "Option<&'a [T]>"
+        - - String: "fn nth(&mut self, n: "
+          - Path: usize
+          - String: ") -> &'a ["
+          - Path: T
+          - String: "]"
+        - - String: fn collect(self) -> (
+          - Path: "Vec<Self::Item>"
+          - String: ", "
+          - Path: "Self::Item::Key"
+          - String: ", "
+          - Path: "Self::Lent<'a>"
+          - String: )
+        "###);
+    }
+
+    #[test]
+    fn test_parse_impl_blanket() {
+        let ast: ItemImpl = parse_quote! {
+            impl<T> Foo for Box<T> {}
+        };
+        let impl_ = Impl::parse(&["crate"], &ast);
+        assert_eq!(impl_.base_type, None);
+        let ast: ItemImpl = parse_quote! {
+            impl<T: Foo> Foo for &T {}
+        };
+        let impl_ = Impl::parse(&["crate"], &ast);
+        assert_eq!(impl_.base_type, None);
+    }
+
+    #[test]
+    fn test_parse_impl_no_base() {
+        let ast: ItemImpl = parse_quote! {
+            impl Foo for [MyType] {}
+        };
+        let impl_ = Impl::parse(&["crate"], &ast);
+        assert_yaml_snapshot!(impl_, @r###"
+        ---
+        kind: impl
+        parent:
+          - crate
+        docstring: ""
+        self_type:
+          - String: "["
+          - Path: MyType
+          - String: "]"
+        trait_:
+          - Path: Foo
+        base_type: ~
+        generics:
+          params: []
+          where_predicates: []
+        functions: []
+        assoc_types: []
+        "###);
+        let ast: ItemImpl = parse_quote! {
+            impl !Send for (MyType, u8) {}
+        };
+        let impl_ = Impl::parse(&["crate"], &ast);
+        assert_yaml_snapshot!(impl_, @r###"
+        ---
+        kind: impl
+        parent:
+          - crate
+        docstring: ""
+        self_type:
+          - String: (
+          - Path: MyType
+          - String: ", "
+          - Path: u8
+          - String: )
+        trait_:
+          - String: "!"
+          - Path: Send
+        base_type: ~
+        generics:
+          params: []
+          where_predicates: []
+        functions: []
+        assoc_types: []
+        "###);
+    }
+
+    #[test]
+    fn test_parse_impl_builder_signatures() {
+        let ast: ItemImpl = parse_quote! {
+            impl Builder {
+                pub fn new() -> Self {}
+                pub fn name(mut self, name: &str) -> Self {}
+                pub fn boxed(self: Box<Self>) -> Box<Self> {}
+                pub fn build(self) -> impl Service<Self::Request> + Send + 'static {}
+            }
+        };
+        let impl_ = Impl::parse(&["crate"], &ast);
+        let render =
+            |signature: TypeSignature| signature.iter().map(|s| s.content()).collect::<String>();
+        assert_eq!(
+            impl_
+                .functions
+                .iter()
+                .map(|f| render(f.signature()))
+                .collect::<Vec<_>>(),
+            vec![
+                "fn new() -> Self",
+                "fn name(mut self, name: &str) -> Self",
+                "fn boxed(self: Box<Self>) -> Box<Self>",
+                "fn build(self) -> impl Service<Self::Request> + Send + 'static",
+            ]
+        );
+        assert_yaml_snapshot!(impl_.functions[3].signature(), @r###"
+        ---
+        - String: "fn build(self) -> impl "
+        - Path: "Service<Self::Request>"
+        - String: " + "
+        - Path: Send
+        - String: " + 'static"
+        "###);
+        assert_eq!(
+            impl_
+                .functions
+                .iter()
+                .map(|f| render(f.resolved_signature()))
+                .collect::<Vec<_>>(),
+            vec![
+                "fn new() -> Builder",
+                "fn name(mut self, name: &str) -> Builder",
+                "fn boxed(self: Box<Builder>) -> Box<Builder>",
+                "fn build(self) -> impl Service<Self::Request> + Send + 'static",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_impl_resolved_signatures_full_self_type() {
+        let render =
+            |signature: TypeSignature| signature.iter().map(|s| s.content()).collect::<String>();
+
+        let ast: ItemImpl = parse_quote! {
+            impl<T: Clone> Wrapper<T> {
+                pub fn new(value: T) -> Self {}
+                pub fn get(&self) -> &T {}
+                pub fn merge(self, other: Self) -> Vec<Self> {}
+            }
+        };
+        let impl_ = Impl::parse(&["crate"], &ast);
+        assert_eq!(
+            impl_
+                .functions
+                .iter()
+                .map(|f| render(f.resolved_signature()))
+                .collect::<Vec<_>>(),
+            vec![
+                "fn new(value: T) -> Wrapper<T>",
+                "fn get(&self) -> &T",
+                "fn merge(self, other: Wrapper<T>) -> Vec<Wrapper<T>>",
+            ]
+        );
+        assert_yaml_snapshot!(impl_.functions[0].resolved_signature(), @r###"
+        ---
+        - String: "fn new(value: "
+        - Path: T
+        - String: ") -> "
+        - Path: Wrapper<T>
+        "###);
+
+        let ast: ItemImpl = parse_quote! {
+            impl Display for &MyType {
+                fn fmt(&self, f: &mut Formatter<'_>) -> Result {}
+                fn to_owned(self) -> Self {}
+            }
+        };
+        let impl_ = Impl::parse(&["crate"], &ast);
+        assert_eq!(
+            impl_
+                .functions
+                .iter()
+                .map(|f| render(f.resolved_signature()))
+                .collect::<Vec<_>>(),
+            vec![
+                "fn fmt(&self, f: &mut Formatter<'_>) -> Result",
+                "fn to_owned(self) -> &MyType",
+            ]
+        );
+    }
+}
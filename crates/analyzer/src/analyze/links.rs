@@ -0,0 +1,447 @@
+//! Check the intra-doc links of the docstrings in the analysis result
+use std::collections::BTreeSet;
+
+use crate::data_model::BrokenLink;
+
+use super::{crate_::AnalysisResult, docstring::intra_doc_links, impl_::resolve_relative_path};
+
+/// The crates that are always available to link to, without being a dependency
+pub(super) const SYSROOT_CRATES: &[&str] = &["std", "core", "alloc", "proc_macro", "test"];
+
+/// The primitive types, which rustdoc links to by name, e.g. ``[u8]`` or ``[str::len]``
+const PRIMITIVES: &[&str] = &[
+    "array",
+    "bool",
+    "char",
+    "f32",
+    "f64",
+    "fn",
+    "i8",
+    "i16",
+    "i32",
+    "i64",
+    "i128",
+    "isize",
+    "never",
+    "pointer",
+    "reference",
+    "slice",
+    "str",
+    "tuple",
+    "u8",
+    "u16",
+    "u32",
+    "u64",
+    "u128",
+    "unit",
+    "usize",
+];
+
+/// The names in scope in every module through the standard prelude, including its macros
+const PRELUDE: &[&str] = &[
+    "AsMut",
+    "AsRef",
+    "Box",
+    "Clone",
+    "Copy",
+    "Debug",
+    "Default",
+    "DoubleEndedIterator",
+    "Drop",
+    "Eq",
+    "Err",
+    "ExactSizeIterator",
+    "Extend",
+    "Fn",
+    "FnMut",
+    "FnOnce",
+    "From",
+    "FromIterator",
+    "Hash",
+    "Into",
+    "IntoIterator",
+    "Iterator",
+    "None",
+    "Ok",
+    "Option",
+    "Ord",
+    "PartialEq",
+    "PartialOrd",
+    "Result",
+    "Send",
+    "Sized",
+    "Some",
+    "String",
+    "Sync",
+    "ToOwned",
+    "ToString",
+    "TryFrom",
+    "TryInto",
+    "Unpin",
+    "Vec",
+    "assert",
+    "assert_eq",
+    "assert_ne",
+    "cfg",
+    "concat",
+    "debug_assert",
+    "env",
+    "eprintln",
+    "format",
+    "include",
+    "include_str",
+    "matches",
+    "panic",
+    "print",
+    "println",
+    "todo",
+    "unimplemented",
+    "unreachable",
+    "vec",
+    "write",
+    "writeln",
+];
+
+/// The scope an intra-doc link is resolved in
+struct LinkScope<'a> {
+    /// The fully qualified name of the item whose docstring contains the link
+    item: String,
+    /// The module that paths are relative to
+    module: &'a [String],
+    /// The fully qualified name of the type or trait that ``Self`` refers to, if any
+    self_path: Option<String>,
+    file: &'a Option<String>,
+}
+
+impl AnalysisResult {
+    /// Find the intra-doc links in the docstrings of all modules and items, including their
+    /// fields, variants and methods, that do not resolve to a known item.
+    ///
+    /// As for rustdoc, links are resolved relative to the module the item is declared in
+    /// (or, for the docstring of a module, the module itself),
+    /// through its ``use`` declarations, or from the crate root for ``crate::`` paths,
+    /// and ``Self::`` refers to the type or trait the item belongs to.
+    /// Links into external crates (those referenced by the analysis, the dependencies
+    /// and ``std``, ``core`` and ``alloc``), primitive types and the prelude
+    /// are assumed to resolve, since their items are not known.
+    pub fn check_links(&self) -> Vec<BrokenLink> {
+        let known = self.linkable_paths();
+        let external = self.external_crates();
+        let kinds = self.item_kinds();
+
+        let mut docs: Vec<(LinkScope, &str)> = vec![];
+        for module in &self.modules {
+            let scope = LinkScope {
+                item: module.path_str(),
+                module: &module.path,
+                self_path: None,
+                file: &module.file,
+            };
+            docs.push((scope, &module.docstring));
+        }
+        for struct_ in &self.structs {
+            let module = parent(&struct_.path);
+            let scope = |item: String| LinkScope {
+                item,
+                module,
+                self_path: Some(struct_.path_str()),
+                file: &struct_.file,
+            };
+            docs.push((scope(struct_.path_str()), &struct_.docstring));
+            for field in &struct_.fields {
+                docs.push((scope(field.path.join("::")), &field.docstring));
+            }
+        }
+        for enum_ in &self.enums {
+            let module = parent(&enum_.path);
+            let scope = |item: String| LinkScope {
+                item,
+                module,
+                self_path: Some(enum_.path_str()),
+                file: &enum_.file,
+            };
+            docs.push((scope(enum_.path_str()), &enum_.docstring));
+            for variant in &enum_.variants {
+                docs.push((scope(variant.name()), &variant.docstring));
+                for field in &variant.fields {
+                    docs.push((scope(field.path.join("::")), &field.docstring));
+                }
+            }
+        }
+        for function in &self.functions {
+            let scope = LinkScope {
+                item: function.path_str(),
+                module: parent(&function.path),
+                self_path: None,
+                file: &function.file,
+            };
+            docs.push((scope, &function.docstring));
+        }
+        for trait_ in &self.traits {
+            let module = parent(&trait_.path);
+            let scope = |item: String, file| LinkScope {
+                item,
+                module,
+                self_path: Some(trait_.path_str()),
+                file,
+            };
+            docs.push((scope(trait_.path_str(), &trait_.file), &trait_.docstring));
+            for function in &trait_.functions {
+                docs.push((
+                    scope(function.path_str(), &function.file),
+                    &function.docstring,
+                ));
+            }
+        }
+        for alias in &self.type_aliases {
+            let scope = LinkScope {
+                item: alias.path_str(),
+                module: parent(&alias.path),
+                self_path: None,
+                file: &alias.file,
+            };
+            docs.push((scope, &alias.docstring));
+        }
+        for const_ in &self.consts {
+            let scope = LinkScope {
+                item: const_.path_str(),
+                module: parent(&const_.path),
+                self_path: None,
+                file: &const_.file,
+            };
+            docs.push((scope, &const_.docstring));
+        }
+        for static_ in &self.statics {
+            let scope = LinkScope {
+                item: static_.path_str(),
+                module: parent(&static_.path),
+                self_path: None,
+                file: &static_.file,
+            };
+            docs.push((scope, &static_.docstring));
+        }
+        for impl_ in &self.impls {
+            let self_path = self.impl_owner(&kinds, impl_);
+            let scope = |item: String, file| LinkScope {
+                item,
+                module: &impl_.parent,
+                self_path: self_path.clone(),
+                file,
+            };
+            let rendered = impl_
+                .self_type
+                .iter()
+                .map(|s| s.content())
+                .collect::<String>();
+            docs.push((
+                scope(format!("impl {}", rendered), &impl_.file),
+                &impl_.docstring,
+            ));
+            for function in &impl_.functions {
+                docs.push((
+                    scope(function.path_str(), &function.file),
+                    &function.docstring,
+                ));
+            }
+        }
+        for block in &self.extern_blocks {
+            let scope = |item: String| LinkScope {
+                item,
+                module: &block.parent,
+                self_path: None,
+                file: &block.file,
+            };
+            for function in &block.functions {
+                docs.push((scope(function.path_str()), &function.docstring));
+            }
+            for static_ in &block.statics {
+                docs.push((scope(static_.path_str()), &static_.docstring));
+            }
+        }
+
+        let mut broken = vec![];
+        for (scope, docstring) in docs {
+            for link in intra_doc_links(docstring) {
+                if !self.link_resolves(&known, &external, &scope, &link.path) {
+                    broken.push(BrokenLink {
+                        item: scope.item.clone(),
+                        text: link.text,
+                        target: link.target,
+                        file: scope.file.clone(),
+                    });
+                }
+            }
+        }
+        broken
+    }
+
+    /// The names of the external crates whose items may be referenced:
+    /// those referenced by the analysis, the dependencies, and the crates of the sysroot
+    pub(super) fn external_crates(&self) -> BTreeSet<String> {
+        self.referenced_crates()
+            .into_iter()
+            .chain(self.dependencies.iter().map(|d| d.name.clone()))
+            .chain(SYSROOT_CRATES.iter().map(|s| s.to_string()))
+            .collect()
+    }
+
+    /// The fully qualified names of all items that can be linked to,
+    /// including methods, variants and fields
+    fn linkable_paths(&self) -> BTreeSet<String> {
+        let mut paths: BTreeSet<String> = self.item_kinds().into_keys().collect();
+        for struct_ in &self.structs {
+            paths.extend(struct_.fields.iter().map(|f| f.path.join("::")));
+        }
+        for enum_ in &self.enums {
+            for variant in &enum_.variants {
+                paths.insert(variant.name());
+                paths.extend(variant.fields.iter().map(|f| f.path.join("::")));
+            }
+        }
+        for trait_ in &self.traits {
+            paths.extend(trait_.functions.iter().map(|f| f.path_str()));
+        }
+        for impl_ in &self.impls {
+            paths.extend(impl_.functions.iter().map(|f| f.path_str()));
+        }
+        paths
+    }
+
+    /// Whether the item path of an intra-doc link resolves in the given scope
+    fn link_resolves(
+        &self,
+        known: &BTreeSet<String>,
+        external: &BTreeSet<String>,
+        scope: &LinkScope,
+        path: &str,
+    ) -> bool {
+        let (first, rest) = match path.split_once("::") {
+            Some((first, rest)) => (first, Some(rest)),
+            None => (path, None),
+        };
+        if first == "Self" {
+            return match (&scope.self_path, rest) {
+                (Some(self_path), Some(rest)) => {
+                    known.contains(&format!("{}::{}", self_path, rest))
+                }
+                (Some(_), None) => true,
+                (None, _) => false,
+            };
+        }
+        let module = scope.module.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+        let mut candidates = vec![resolve_relative_path(&module, path), vec![path.to_string()]];
+        if let Some(declaring) = self.modules.iter().find(|m| m.path == scope.module) {
+            for use_ in &declaring.uses {
+                match &use_.name {
+                    Some(name) if name == first => {
+                        // a name imported from an external crate, e.g. ``use serde::Serialize;``
+                        if use_.path.first().is_some_and(|c| external.contains(c)) {
+                            return true;
+                        }
+                        let mut imported = resolve_relative_path(&module, &use_.path.join("::"));
+                        imported.extend(
+                            rest.into_iter()
+                                .flat_map(|r| r.split("::").map(String::from)),
+                        );
+                        candidates.push(imported);
+                    }
+                    Some(_) => {}
+                    None => {
+                        // the items of a glob import, e.g. ``use super::*;``
+                        let mut imported = resolve_relative_path(&module, &use_.path.join("::"));
+                        imported.extend(path.split("::").map(String::from));
+                        candidates.push(imported);
+                    }
+                }
+            }
+        }
+        candidates.iter().any(|c| known.contains(&c.join("::")))
+            || external.contains(first)
+            || PRIMITIVES.contains(&first)
+            || PRELUDE.contains(&first)
+    }
+}
+
+/// The path of the module an item is declared in
+fn parent(path: &[String]) -> &[String] {
+    &path[..path.len().saturating_sub(1)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyze::module::ModuleItems;
+    use crate::data_model::{Crate, Module};
+
+    #[test]
+    fn test_check_links() {
+        let mut result = AnalysisResult::new(Crate {
+            name: "my_crate".to_string(),
+            version: "0.1.0".to_string(),
+            ..Default::default()
+        });
+        let modules: Vec<(Vec<&str>, &str)> = vec![
+            (
+                vec!["my_crate"],
+                r#"
+//! See [`net::Socket`], [the address](crate::net::Address) and [`Missing`].
+//!
+//! ```
+//! let x = [Ignored];
+//! ```
+pub mod net;
+use serde::Serialize;
+/// Converts to a [`Socket`][socket], [`Serialize`] and [`Vec<u8>`], or [u8] or [`Option::None`]
+///
+/// - [x] done
+/// - [ ] todo
+///
+/// ![image](Logo), [website](https://example.com), [^note] and `[not_a_link]`
+///
+/// [socket]: net::Socket
+pub fn convert() {}
+"#,
+            ),
+            (
+                vec!["my_crate", "net"],
+                r#"
+use super::*;
+/// A socket, created with [`Self::new`] or [`Self::old`], for a [`convert`]ed [Address]
+pub struct Socket {
+    /// See [`Self::port`] and [`Socket::host`]
+    pub port: u16,
+}
+impl Socket {
+    /// Replaces [`Self::port`] with [fn@Socket::new()]
+    pub fn new() -> Self {}
+}
+/// An address
+pub enum Address {
+    /// Links to [`Address::V6`]
+    V4,
+}
+"#,
+            ),
+        ];
+        for (path, content) in modules {
+            let (module, items): (Module, ModuleItems) =
+                Module::parse(None, &path, content).unwrap();
+            result.modules.push(module);
+            result.extend_items(items);
+        }
+        let broken = result
+            .check_links()
+            .into_iter()
+            .map(|l| format!("{}: [{}]({})", l.item, l.text, l.target))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            broken,
+            vec![
+                "my_crate: [`Missing`](`Missing`)",
+                "my_crate::net::Socket: [`Self::old`](`Self::old`)",
+                "my_crate::net::Socket::port: [`Socket::host`](`Socket::host`)",
+                "my_crate::net::Address::V4: [`Address::V6`](`Address::V6`)",
+            ]
+        );
+    }
+}
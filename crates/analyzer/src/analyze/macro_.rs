@@ -0,0 +1,204 @@
+//! Analyze declarative macros
+use proc_macro2::{Delimiter, Group, Spacing, TokenStream, TokenTree};
+
+use crate::data_model::Macro;
+
+use super::{anchor_id, cfg::cfg_from_attrs, docstring_from_attrs};
+
+impl Macro {
+    /// Fully qualified name of the macro
+    pub fn path_str(&self) -> String {
+        self.path.join("::")
+    }
+    /// A stable, URL-safe identifier for the macro, e.g. for anchor links
+    pub fn anchor_id(&self) -> String {
+        anchor_id("macro", &self.path)
+    }
+    /// Extract the relevant information from the AST,
+    /// or ``None`` if the item is not a ``macro_rules!`` definition, e.g. an invocation of another macro.
+    ///
+    /// An exported macro is placed at the crate root, i.e. the first segment of the parent path.
+    pub fn parse(parent: &[&str], ast: &syn::ItemMacro) -> Option<Self> {
+        if !ast.mac.path.is_ident("macro_rules") {
+            return None;
+        }
+        let name = ast.ident.as_ref()?.to_string();
+        let exported = ast
+            .attrs
+            .iter()
+            .any(|attr| attr.path().is_ident("macro_export"));
+        let parent = if exported {
+            &parent[..parent.len().min(1)]
+        } else {
+            parent
+        };
+        Some(Self {
+            name: name.clone(),
+            path: parent
+                .iter()
+                .map(|s| s.to_string())
+                .chain(Some(name))
+                .collect(),
+            file: None,
+            docstring: docstring_from_attrs(&ast.attrs),
+            rules: macro_rules_patterns(&ast.mac.tokens),
+            exported,
+            effectively_public: exported,
+            cfg_info: cfg_from_attrs(&ast.attrs).map(|p| p.to_string()),
+        })
+    }
+}
+
+/// The patterns of the rules of a ``macro_rules!`` body, i.e. the groups before each ``=>``
+fn macro_rules_patterns(tokens: &TokenStream) -> Vec<String> {
+    let tokens = tokens.clone().into_iter().collect::<Vec<_>>();
+    tokens
+        .windows(2)
+        .filter_map(|pair| match pair {
+            [TokenTree::Group(pattern), TokenTree::Punct(punct)]
+                if punct.as_char() == '=' && punct.spacing() == Spacing::Joint =>
+            {
+                Some(render_group(pattern))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Render a group of a macro pattern as rustdoc does, e.g. ``($($key:expr => $value:expr),* $(,)?)``.
+///
+/// Metavariables, e.g. ``$x:expr``, and repetitions, e.g. ``$(...),*``, are kept together,
+/// and other tokens are separated by spaces, except before ``,`` and ``;``, and after ``@``, ``&`` and ``#``.
+fn render_group(group: &Group) -> String {
+    let (open, close) = match group.delimiter() {
+        Delimiter::Parenthesis => ("(", ")"),
+        Delimiter::Bracket => ("[", "]"),
+        Delimiter::Brace => ("{", "}"),
+        Delimiter::None => ("", ""),
+    };
+    format!("{}{}{}", open, render_pattern(group.stream()), close)
+}
+
+/// Render the tokens of a macro pattern, see ``render_group``
+fn render_pattern(tokens: TokenStream) -> String {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    let punct = |index: usize| match tokens.get(index) {
+        Some(TokenTree::Punct(punct)) => Some(punct.as_char()),
+        _ => None,
+    };
+    let mut pieces: Vec<String> = vec![];
+    let mut index = 0;
+    while index < tokens.len() {
+        let mut piece = match &tokens[index] {
+            TokenTree::Punct(dollar) if dollar.as_char() == '$' => match tokens.get(index + 1) {
+                // a repetition, with an optional separator and its operator
+                Some(TokenTree::Group(group)) => {
+                    let mut piece = format!("${}", render_group(group));
+                    index += 2;
+                    if punct(index).is_some_and(|c| !matches!(c, '*' | '+' | '?'))
+                        && punct(index + 1).is_some_and(|c| matches!(c, '*' | '+' | '?'))
+                    {
+                        piece.extend(punct(index));
+                        index += 1;
+                    }
+                    if let Some(op) = punct(index).filter(|c| matches!(c, '*' | '+' | '?')) {
+                        piece.push(op);
+                        index += 1;
+                    }
+                    pieces.push(piece);
+                    continue;
+                }
+                // a metavariable, with its fragment specifier, if any
+                Some(TokenTree::Ident(name)) => {
+                    let mut piece = format!("${}", name);
+                    index += 2;
+                    if let (Some(':'), Some(TokenTree::Ident(fragment))) =
+                        (punct(index), tokens.get(index + 1))
+                    {
+                        piece.push_str(&format!(":{}", fragment));
+                        index += 2;
+                    }
+                    pieces.push(piece);
+                    continue;
+                }
+                _ => "$".to_string(),
+            },
+            TokenTree::Punct(p) if matches!(p.as_char(), ',' | ';') && !pieces.is_empty() => {
+                pieces.last_mut().unwrap().push(p.as_char());
+                index += 1;
+                continue;
+            }
+            TokenTree::Punct(p) => p.as_char().to_string(),
+            TokenTree::Group(group) => render_group(group),
+            TokenTree::Ident(ident) => ident.to_string(),
+            TokenTree::Literal(literal) => literal.to_string(),
+        };
+        // joint punctuation forms a single operator, e.g. ``=>``
+        while let Some(TokenTree::Punct(p)) = tokens.get(index) {
+            if p.spacing() != Spacing::Joint || punct(index + 1).is_none() {
+                break;
+            }
+            index += 1;
+            piece.extend(punct(index));
+        }
+        index += 1;
+        pieces.push(piece);
+    }
+    let mut output = String::new();
+    for (i, piece) in pieces.iter().enumerate() {
+        if i > 0 && !matches!(pieces[i - 1].as_str(), "@" | "&" | "#") {
+            output.push(' ');
+        }
+        output.push_str(piece);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_yaml_snapshot;
+
+    #[test]
+    fn test_parse_macro() {
+        let ast: syn::ItemMacro = syn::parse_quote! {
+            /// Squares a value
+            #[macro_export]
+            macro_rules! square {
+                ($x:expr) => { $x * $x };
+                ($($x:expr),+ $(,)?) => { ($(square!($x)),+) };
+                (@pair $($k:ident => $v:literal);* ; &mut $rest:tt) => {};
+            }
+        };
+        let macro_ = Macro::parse(&["crate", "math"], &ast).unwrap();
+        assert_yaml_snapshot!(macro_, @r###"
+        ---
+        kind: macro
+        name: square
+        path:
+          - crate
+          - square
+        docstring: Squares a value
+        rules:
+          - "($x:expr)"
+          - "($($x:expr),+ $(,)?)"
+          - "(@pair $($k:ident => $v:literal);*; &mut $rest:tt)"
+        exported: true
+        effectively_public: true
+        "###);
+
+        let ast: syn::ItemMacro = syn::parse_quote! {
+            macro_rules! local {
+                () => {};
+            }
+        };
+        let macro_ = Macro::parse(&["crate", "math"], &ast).unwrap();
+        assert_eq!(macro_.path_str(), "crate::math::local");
+        assert!(!macro_.effectively_public);
+
+        let ast: syn::ItemMacro = syn::parse_quote! {
+            lazy_static! { static ref X: u8 = 1; }
+        };
+        assert!(Macro::parse(&["crate"], &ast).is_none());
+    }
+}
@@ -2,67 +2,375 @@
 use std::path::Path;
 
 use anyhow::Result;
-use syn::parse_file;
+use proc_macro2::{Delimiter, Group, Ident, Punct, Spacing, Span, TokenStream, TokenTree};
+use serde::{Deserialize, Serialize};
+use syn::{parse_file, visit_mut::VisitMut};
 
-use crate::data_model::{Enum, Function, Module, Struct};
+use crate::data_model::{
+    Const, Enum, ExternBlock, Function, Impl, Macro, Module, Static, Struct, Trait, TypeAlias,
+    UseDeclaration,
+};
 
-use super::docstring_from_attrs;
+use super::{
+    anchor_id,
+    cfg::{cfg_from_attrs, no_std_from_attrs, paths_from_attrs, CfgPredicate, CfgSet},
+    docstring_from_attrs,
+    type_::render_tokens,
+};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+/// The items declared in a module, public or not
+pub struct ModuleItems {
+    pub structs: Vec<Struct>,
+    pub enums: Vec<Enum>,
+    pub functions: Vec<Function>,
+    pub traits: Vec<Trait>,
+    pub type_aliases: Vec<TypeAlias>,
+    #[serde(default)]
+    pub consts: Vec<Const>,
+    #[serde(default)]
+    pub statics: Vec<Static>,
+    pub impls: Vec<Impl>,
+    pub extern_blocks: Vec<ExternBlock>,
+    /// The ``macro_rules!`` definitions
+    #[serde(default)]
+    pub macros: Vec<Macro>,
+    /// The sub-module declarations that are not inline, e.g. ``pub mod name;``
+    pub submodules: Vec<ModuleDeclaration>,
+    /// The files included with ``include!`` at the module level
+    pub includes: Vec<IncludePath>,
+    /// The ``#![no_std]`` attributes of the module, with the predicate of the ``cfg_attr`` (if any);
+    /// these are only meaningful for the crate root
+    pub no_std: Vec<Option<CfgPredicate>>,
+    /// Problems reading the files included in the docstrings of the module and its items,
+    /// with ``#[doc = include_str!("...")]``
+    #[serde(default)]
+    pub doc_errors: Vec<String>,
+}
+
+impl ModuleItems {
+    /// Record the file that the items, including their methods, are declared in
+    fn set_file(&mut self, file: &Option<String>) {
+        let set_functions = |functions: &mut Vec<Function>| {
+            for function in functions.iter_mut() {
+                function.file.clone_from(file);
+            }
+        };
+        for struct_ in self.structs.iter_mut() {
+            struct_.file.clone_from(file);
+        }
+        for enum_ in self.enums.iter_mut() {
+            enum_.file.clone_from(file);
+        }
+        set_functions(&mut self.functions);
+        for trait_ in self.traits.iter_mut() {
+            trait_.file.clone_from(file);
+            set_functions(&mut trait_.functions);
+        }
+        for alias in self.type_aliases.iter_mut() {
+            alias.file.clone_from(file);
+        }
+        for const_ in self.consts.iter_mut() {
+            const_.file.clone_from(file);
+        }
+        for static_ in self.statics.iter_mut() {
+            static_.file.clone_from(file);
+        }
+        for impl_ in self.impls.iter_mut() {
+            impl_.file.clone_from(file);
+            set_functions(&mut impl_.functions);
+        }
+        for block in self.extern_blocks.iter_mut() {
+            block.file.clone_from(file);
+            set_functions(&mut block.functions);
+        }
+        for macro_ in self.macros.iter_mut() {
+            macro_.file.clone_from(file);
+        }
+    }
+
+    /// Add the items from another set, e.g. those of an included file
+    pub fn extend(&mut self, other: ModuleItems) {
+        self.structs.extend(other.structs);
+        self.enums.extend(other.enums);
+        self.functions.extend(other.functions);
+        self.traits.extend(other.traits);
+        self.type_aliases.extend(other.type_aliases);
+        self.consts.extend(other.consts);
+        self.statics.extend(other.statics);
+        self.impls.extend(other.impls);
+        self.extern_blocks.extend(other.extern_blocks);
+        self.macros.extend(other.macros);
+        self.submodules.extend(other.submodules);
+        self.includes.extend(other.includes);
+        self.doc_errors.extend(other.doc_errors);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// The file path of an ``include!`` macro invocation
+pub enum IncludePath {
+    /// A path relative to the directory of the including file, e.g. ``include!("generated.rs")``
+    File(String),
+    /// A path relative to the ``OUT_DIR`` of the build script,
+    /// e.g. ``include!(concat!(env!("OUT_DIR"), "/generated.rs"))``
+    OutDir(String),
+}
+
+impl IncludePath {
+    /// Extract the path from the arguments of an ``include!`` macro,
+    /// returning ``None`` if it is not a string literal or a ``concat!`` of literals and ``env!("OUT_DIR")``
+    fn parse(tokens: &proc_macro2::TokenStream) -> Option<Self> {
+        let expr: syn::Expr = syn::parse2(tokens.clone()).ok()?;
+        match expr {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(lit),
+                ..
+            }) => Some(Self::File(lit.value())),
+            syn::Expr::Macro(concat) if concat.mac.path.is_ident("concat") => {
+                let args = concat
+                    .mac
+                    .parse_body_with(
+                        syn::punctuated::Punctuated::<syn::Expr, syn::Token![,]>::parse_terminated,
+                    )
+                    .ok()?;
+                let mut out_dir = false;
+                let mut path = String::new();
+                for (index, arg) in args.iter().enumerate() {
+                    match arg {
+                        syn::Expr::Lit(syn::ExprLit {
+                            lit: syn::Lit::Str(lit),
+                            ..
+                        }) => path.push_str(&lit.value()),
+                        syn::Expr::Macro(env) if index == 0 && env.mac.path.is_ident("env") => {
+                            let var: syn::LitStr = env.mac.parse_body().ok()?;
+                            if var.value() != "OUT_DIR" {
+                                return None;
+                            }
+                            out_dir = true;
+                        }
+                        _ => return None,
+                    }
+                }
+                if out_dir {
+                    Some(Self::OutDir(
+                        path.trim_start_matches(['/', '\\']).to_string(),
+                    ))
+                } else {
+                    Some(Self::File(path))
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// A sub-module declaration, with the information needed to locate its file
+pub struct ModuleDeclaration {
+    pub name: String,
+    /// Whether the declaration is ``pub``
+    pub public: bool,
+    /// The file paths from ``#[path]`` and ``#[cfg_attr(..., path = ...)]`` attributes,
+    /// in the order they are declared
+    pub paths: Vec<(Option<CfgPredicate>, String)>,
+    /// The ``#[cfg(...)]`` predicate the declaration is conditional on, if any
+    #[serde(default)]
+    pub cfg: Option<String>,
+}
+
+impl ModuleDeclaration {
+    /// The file path of the module, relative to the directory of the declaring file,
+    /// taken from the first ``path`` attribute that is active for the configuration.
+    ///
+    /// Returns ``None`` if no ``path`` attribute is active,
+    /// in which case the default module file lookup should be used.
+    pub fn active_path(&self, cfg: &CfgSet) -> Option<&str> {
+        self.paths
+            .iter()
+            .find(|(predicate, _)| predicate.as_ref().is_none_or(|p| cfg.evaluate(p)))
+            .map(|(_, path)| path.as_str())
+    }
+}
+
+/// Select the sub-module declarations to read, with the ``cfg`` predicates of the alternative declarations
+/// of each module that is declared more than once, e.g. ``#[cfg(unix)] mod imp;`` and ``#[cfg(windows)] mod imp;``.
+///
+/// Of the alternatives, the first whose predicate is satisfied is read if ``cfg`` is configured
+/// (and none, if no predicate is satisfied), otherwise the first declared.
+/// Modules declared once are always read.
+pub(super) fn select_declarations<'a>(
+    decls: &'a [ModuleDeclaration],
+    cfg: &CfgSet,
+    configured: bool,
+) -> Vec<(&'a ModuleDeclaration, Vec<String>)> {
+    let mut selected = vec![];
+    for (index, decl) in decls.iter().enumerate() {
+        if decls[..index].iter().any(|d| d.name == decl.name) {
+            continue;
+        }
+        let alternatives = decls
+            .iter()
+            .filter(|d| d.name == decl.name)
+            .collect::<Vec<_>>();
+        if alternatives.len() == 1 {
+            selected.push((decl, vec![]));
+            continue;
+        }
+        let variants = alternatives
+            .iter()
+            .filter_map(|d| d.cfg.clone())
+            .collect::<Vec<_>>();
+        let active = if configured {
+            alternatives.into_iter().find(|d| {
+                d.cfg.as_deref().is_none_or(|predicate| {
+                    CfgPredicate::parse_str(predicate).is_some_and(|p| cfg.evaluate(&p))
+                })
+            })
+        } else {
+            Some(decl)
+        };
+        selected.extend(active.map(|d| (d, variants)));
+    }
+    selected
+}
 
 impl Module {
     /// Fully qualified name of the variant
     pub fn path_str(&self) -> String {
         self.path.join("::")
     }
+    /// A stable, URL-safe identifier for the module, e.g. for anchor links
+    pub fn anchor_id(&self) -> String {
+        anchor_id("mod", &self.path)
+    }
     /// Extract the relevant information from the AST
-    pub fn parse(
-        file: Option<&Path>,
-        path: &[&str],
-        content: &str,
-    ) -> Result<(Self, Vec<Struct>, Vec<Enum>, Vec<Function>)> {
-        let syntax = parse_file(content)?;
+    pub fn parse(file: Option<&Path>, path: &[&str], content: &str) -> Result<(Self, ModuleItems)> {
+        let mut syntax = match parse_file(content) {
+            Ok(syntax) => syntax,
+            // the methods of traits may have anonymous parameters in the 2015 edition,
+            // e.g. ``fn get(&self, u32);``, which are not parsed, so these are named in the tokens,
+            // which keep their spans into the source, before parsing again
+            Err(err) => content
+                .parse::<TokenStream>()
+                .ok()
+                .and_then(|tokens| syn::parse2::<syn::File>(name_anonymous_params(tokens)).ok())
+                .ok_or(err)?,
+        };
         let mut mod_ = Self {
             file: file.map(|f| f.to_string_lossy().to_string()), // TODO better way to serialize the path, also ?
+            line_count: file.map(|_| content.lines().count()),
+            byte_size: file.map(|_| content.len()),
+            name: path.last().map(|s| s.to_string()).unwrap_or_default(),
             path: path.iter().map(|s| s.to_string()).collect(),
-            docstring: docstring_from_attrs(&syntax.attrs),
+            docstring: String::new(),
             declarations: vec![],
+            uses: vec![],
+            is_generated: false,
+            effectively_public: true,
+            target_kind: None,
+            cfg_info: cfg_from_attrs(&syntax.attrs).map(|p| p.to_string()),
+            cfg_variants: vec![],
+        };
+
+        let mut includes = DocIncludes {
+            file,
+            errors: vec![],
         };
+        includes.visit_file_mut(&mut syntax);
 
-        let mut structs = vec![];
-        let mut enums = vec![];
-        let mut functions = vec![];
+        let mut items = ModuleItems {
+            no_std: no_std_from_attrs(&syntax.attrs),
+            doc_errors: includes.errors,
+            ..Default::default()
+        };
+        mod_.docstring = docstring_from_attrs(&syntax.attrs);
 
         for item in syntax.items {
-            // TODO traits, functions, impls, et
+            // TODO traits, etc
             match &item {
                 syn::Item::Mod(mod_item) => {
-                    if let syn::Visibility::Public(_) = mod_item.vis {
-                        // TODO handle modules that are not just declarations
-                        mod_.declarations.push(mod_item.ident.to_string());
+                    let public = matches!(mod_item.vis, syn::Visibility::Public(_));
+                    // a module may be declared more than once, under different ``cfg`` predicates
+                    let name = mod_item.ident.to_string();
+                    if public && !mod_.declarations.contains(&name) {
+                        mod_.declarations.push(name);
+                    }
+                    // TODO handle modules that are not just declarations
+                    if mod_item.content.is_none() {
+                        items.submodules.push(ModuleDeclaration {
+                            name: mod_item.ident.to_string(),
+                            public,
+                            paths: paths_from_attrs(&mod_item.attrs),
+                            cfg: cfg_from_attrs(&mod_item.attrs).map(|p| p.to_string()),
+                        });
                     }
                 }
+                syn::Item::Use(use_item) => {
+                    let public = matches!(use_item.vis, syn::Visibility::Public(_));
+                    flatten_use_tree(&mut vec![], &use_item.tree, public, &mut mod_.uses);
+                }
+                syn::Item::ExternCrate(extern_item) => {
+                    let name = extern_item
+                        .rename
+                        .as_ref()
+                        .map_or(&extern_item.ident, |(_, rename)| rename);
+                    mod_.uses.push(UseDeclaration {
+                        path: vec![extern_item.ident.to_string()],
+                        name: Some(name.to_string()),
+                        public: matches!(extern_item.vis, syn::Visibility::Public(_)),
+                    });
+                }
                 syn::Item::Struct(struct_item) => {
-                    if let syn::Visibility::Public(_) = struct_item.vis {
-                        let struct_ = Struct::parse(path, struct_item);
-                        structs.push(struct_);
-                    }
+                    let struct_ = Struct::parse(path, struct_item);
+                    items.structs.push(struct_);
                 }
                 syn::Item::Enum(enum_item) => {
-                    if let syn::Visibility::Public(_) = enum_item.vis {
-                        let enum_ = Enum::parse(path, enum_item);
-                        enums.push(enum_);
-                    }
+                    let enum_ = Enum::parse(path, enum_item);
+                    items.enums.push(enum_);
                 }
                 syn::Item::Fn(fn_item) => {
-                    if let syn::Visibility::Public(_) = fn_item.vis {
-                        let function = Function::parse(path, fn_item);
-                        functions.push(function);
+                    let function = Function::parse(path, fn_item);
+                    items.functions.push(function);
+                }
+                syn::Item::Trait(trait_item) => {
+                    items.traits.push(Trait::parse(path, trait_item));
+                }
+                syn::Item::Type(type_item) => {
+                    items.type_aliases.push(TypeAlias::parse(path, type_item));
+                }
+                syn::Item::Const(const_item) => {
+                    items.consts.push(Const::parse(path, const_item));
+                }
+                syn::Item::Static(static_item) => {
+                    items.statics.push(Static::parse(path, static_item));
+                }
+                syn::Item::Impl(impl_item) => {
+                    items.impls.push(Impl::parse(path, impl_item));
+                }
+                syn::Item::ForeignMod(foreign_item) => {
+                    items
+                        .extern_blocks
+                        .push(ExternBlock::parse(path, foreign_item));
+                }
+                syn::Item::Macro(macro_item) if macro_item.mac.path.is_ident("include") => {
+                    if let Some(include) = IncludePath::parse(&macro_item.mac.tokens) {
+                        items.includes.push(include);
                     }
                 }
+                // other macro invocations are not expanded
+                syn::Item::Macro(macro_item) => {
+                    items.macros.extend(Macro::parse(path, macro_item));
+                }
                 _ => {}
             }
         }
 
-        Ok((mod_, structs, enums, functions))
+        if mod_.file.is_some() {
+            items.set_file(&mod_.file);
+        }
+
+        Ok((mod_, items))
     }
 
     pub fn to_json(&self) -> String {
@@ -70,9 +378,245 @@ impl Module {
     }
 }
 
+/// Inline the files included in docstrings with ``#[doc = include_str!("...")]``,
+/// e.g. to use the ``README.md`` as the crate documentation, or keep long documentation of an item
+/// in a separate file, by replacing the attributes with the contents of the files.
+///
+/// This applies to the inner attributes of the module, and those of all items,
+/// including their fields, variants and methods.
+/// Included paths are relative to the module file, and so are only read if it is known.
+struct DocIncludes<'a> {
+    file: Option<&'a Path>,
+    /// Problems reading the included files
+    errors: Vec<String>,
+}
+
+impl VisitMut for DocIncludes<'_> {
+    fn visit_attribute_mut(&mut self, attr: &mut syn::Attribute) {
+        let syn::Meta::NameValue(meta) = &mut attr.meta else {
+            return;
+        };
+        if !meta.path.is_ident("doc") {
+            return;
+        }
+        let syn::Expr::Macro(mac) = &meta.value else {
+            return;
+        };
+        if !mac.mac.path.is_ident("include_str") {
+            return;
+        }
+        let Some(file) = self.file else {
+            return;
+        };
+        let Ok(included) = mac.mac.parse_body::<syn::LitStr>() else {
+            self.errors.push(format!(
+                "Cannot resolve the file included in the docstring, as its path is not a string literal: {}",
+                render_tokens(&mac.mac.tokens)
+            ));
+            return;
+        };
+        let included = file
+            .parent()
+            .unwrap_or(Path::new(""))
+            .join(included.value());
+        match std::fs::read_to_string(&included) {
+            Ok(content) => {
+                meta.value = syn::Expr::Lit(syn::ExprLit {
+                    attrs: vec![],
+                    lit: syn::Lit::Str(syn::LitStr::new(content.trim_end(), Span::call_site())),
+                })
+            }
+            Err(err) => self.errors.push(format!(
+                "Cannot read the file {} included in the docstring: {}",
+                included.to_string_lossy(),
+                err
+            )),
+        }
+    }
+}
+
+/// Name the anonymous parameters of the methods of the traits in a token stream ``_``,
+/// e.g. ``fn get(&self, u32);`` becomes ``fn get(&self, _: u32);``
+fn name_anonymous_params(tokens: TokenStream) -> TokenStream {
+    let mut output = vec![];
+    let mut in_trait = false;
+    for token in tokens {
+        match token {
+            TokenTree::Ident(ident) if ident == "trait" => {
+                in_trait = true;
+                output.push(TokenTree::Ident(ident));
+            }
+            TokenTree::Group(group) if in_trait && group.delimiter() == Delimiter::Brace => {
+                in_trait = false;
+                let mut named = Group::new(Delimiter::Brace, name_trait_params(group.stream()));
+                named.set_span(group.span());
+                output.push(TokenTree::Group(named));
+            }
+            TokenTree::Group(group) => {
+                let mut named =
+                    Group::new(group.delimiter(), name_anonymous_params(group.stream()));
+                named.set_span(group.span());
+                output.push(TokenTree::Group(named));
+            }
+            token => output.push(token),
+        }
+    }
+    output.into_iter().collect()
+}
+
+/// Name the anonymous parameters of the methods in the body of a trait, see ``name_anonymous_params``
+fn name_trait_params(tokens: TokenStream) -> TokenStream {
+    let mut output = vec![];
+    // the depth of angle brackets since the last ``fn``, if the parameters are yet to be found
+    let mut generics_depth: Option<usize> = None;
+    let mut prev_char = None;
+    for token in tokens {
+        match &token {
+            TokenTree::Ident(ident) if ident == "fn" => generics_depth = Some(0),
+            TokenTree::Punct(punct) if generics_depth.is_some() => match punct.as_char() {
+                '<' => generics_depth = generics_depth.map(|d| d + 1),
+                '>' if prev_char != Some('-') => {
+                    generics_depth = generics_depth.map(|d| d.saturating_sub(1))
+                }
+                _ => {}
+            },
+            TokenTree::Group(group)
+                if generics_depth == Some(0) && group.delimiter() == Delimiter::Parenthesis =>
+            {
+                generics_depth = None;
+                let mut named = Group::new(Delimiter::Parenthesis, name_params(group.stream()));
+                named.set_span(group.span());
+                output.push(TokenTree::Group(named));
+                prev_char = None;
+                continue;
+            }
+            _ => {}
+        }
+        prev_char = match &token {
+            TokenTree::Punct(punct) => Some(punct.as_char()),
+            _ => None,
+        };
+        output.push(token);
+    }
+    output.into_iter().collect()
+}
+
+/// Name the anonymous parameters in the parameter list of a method ``_``,
+/// i.e. those that are not a receiver and have no ``:`` outside of their type's generic arguments
+fn name_params(tokens: TokenStream) -> TokenStream {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    let mut params: Vec<Vec<TokenTree>> = vec![vec![]];
+    let mut depth: usize = 0;
+    for (i, token) in tokens.iter().enumerate() {
+        if let TokenTree::Punct(punct) = token {
+            let after_dash =
+                i > 0 && matches!(&tokens[i - 1], TokenTree::Punct(p) if p.as_char() == '-');
+            match punct.as_char() {
+                '<' => depth += 1,
+                '>' if !after_dash => depth = depth.saturating_sub(1),
+                ',' if depth == 0 => {
+                    params.push(vec![]);
+                    continue;
+                }
+                _ => {}
+            }
+        }
+        params.last_mut().unwrap().push(token.clone());
+    }
+    let is_named = |param: &[TokenTree]| {
+        let mut depth: usize = 0;
+        for (i, token) in param.iter().enumerate() {
+            match token {
+                TokenTree::Ident(ident) if ident == "self" && depth == 0 => return true,
+                TokenTree::Punct(punct) => match punct.as_char() {
+                    '<' => depth += 1,
+                    '>' => depth = depth.saturating_sub(1),
+                    // a single colon, rather than part of a path separator ``::``
+                    ':' if depth == 0 => {
+                        let joint_next = punct.spacing() == Spacing::Joint
+                            && matches!(param.get(i + 1), Some(TokenTree::Punct(p)) if p.as_char() == ':');
+                        let joint_prev = i > 0
+                            && matches!(&param[i - 1], TokenTree::Punct(p) if p.as_char() == ':' && p.spacing() == Spacing::Joint);
+                        if !joint_next && !joint_prev {
+                            return true;
+                        }
+                    }
+                    _ => {}
+                },
+                _ => {}
+            }
+        }
+        false
+    };
+    let mut output: Vec<TokenTree> = vec![];
+    for (i, param) in params.iter().enumerate() {
+        if i > 0 {
+            output.push(TokenTree::Punct(Punct::new(',', Spacing::Alone)));
+        }
+        if !param.is_empty() && !is_named(param) {
+            output.push(TokenTree::Ident(Ident::new("_", Span::call_site())));
+            output.push(TokenTree::Punct(Punct::new(':', Spacing::Alone)));
+        }
+        output.extend(param.iter().cloned());
+    }
+    output.into_iter().collect()
+}
+
+/// Flatten a ``use`` tree into one declaration per imported path
+fn flatten_use_tree(
+    prefix: &mut Vec<String>,
+    tree: &syn::UseTree,
+    public: bool,
+    uses: &mut Vec<UseDeclaration>,
+) {
+    // ``use path::{self}`` imports ``path`` itself
+    let imported = |prefix: &[String], ident: &syn::Ident| {
+        let mut path = prefix.to_vec();
+        if ident != "self" {
+            path.push(ident.to_string());
+        }
+        path
+    };
+    match tree {
+        syn::UseTree::Path(use_path) => {
+            prefix.push(use_path.ident.to_string());
+            flatten_use_tree(prefix, &use_path.tree, public, uses);
+            prefix.pop();
+        }
+        syn::UseTree::Name(use_name) => {
+            let path = imported(prefix, &use_name.ident);
+            uses.push(UseDeclaration {
+                name: path.last().cloned(),
+                path,
+                public,
+            });
+        }
+        syn::UseTree::Rename(use_rename) => uses.push(UseDeclaration {
+            path: imported(prefix, &use_rename.ident),
+            name: Some(use_rename.rename.to_string()),
+            public,
+        }),
+        syn::UseTree::Glob(_) => uses.push(UseDeclaration {
+            path: prefix.clone(),
+            name: None,
+            public,
+        }),
+        syn::UseTree::Group(group) => {
+            for tree in &group.items {
+                flatten_use_tree(prefix, tree, public, uses);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::analyze::{
+        type_::{truncate_type_depth, with_max_parse_depth},
+        ELLIPSIS,
+    };
+    use crate::data_model::TypeSegment;
     use insta::assert_yaml_snapshot;
 
     #[test]
@@ -88,25 +632,262 @@ pub enum MyEnum {
         let mod_ = Module::parse(None, &["test"], content).unwrap();
         assert_yaml_snapshot!(mod_, @r###"
         ---
-        - file: ~
+        - kind: module
+          file: ~
+          line_count: ~
+          byte_size: ~
+          name: test
           path:
             - test
           docstring: "Multi-line\ndocstring"
           declarations: []
-        - []
-        - - path:
-              - test
-              - MyEnum
-            docstring: ""
-            variants:
-              - path:
-                  - test
-                  - MyEnum
-                  - MyVariant1
-                docstring: ""
-                discriminant: ~
-                fields: []
-        - []
+          uses: []
+          is_generated: false
+          effectively_public: true
+        - structs: []
+          enums:
+            - kind: enum
+              name: MyEnum
+              path:
+                - test
+                - MyEnum
+              docstring: ""
+              doc_aliases: []
+              attributes: []
+              generics:
+                params: []
+                where_predicates: []
+              variants:
+                - name: MyVariant1
+                  path:
+                    - test
+                    - MyEnum
+                    - MyVariant1
+                  docstring: ""
+                  discriminant: ~
+                  fields_kind: unit
+                  fields: []
+              deref_target: ~
+              recursive: false
+              effectively_public: true
+          functions: []
+          traits: []
+          type_aliases: []
+          consts: []
+          statics: []
+          impls: []
+          extern_blocks: []
+          macros: []
+          submodules: []
+          includes: []
+          no_std: []
+          doc_errors: []
         "###);
     }
+
+    #[test]
+    fn test_parse_module_file_size() {
+        let content = "//! Docstring\npub struct A;\n";
+        let (mod_, _) = Module::parse(Some(Path::new("lib.rs")), &["test"], content).unwrap();
+        assert_eq!(mod_.line_count, Some(2));
+        assert_eq!(mod_.byte_size, Some(28));
+        let (mod_, _) = Module::parse(None, &["test"], content).unwrap();
+        assert_eq!(mod_.line_count, None);
+    }
+
+    #[test]
+    fn test_parse_module_anonymous_params_source_signature() {
+        let content = r###"
+pub trait MyTrait {
+    fn get(&self, u32) -> u32;
+}
+
+pub fn my_function(
+    a: u32, // the first
+    b: u32,
+) -> u32 {
+    a + b
+}
+"###;
+        let (_, items) = Module::parse(None, &["test"], content).unwrap();
+        assert_eq!(
+            items.traits[0].functions[0].source_signature.as_deref(),
+            Some("fn get(&self, u32) -> u32")
+        );
+        assert_eq!(
+            items.functions[0].source_signature.as_deref(),
+            Some("fn my_function(\n    a: u32, // the first\n    b: u32,\n) -> u32")
+        );
+    }
+
+    #[test]
+    fn test_parse_module_include_str_docstring() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(temp_dir.path().join("src")).unwrap();
+        std::fs::write(
+            temp_dir.path().join("README.md"),
+            "# My crate\n\nDoes things.\n",
+        )
+        .unwrap();
+        let content = r###"
+//! Preamble
+#![doc = include_str!("../README.md")]
+#![doc = include_str!("../MISSING.md")]
+#![no_std]
+"###;
+        let file = temp_dir.path().join("src").join("lib.rs");
+        let (mod_, items) = Module::parse(Some(&file), &["test"], content).unwrap();
+        assert_eq!(mod_.docstring, "Preamble\n# My crate\n\nDoes things.");
+        assert_eq!(items.doc_errors.len(), 1);
+        assert!(items.doc_errors[0].contains("MISSING.md"));
+
+        // the included path cannot be resolved without the module file
+        let (mod_, items) = Module::parse(None, &["test"], content).unwrap();
+        assert_eq!(mod_.docstring, "Preamble");
+        assert!(items.doc_errors.is_empty());
+    }
+
+    #[test]
+    fn test_parse_item_include_str_docstring() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let docs = temp_dir.path().join("docs");
+        std::fs::create_dir(&docs).unwrap();
+        std::fs::write(docs.join("socket.md"), "A socket\n\nWith details.\n").unwrap();
+        std::fs::write(docs.join("port.md"), "The port").unwrap();
+        let content = r###"
+/// Summary
+#[doc = include_str!("docs/socket.md")]
+pub struct Socket {
+    #[doc = include_str!("docs/port.md")]
+    pub port: u16,
+}
+impl Socket {
+    #[doc = include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/docs/new.md"))]
+    pub fn new() -> Self {}
+}
+"###;
+        let file = temp_dir.path().join("lib.rs");
+        let (_, items) = Module::parse(Some(&file), &["test"], content).unwrap();
+        assert_eq!(
+            items.structs[0].docstring,
+            "Summary\nA socket\n\nWith details."
+        );
+        assert_eq!(items.structs[0].fields[0].docstring, "The port");
+        assert_eq!(items.impls[0].functions[0].docstring, "");
+        assert_eq!(
+            items.doc_errors,
+            vec![
+                "Cannot resolve the file included in the docstring, as its path is not a string literal: \
+                concat!(env!(\"CARGO_MANIFEST_DIR\"), \"/docs/new.md\")"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_module_declaration_active_path() {
+        let content = r###"
+#[cfg_attr(unix, path = "unix.rs")]
+#[cfg_attr(windows, path = "windows.rs")]
+pub mod imp;
+"###;
+        let (_, items) = Module::parse(None, &["test"], content).unwrap();
+        let decl = &items.submodules[0];
+        assert_eq!(decl.name, "imp");
+        assert_eq!(
+            decl.active_path(&CfgSet::new(&["unix".to_string()])),
+            Some("unix.rs")
+        );
+        assert_eq!(
+            decl.active_path(&CfgSet::new(&["windows".to_string()])),
+            Some("windows.rs")
+        );
+        // fall back to the default lookup if no predicate matches
+        assert_eq!(decl.active_path(&CfgSet::default()), None);
+    }
+
+    #[test]
+    fn test_module_uses() {
+        let content = r###"
+use std::fmt;
+pub use inner::{self as renamed, nested::*, Foo};
+extern crate alloc as core_alloc;
+"###;
+        let (mod_, _) = Module::parse(None, &["test"], content).unwrap();
+        let uses = mod_
+            .uses
+            .iter()
+            .map(|u| {
+                format!(
+                    "{} {} {:?}",
+                    if u.public { "pub" } else { "priv" },
+                    u.path.join("::"),
+                    u.name
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            uses,
+            vec![
+                "priv std::fmt Some(\"fmt\")",
+                "pub inner Some(\"renamed\")",
+                "pub inner::nested None",
+                "pub inner::Foo Some(\"Foo\")",
+                "priv alloc Some(\"core_alloc\")"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_module_max_parse_depth() {
+        let nested = format!("{}u8{}", "Vec<".repeat(20), ">".repeat(20));
+        let content = format!(
+            "pub struct Deep(pub {}); pub fn refs(a: {}u8) {{}}",
+            nested,
+            "&".repeat(20)
+        );
+        let (_, items) =
+            with_max_parse_depth(4, || Module::parse(None, &["test"], &content)).unwrap();
+        // generic arguments are rendered as part of the path
+        let field = &items.structs[0].fields[0].type_;
+        assert_eq!(field, &vec![TypeSegment::Path(nested)]);
+        assert_eq!(
+            truncate_type_depth(field, 2),
+            vec![TypeSegment::Path("Vec<Vec<Vec<…>>>".to_string())]
+        );
+        // references are rendered recursively, up to the maximum depth
+        assert_eq!(
+            items.functions[0].params[0].type_,
+            vec![TypeSegment::String(format!(
+                "{}{}",
+                "&".repeat(5),
+                ELLIPSIS
+            ))]
+        );
+        // the default depth is restored
+        let (_, items) = Module::parse(None, &["test"], &content).unwrap();
+        assert_eq!(
+            items.functions[0].params[0].type_,
+            vec![
+                TypeSegment::String("&".repeat(20)),
+                TypeSegment::Path("u8".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_module_includes() {
+        let content = r###"
+include!("generated.rs");
+include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+include!(concat!(env!("OTHER"), "/ignored.rs"));
+"###;
+        let (_, items) = Module::parse(None, &["test"], content).unwrap();
+        assert_eq!(
+            items.includes,
+            vec![
+                IncludePath::File("generated.rs".to_string()),
+                IncludePath::OutDir("bindings.rs".to_string())
+            ]
+        );
+    }
 }
@@ -0,0 +1,2353 @@
+//! Derived views and queries over the analysis result
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::path::Path;
+
+use crate::data_model::{
+    Function, FunctionOwner, Impl, ImplementedMethod, Implementor, InherentFunctions,
+    InheritedMethods, ItemKind, ItemRef, Module, ModuleContents, ModuleNode, ObjectEntry, Trait,
+    TypeSegment,
+};
+
+use super::{
+    cfg::CfgPredicate,
+    crate_::{AnalysisResult, DocUrlResolver},
+    impl_::resolve_relative_path,
+    type_::{nesting_depth, DEFAULT_MAX_PARSE_DEPTH},
+};
+
+impl AnalysisResult {
+    /// The fully qualified names of all effectively public items in the crate,
+    /// i.e. the modules, structs, enums, functions, traits and their methods, type aliases, macros,
+    /// impl methods and foreign items,
+    /// sorted and deduplicated.
+    ///
+    /// Impl methods of private types and foreign items of private modules are omitted.
+    pub fn public_paths(&self) -> Vec<String> {
+        let private_items: BTreeSet<String> = self
+            .structs
+            .iter()
+            .filter(|s| !s.effectively_public)
+            .map(|s| s.path_str())
+            .chain(
+                self.enums
+                    .iter()
+                    .filter(|e| !e.effectively_public)
+                    .map(|e| e.path_str()),
+            )
+            .chain(
+                self.traits
+                    .iter()
+                    .filter(|t| !t.effectively_public)
+                    .map(|t| t.path_str()),
+            )
+            .collect();
+        let private_modules: BTreeSet<&[String]> = self
+            .modules
+            .iter()
+            .filter(|m| !m.effectively_public)
+            .map(|m| m.path.as_slice())
+            .collect();
+        let paths: BTreeSet<String> = self
+            .modules
+            .iter()
+            .filter(|m| m.effectively_public)
+            .map(|m| m.path_str())
+            .chain(
+                self.structs
+                    .iter()
+                    .filter(|s| s.effectively_public)
+                    .map(|s| s.path_str()),
+            )
+            .chain(
+                self.enums
+                    .iter()
+                    .filter(|e| e.effectively_public)
+                    .map(|e| e.path_str()),
+            )
+            .chain(
+                self.functions
+                    .iter()
+                    .filter(|f| f.effectively_public)
+                    .map(|f| f.path_str()),
+            )
+            .chain(
+                self.traits
+                    .iter()
+                    .filter(|t| t.effectively_public)
+                    .flat_map(|t| {
+                        Some(t.path_str())
+                            .into_iter()
+                            .chain(t.functions.iter().map(|f| f.path_str()))
+                    }),
+            )
+            .chain(
+                self.type_aliases
+                    .iter()
+                    .filter(|a| a.effectively_public)
+                    .map(|a| a.path_str()),
+            )
+            .chain(
+                self.macros
+                    .iter()
+                    .filter(|m| m.effectively_public)
+                    .map(|m| m.path_str()),
+            )
+            .chain(
+                self.consts
+                    .iter()
+                    .filter(|c| c.effectively_public)
+                    .map(|c| c.path_str()),
+            )
+            .chain(
+                self.statics
+                    .iter()
+                    .filter(|s| s.effectively_public)
+                    .map(|s| s.path_str()),
+            )
+            .chain(
+                self.impls
+                    .iter()
+                    .flat_map(|i| i.functions.iter())
+                    .filter(|f| match &f.owner {
+                        FunctionOwner::Inherent(type_) | FunctionOwner::TraitImpl { type_, .. } => {
+                            !private_items.contains(type_)
+                        }
+                        _ => true,
+                    })
+                    .map(|f| f.path_str()),
+            )
+            .chain(
+                self.extern_blocks
+                    .iter()
+                    .filter(|block| !private_modules.contains(block.parent.as_slice()))
+                    .flat_map(|block| {
+                        block
+                            .functions
+                            .iter()
+                            .map(|f| f.path_str())
+                            .chain(block.statics.iter().map(|s| s.path_str()))
+                    }),
+            )
+            .collect();
+        paths.into_iter().collect()
+    }
+
+    /// The modules and items that the crate exposes, as for rustdoc, i.e. those reachable from the crate root
+    /// through ``pub mod`` declarations and ``pub use`` re-exports, including glob re-exports,
+    /// sorted by their public path.
+    ///
+    /// Each module and item is returned once, with its canonical public path as the ``name``
+    /// and its fully qualified name as the ``path``.
+    /// The canonical path is the one it is declared at, if that is reachable through ``pub mod`` declarations,
+    /// otherwise the shortest path it is re-exported at.
+    /// Methods, fields and variants are documented with their parent, and so are not included.
+    pub fn public_api(&self) -> Vec<ItemRef> {
+        let modules: BTreeMap<&[String], &Module> = self
+            .modules
+            .iter()
+            .map(|m| (m.path.as_slice(), m))
+            .collect();
+        let mut public_items: Vec<(&[String], ItemKind)> = vec![];
+        public_items.extend(
+            self.structs
+                .iter()
+                .filter(|s| s.effectively_public)
+                .map(|s| (s.path.as_slice(), ItemKind::Struct)),
+        );
+        public_items.extend(
+            self.enums
+                .iter()
+                .filter(|e| e.effectively_public)
+                .map(|e| (e.path.as_slice(), ItemKind::Enum)),
+        );
+        public_items.extend(
+            self.functions
+                .iter()
+                .filter(|f| f.effectively_public)
+                .map(|f| (f.path.as_slice(), ItemKind::Function)),
+        );
+        public_items.extend(
+            self.traits
+                .iter()
+                .filter(|t| t.effectively_public)
+                .map(|t| (t.path.as_slice(), ItemKind::Trait)),
+        );
+        public_items.extend(
+            self.type_aliases
+                .iter()
+                .filter(|a| a.effectively_public)
+                .map(|a| (a.path.as_slice(), ItemKind::TypeAlias)),
+        );
+        public_items.extend(
+            self.macros
+                .iter()
+                .filter(|m| m.effectively_public)
+                .map(|m| (m.path.as_slice(), ItemKind::Macro)),
+        );
+        public_items.extend(
+            self.consts
+                .iter()
+                .filter(|c| c.effectively_public)
+                .map(|c| (c.path.as_slice(), ItemKind::Const)),
+        );
+        public_items.extend(
+            self.statics
+                .iter()
+                .filter(|s| s.effectively_public)
+                .map(|s| (s.path.as_slice(), ItemKind::Static)),
+        );
+        for block in &self.extern_blocks {
+            public_items.extend(
+                block
+                    .functions
+                    .iter()
+                    .map(|f| (f.path.as_slice(), ItemKind::Function)),
+            );
+            public_items.extend(
+                block
+                    .statics
+                    .iter()
+                    .map(|s| (s.path.as_slice(), ItemKind::Static)),
+            );
+        }
+        let kinds: BTreeMap<&[String], ItemKind> = public_items.iter().copied().collect();
+        let mut children: BTreeMap<&[String], Vec<&[String]>> = BTreeMap::new();
+        for (path, _) in &public_items {
+            children
+                .entry(&path[..path.len().saturating_sub(1)])
+                .or_default()
+                .push(path);
+        }
+        let join = |path: &[String], name: &str| {
+            path.iter()
+                .cloned()
+                .chain(Some(name.to_string()))
+                .collect::<Vec<_>>()
+        };
+
+        // the public paths of the modules, first at their declared paths through ``pub mod`` declarations,
+        // then at the first path they are re-exported at, in breadth-first order
+        let mut module_paths: BTreeMap<Vec<String>, Vec<String>> = BTreeMap::new();
+        let mut order = vec![];
+        let mut queue = VecDeque::from([vec![self.crate_.name.clone()]]);
+        while let Some(path) = queue.pop_front() {
+            let Some(module) = modules.get(path.as_slice()) else {
+                continue;
+            };
+            if module_paths.contains_key(&path) {
+                continue;
+            }
+            module_paths.insert(path.clone(), path.clone());
+            order.push(path.clone());
+            queue.extend(module.declarations.iter().map(|name| join(&path, name)));
+        }
+        let mut queue: VecDeque<(Vec<String>, Vec<String>)> =
+            order.into_iter().map(|p| (p.clone(), p)).collect();
+        let mut item_paths: BTreeMap<&[String], Vec<Vec<String>>> = BTreeMap::new();
+        while let Some((declared, public_path)) = queue.pop_front() {
+            let Some(module) = modules.get(declared.as_slice()) else {
+                continue;
+            };
+            for item in children.get(declared.as_slice()).into_iter().flatten() {
+                let name = item.last().cloned().unwrap_or_default();
+                item_paths
+                    .entry(item)
+                    .or_default()
+                    .push(join(&public_path, &name));
+            }
+            let module_path = declared.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+            for use_ in module.uses.iter().filter(|u| u.public) {
+                let target = resolve_relative_path(&module_path, &use_.path.join("::"));
+                let reexports: Vec<(Vec<String>, Vec<String>)> = match &use_.name {
+                    Some(name) => vec![(target, join(&public_path, name))],
+                    // a glob re-exports the public items and sub-modules of the target
+                    None => modules
+                        .get(target.as_slice())
+                        .into_iter()
+                        .flat_map(|m| m.declarations.iter().map(|name| join(&target, name)))
+                        .chain(
+                            children
+                                .get(target.as_slice())
+                                .into_iter()
+                                .flatten()
+                                .map(|item| item.to_vec()),
+                        )
+                        .map(|path| {
+                            let name = path.last().cloned().unwrap_or_default();
+                            (path, join(&public_path, &name))
+                        })
+                        .collect(),
+                };
+                for (target, exposed) in reexports {
+                    if modules.contains_key(target.as_slice()) {
+                        if !module_paths.contains_key(&target) {
+                            module_paths.insert(target.clone(), exposed.clone());
+                            queue.push_back((target, exposed));
+                        }
+                    } else if let Some((item, _)) = kinds.get_key_value(target.as_slice()) {
+                        item_paths.entry(item).or_default().push(exposed);
+                    }
+                }
+            }
+        }
+
+        let mut api: Vec<ItemRef> = module_paths
+            .into_iter()
+            .map(|(declared, public_path)| (declared, public_path, ItemKind::Module))
+            .chain(item_paths.into_iter().filter_map(|(declared, mut paths)| {
+                paths.sort_by(|a, b| a.len().cmp(&b.len()).then(a.cmp(b)));
+                let public_path = if paths.iter().any(|p| p == declared) {
+                    declared.to_vec()
+                } else {
+                    paths.into_iter().next()?
+                };
+                Some((declared.to_vec(), public_path, kinds[declared]))
+            }))
+            .map(|(declared, public_path, kind)| ItemRef {
+                name: public_path.join("::"),
+                kind: Some(kind),
+                path: Some(declared.join("::")),
+                args: vec![],
+                url: None,
+            })
+            .collect();
+        api.sort_by(|a, b| a.name.cmp(&b.name));
+        api
+    }
+
+    /// The fully qualified names and kinds of the public items declared in extern blocks
+    fn foreign_items(&self) -> impl Iterator<Item = (String, ItemKind)> + '_ {
+        self.extern_blocks.iter().flat_map(|block| {
+            block
+                .functions
+                .iter()
+                .map(|f| (f.path_str(), ItemKind::Function))
+                .chain(
+                    block
+                        .statics
+                        .iter()
+                        .map(|s| (s.path_str(), ItemKind::Static)),
+                )
+        })
+    }
+
+    /// A flat manifest of every documented object, i.e. the modules and items,
+    /// and their fields, variants and methods (including those of impls and foreign functions),
+    /// with its kind, fully qualified name, anchor id and summary,
+    /// sorted by path, then kind, and deduplicated.
+    pub fn object_manifest(&self) -> Vec<ObjectEntry> {
+        let mut entries: BTreeSet<ObjectEntry> = BTreeSet::new();
+        let mut add = |path: String, anchor_id: String, docstring: &str| {
+            entries.insert(ObjectEntry {
+                kind: anchor_id.split('.').next().unwrap_or_default().to_string(),
+                path,
+                anchor_id,
+                summary: docstring
+                    .lines()
+                    .next()
+                    .unwrap_or_default()
+                    .trim()
+                    .to_string(),
+            });
+        };
+        for module in &self.modules {
+            add(module.path_str(), module.anchor_id(), &module.docstring);
+        }
+        for struct_ in &self.structs {
+            add(struct_.path_str(), struct_.anchor_id(), &struct_.docstring);
+            for field in &struct_.fields {
+                add(field.path.join("::"), field.anchor_id(), &field.docstring);
+            }
+        }
+        for enum_ in &self.enums {
+            add(enum_.path_str(), enum_.anchor_id(), &enum_.docstring);
+            for variant in &enum_.variants {
+                add(variant.name(), variant.anchor_id(), &variant.docstring);
+                for field in &variant.fields {
+                    add(field.path.join("::"), field.anchor_id(), &field.docstring);
+                }
+            }
+        }
+        let functions = self
+            .functions
+            .iter()
+            .chain(self.traits.iter().flat_map(|t| &t.functions))
+            .chain(self.impls.iter().flat_map(|i| &i.functions))
+            .chain(self.extern_blocks.iter().flat_map(|b| &b.functions));
+        for function in functions {
+            add(
+                function.path_str(),
+                function.anchor_id(),
+                &function.docstring,
+            );
+        }
+        for trait_ in &self.traits {
+            add(trait_.path_str(), trait_.anchor_id(), &trait_.docstring);
+        }
+        for alias in &self.type_aliases {
+            add(alias.path_str(), alias.anchor_id(), &alias.docstring);
+        }
+        for macro_ in &self.macros {
+            add(macro_.path_str(), macro_.anchor_id(), &macro_.docstring);
+        }
+        for const_ in &self.consts {
+            add(const_.path_str(), const_.anchor_id(), &const_.docstring);
+        }
+        for static_ in &self.statics {
+            add(static_.path_str(), static_.anchor_id(), &static_.docstring);
+        }
+        for static_ in self.extern_blocks.iter().flat_map(|b| &b.statics) {
+            add(static_.path_str(), static_.anchor_id(), &static_.docstring);
+        }
+        let mut entries = entries.into_iter().collect::<Vec<_>>();
+        entries.sort_by(|a, b| (&a.path, &a.kind).cmp(&(&b.path, &b.kind)));
+        entries.dedup_by(|a, b| a.anchor_id == b.anchor_id);
+        entries
+    }
+
+    /// A map of each feature to the fully qualified names of the effectively public modules, items
+    /// and methods gated behind it, e.g. for a page documenting what enabling each feature provides.
+    ///
+    /// An item is gated behind the features named in ``feature = "..."`` in its ``cfg_info``,
+    /// outside of a ``not(...)``, so an item under ``any(feature = "a", feature = "b")`` is listed for both.
+    /// The predicates of the enclosing modules (and impl or trait) are only included
+    /// once ``cfg_info`` has been resolved for a crate analysis.
+    pub fn feature_matrix(&self) -> BTreeMap<String, Vec<String>> {
+        let mut matrix: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        let mut add = |path: String, cfg_info: &Option<String>| {
+            let Some(predicate) = cfg_info.as_deref().and_then(CfgPredicate::parse_str) else {
+                return;
+            };
+            for feature in predicate.enabling_features() {
+                matrix.entry(feature).or_default().insert(path.clone());
+            }
+        };
+        for module in self.modules.iter().filter(|m| m.effectively_public) {
+            add(module.path_str(), &module.cfg_info);
+        }
+        for struct_ in self.structs.iter().filter(|s| s.effectively_public) {
+            add(struct_.path_str(), &struct_.cfg_info);
+        }
+        for enum_ in self.enums.iter().filter(|e| e.effectively_public) {
+            add(enum_.path_str(), &enum_.cfg_info);
+        }
+        let functions = self
+            .functions
+            .iter()
+            .chain(self.traits.iter().flat_map(|t| &t.functions))
+            .chain(self.impls.iter().flat_map(|i| &i.functions))
+            .filter(|f| f.effectively_public);
+        for function in functions {
+            add(function.path_str(), &function.cfg_info);
+        }
+        for trait_ in self.traits.iter().filter(|t| t.effectively_public) {
+            add(trait_.path_str(), &trait_.cfg_info);
+        }
+        for alias in self.type_aliases.iter().filter(|a| a.effectively_public) {
+            add(alias.path_str(), &alias.cfg_info);
+        }
+        for macro_ in self.macros.iter().filter(|m| m.effectively_public) {
+            add(macro_.path_str(), &macro_.cfg_info);
+        }
+        for const_ in self.consts.iter().filter(|c| c.effectively_public) {
+            add(const_.path_str(), &const_.cfg_info);
+        }
+        for static_ in self.statics.iter().filter(|s| s.effectively_public) {
+            add(static_.path_str(), &static_.cfg_info);
+        }
+        matrix
+            .into_iter()
+            .map(|(feature, paths)| (feature, paths.into_iter().collect()))
+            .collect()
+    }
+
+    /// Reconstruct the module hierarchy from the flat list of modules, rooted at the crate
+    pub fn module_tree(&self) -> ModuleNode {
+        self.module_node(std::slice::from_ref(&self.crate_.name))
+    }
+
+    /// Reconstruct the module hierarchy as for ``module_tree``, optionally collapsing chains of modules,
+    /// e.g. for a more compact sidebar.
+    ///
+    /// If ``collapse_chains`` is set, a module (other than the crate root) with exactly one child module
+    /// and no items of its own is merged with its child, recursively, so that e.g. ``a::b::c``
+    /// becomes a single node, for ``a::b::c``, with ``a`` and ``a::b`` recorded as ``collapsed``.
+    pub fn module_tree_with(&self, collapse_chains: bool) -> ModuleNode {
+        let mut tree = self.module_tree();
+        if collapse_chains {
+            tree.children = tree.children.into_iter().map(collapse_chain).collect();
+        }
+        tree
+    }
+
+    fn module_node(&self, path: &[String]) -> ModuleNode {
+        let is_child = |child: &[String]| child.len() == path.len() + 1 && child.starts_with(path);
+        let mut children: Vec<ModuleNode> = self
+            .modules
+            .iter()
+            .filter(|m| is_child(&m.path))
+            .map(|m| self.module_node(&m.path))
+            .collect();
+        children.sort_by(|a, b| a.path.cmp(&b.path));
+        let mut items: Vec<String> = self
+            .structs
+            .iter()
+            .filter(|s| is_child(&s.path))
+            .map(|s| s.path_str())
+            .chain(
+                self.enums
+                    .iter()
+                    .filter(|e| is_child(&e.path))
+                    .map(|e| e.path_str()),
+            )
+            .chain(
+                self.functions
+                    .iter()
+                    .filter(|f| is_child(&f.path))
+                    .map(|f| f.path_str()),
+            )
+            .chain(
+                self.traits
+                    .iter()
+                    .filter(|t| is_child(&t.path))
+                    .map(|t| t.path_str()),
+            )
+            .chain(
+                self.type_aliases
+                    .iter()
+                    .filter(|a| is_child(&a.path))
+                    .map(|a| a.path_str()),
+            )
+            .chain(
+                self.consts
+                    .iter()
+                    .filter(|c| is_child(&c.path))
+                    .map(|c| c.path_str()),
+            )
+            .chain(
+                self.statics
+                    .iter()
+                    .filter(|s| is_child(&s.path))
+                    .map(|s| s.path_str()),
+            )
+            .chain(
+                self.foreign_items()
+                    .map(|(path, _)| path)
+                    .filter(|p| is_child(&p.split("::").map(String::from).collect::<Vec<_>>())),
+            )
+            .collect();
+        items.sort();
+        ModuleNode {
+            path: path.to_vec(),
+            children,
+            items,
+            collapsed: vec![],
+        }
+    }
+
+    /// Group the structs, enums, functions, traits, type aliases, constants and statics by the module they are declared in,
+    /// keyed by the fully qualified name of the module.
+    ///
+    /// Every module has an entry, even if it declares no items,
+    /// and items within each module are in the order of the analysis result.
+    pub fn by_module(&self) -> BTreeMap<Vec<String>, ModuleContents<'_>> {
+        let mut contents: BTreeMap<Vec<String>, ModuleContents> = self
+            .modules
+            .iter()
+            .map(|m| (m.path.clone(), ModuleContents::default()))
+            .collect();
+        let parent = |path: &[String]| path[..path.len().saturating_sub(1)].to_vec();
+        for struct_ in &self.structs {
+            let entry = contents.entry(parent(&struct_.path)).or_default();
+            entry.structs.push(struct_);
+        }
+        for enum_ in &self.enums {
+            let entry = contents.entry(parent(&enum_.path)).or_default();
+            entry.enums.push(enum_);
+        }
+        for function in &self.functions {
+            let entry = contents.entry(parent(&function.path)).or_default();
+            entry.functions.push(function);
+        }
+        for trait_ in &self.traits {
+            let entry = contents.entry(parent(&trait_.path)).or_default();
+            entry.traits.push(trait_);
+        }
+        for alias in &self.type_aliases {
+            let entry = contents.entry(parent(&alias.path)).or_default();
+            entry.type_aliases.push(alias);
+        }
+        for const_ in &self.consts {
+            let entry = contents.entry(parent(&const_.path)).or_default();
+            entry.consts.push(const_);
+        }
+        for static_ in &self.statics {
+            let entry = contents.entry(parent(&static_.path)).or_default();
+            entry.statics.push(static_);
+        }
+        contents
+    }
+
+    /// Resolve the paths in a rendered type, e.g. ``Option<net::Socket>``, to known items.
+    ///
+    /// A path is matched if it is the fully qualified name of an item
+    /// (a leading ``crate`` is replaced by the crate name),
+    /// or else if it is the unique suffix of the fully qualified name of an item.
+    /// References, pointers, slices and arrays are looked through to the type they contain.
+    ///
+    /// Returns ``None`` if the type cannot be parsed, or if neither the leading path
+    /// nor any of its generic arguments resolve to a known item.
+    pub fn resolve_type(&self, type_str: &str) -> Option<ItemRef> {
+        // parsing is recursive, so pathologically nested types are not resolved
+        if nesting_depth(type_str) > DEFAULT_MAX_PARSE_DEPTH {
+            return None;
+        }
+        let ty: syn::Type = syn::parse_str(type_str).ok()?;
+        self.resolve_syn_type(&self.item_kinds(), None, &ty)
+    }
+
+    /// Resolve a type as for ``resolve_type``, and also resolve the paths to items of external crates,
+    /// e.g. ``serde::Serialize``, to their documentation URL with the given resolver.
+    ///
+    /// A path is to an external crate if it is not a known item,
+    /// and its first segment is a crate referenced by the analysis, a dependency, or ``std``, ``core`` or ``alloc``.
+    /// Names imported with ``use`` are not resolved, since the module the type is written in is not known.
+    pub fn resolve_type_with_urls(
+        &self,
+        type_str: &str,
+        resolver: &DocUrlResolver,
+    ) -> Option<ItemRef> {
+        if nesting_depth(type_str) > DEFAULT_MAX_PARSE_DEPTH {
+            return None;
+        }
+        let ty: syn::Type = syn::parse_str(type_str).ok()?;
+        let external = ExternalDocs {
+            crates: self.external_crates(),
+            resolver,
+        };
+        self.resolve_syn_type(&self.item_kinds(), Some(&external), &ty)
+    }
+
+    /// Link the path segments of all rendered types that are paths to items of external crates,
+    /// as for ``resolve_type_with_urls``, to their documentation URL with the given resolver,
+    /// e.g. ``serde_json::Value`` to ``https://docs.rs/serde_json/latest/serde_json/?search=Value``.
+    ///
+    /// Only the outermost path of a segment is linked, e.g. ``serde_json::Map`` for ``serde_json::Map<String, Value>``.
+    pub fn link_external_types(&mut self, resolver: &DocUrlResolver) {
+        let kinds = self.item_kinds();
+        let external = ExternalDocs {
+            crates: self.external_crates(),
+            resolver,
+        };
+        // the signatures are replaced in place, so are resolved against a copy of the analysis
+        let analysis = self.clone();
+        self.map_signatures(|signature| {
+            signature
+                .iter()
+                .map(|segment| match segment {
+                    TypeSegment::Path(path) if nesting_depth(path) <= DEFAULT_MAX_PARSE_DEPTH => {
+                        let url = syn::parse_str::<syn::Type>(path)
+                            .ok()
+                            .and_then(|ty| analysis.resolve_syn_type(&kinds, Some(&external), &ty))
+                            .and_then(|resolved| resolved.url);
+                        match url {
+                            Some(url) => TypeSegment::ExternalPath {
+                                path: path.clone(),
+                                url,
+                            },
+                            None => segment.clone(),
+                        }
+                    }
+                    segment => segment.clone(),
+                })
+                .collect()
+        });
+    }
+
+    /// The modules and items declared in a source file, sorted by their fully qualified name.
+    ///
+    /// The path matches a file if it is the same, or a suffix of it by whole components,
+    /// e.g. ``src/net/socket.rs`` matches ``/path/to/crate/src/net/socket.rs``.
+    /// Methods are not included, as they are documented with their type or trait.
+    pub fn items_in_file(&self, path: &str) -> Vec<ItemRef> {
+        let matches = |file: &Option<String>| {
+            file.as_ref()
+                .is_some_and(|file| Path::new(file).ends_with(Path::new(path)))
+        };
+        let mut items: Vec<(&[String], ItemKind)> = vec![];
+        items.extend(
+            self.modules
+                .iter()
+                .filter(|m| matches(&m.file))
+                .map(|m| (m.path.as_slice(), ItemKind::Module)),
+        );
+        items.extend(
+            self.structs
+                .iter()
+                .filter(|s| matches(&s.file))
+                .map(|s| (s.path.as_slice(), ItemKind::Struct)),
+        );
+        items.extend(
+            self.enums
+                .iter()
+                .filter(|e| matches(&e.file))
+                .map(|e| (e.path.as_slice(), ItemKind::Enum)),
+        );
+        items.extend(
+            self.functions
+                .iter()
+                .filter(|f| matches(&f.file))
+                .map(|f| (f.path.as_slice(), ItemKind::Function)),
+        );
+        items.extend(
+            self.traits
+                .iter()
+                .filter(|t| matches(&t.file))
+                .map(|t| (t.path.as_slice(), ItemKind::Trait)),
+        );
+        items.extend(
+            self.type_aliases
+                .iter()
+                .filter(|a| matches(&a.file))
+                .map(|a| (a.path.as_slice(), ItemKind::TypeAlias)),
+        );
+        items.extend(
+            self.consts
+                .iter()
+                .filter(|c| matches(&c.file))
+                .map(|c| (c.path.as_slice(), ItemKind::Const)),
+        );
+        items.extend(
+            self.statics
+                .iter()
+                .filter(|s| matches(&s.file))
+                .map(|s| (s.path.as_slice(), ItemKind::Static)),
+        );
+        for block in self.extern_blocks.iter().filter(|b| matches(&b.file)) {
+            items.extend(
+                block
+                    .functions
+                    .iter()
+                    .map(|f| (f.path.as_slice(), ItemKind::Function)),
+            );
+            items.extend(
+                block
+                    .statics
+                    .iter()
+                    .map(|s| (s.path.as_slice(), ItemKind::Static)),
+            );
+        }
+        items.sort_by(|a, b| a.0.cmp(b.0));
+        items
+            .into_iter()
+            .map(|(path, kind)| ItemRef {
+                name: path.last().cloned().unwrap_or_default(),
+                kind: Some(kind),
+                path: Some(path.join("::")),
+                args: vec![],
+                url: None,
+            })
+            .collect()
+    }
+
+    /// The methods that a trait inherits from its supertraits, transitively,
+    /// in breadth-first order of the supertrait declarations and without duplicates.
+    ///
+    /// Supertraits are resolved relative to the module of the trait that declares them.
+    /// Supertraits that are not part of the analysis are listed without methods,
+    /// and their own supertraits are unknown.
+    pub fn supertrait_methods(&self, trait_path: &str) -> Vec<InheritedMethods> {
+        let traits: BTreeMap<String, &Trait> =
+            self.traits.iter().map(|t| (t.path_str(), t)).collect();
+        let Some(trait_) = traits.get(trait_path) else {
+            return vec![];
+        };
+        let kinds = self.item_kinds();
+        let mut inherited: Vec<InheritedMethods> = vec![];
+        let mut visited = BTreeSet::from([trait_path.to_string()]);
+        let mut queue = VecDeque::from([*trait_]);
+        while let Some(current) = queue.pop_front() {
+            let parent = current.path[..current.path.len() - 1]
+                .iter()
+                .map(|s| s.as_str())
+                .collect::<Vec<_>>();
+            for supertrait in &current.supertraits {
+                let resolved = self.resolve_supertrait(&traits, &kinds, &parent, supertrait);
+                let path = resolved.map_or_else(|| supertrait.clone(), |t| t.path_str());
+                if !visited.insert(path.clone()) {
+                    continue;
+                }
+                inherited.push(InheritedMethods {
+                    trait_: path,
+                    local: resolved.is_some(),
+                    functions: resolved.map(|t| t.functions.clone()).unwrap_or_default(),
+                });
+                if let Some(resolved) = resolved {
+                    queue.push_back(resolved);
+                }
+            }
+        }
+        inherited
+    }
+
+    /// The module an item is declared in, given the fully qualified name of the item,
+    /// i.e. the module whose path is that of the item without its last segment,
+    /// e.g. the root module for ``my_crate::MyStruct``.
+    ///
+    /// This is ``None`` for the root module, and for members of types and traits, such as methods.
+    pub fn parent_module(&self, item_path: &str) -> Option<&Module> {
+        let (parent, _) = item_path.rsplit_once("::")?;
+        self.modules.iter().find(|m| m.path_str() == parent)
+    }
+
+    /// The impl blocks for a type, given its fully qualified name, from all modules of the analysis,
+    /// with the inherent impls first, then the trait impls, each in the order of the analysis result.
+    ///
+    /// An impl is matched by its base type, as resolved by ``impl_owner``.
+    /// Blanket impls, e.g. ``impl<T: Display> ToString for T``, are only listed with their trait,
+    /// by ``implementors``.
+    pub fn impls_for(&self, type_path: &str) -> Vec<&Impl> {
+        self.impls_for_with(type_path, false)
+    }
+
+    /// The impl blocks for a type as for ``impls_for``, optionally followed by the blanket impls of the analysis.
+    ///
+    /// If ``include_blanket`` is set and the type is a struct or enum, all blanket impls are listed after
+    /// its own impls, e.g. for a full "Trait Implementations" section.
+    /// As for rustdoc, the bounds of a blanket impl are not checked against the type.
+    pub fn impls_for_with(&self, type_path: &str, include_blanket: bool) -> Vec<&Impl> {
+        let kinds = self.item_kinds();
+        let (inherent, trait_impls): (Vec<&Impl>, Vec<&Impl>) = self
+            .impls
+            .iter()
+            .filter(|impl_| self.impl_owner(&kinds, impl_).as_deref() == Some(type_path))
+            .partition(|impl_| impl_.trait_.is_none());
+        let blanket_impls = match kinds.get(type_path) {
+            Some(ItemKind::Struct | ItemKind::Enum) if include_blanket => self
+                .impls
+                .iter()
+                .filter(|impl_| impl_.is_blanket())
+                .collect(),
+            _ => vec![],
+        };
+        inherent
+            .into_iter()
+            .chain(trait_impls)
+            .chain(blanket_impls)
+            .collect()
+    }
+
+    /// The types that implement a trait, given its fully qualified name, from all modules of the analysis,
+    /// in the order of the analysis result.
+    ///
+    /// The self types are kept as written, with their generic arguments, array lengths and tuple elements,
+    /// so that e.g. ``impl Foo for Vec<A>`` and ``impl Foo for Vec<B>`` are listed separately.
+    /// The trait of an impl is resolved as for ``impl_owner``; a trait that is not part of the analysis
+    /// is matched by its path as written, or as imported by a ``use`` declaration, e.g. ``serde::Serialize``.
+    /// Negative impls, e.g. ``impl !Send for MyType``, are omitted.
+    pub fn implementors(&self, trait_path: &str) -> Vec<Implementor> {
+        let kinds = self.item_kinds();
+        self.impls
+            .iter()
+            .filter(|impl_| self.impl_trait(&kinds, impl_).as_deref() == Some(trait_path))
+            .map(|impl_| Implementor {
+                self_type: impl_.self_type.clone(),
+                type_path: self.impl_owner(&kinds, impl_),
+                generics: impl_.generics.clone(),
+                parent: impl_.parent.clone(),
+            })
+            .collect()
+    }
+
+    /// The methods of a local trait, in the order they are declared, as implemented for a type,
+    /// given the fully qualified names of the type and the trait,
+    /// e.g. to list the required and provided methods of an implementor.
+    ///
+    /// A method is overridden if any impl of the trait attributed to the type defines it.
+    /// This is empty if the trait is not part of the analysis, or the type does not implement it.
+    pub fn implemented_methods(&self, type_path: &str, trait_path: &str) -> Vec<ImplementedMethod> {
+        let Some(trait_) = self.traits.iter().find(|t| t.path_str() == trait_path) else {
+            return vec![];
+        };
+        let kinds = self.item_kinds();
+        let impls = self
+            .impls_for(type_path)
+            .into_iter()
+            .filter(|impl_| self.impl_trait(&kinds, impl_).as_deref() == Some(trait_path))
+            .collect::<Vec<_>>();
+        if impls.is_empty() {
+            return vec![];
+        }
+        let defined: BTreeSet<&str> = impls
+            .iter()
+            .flat_map(|impl_| impl_.functions.iter().map(|f| f.name.as_str()))
+            .collect();
+        trait_
+            .functions
+            .iter()
+            .map(|function| ImplementedMethod {
+                function: function.clone(),
+                required: !trait_.provided_methods.contains(&function.name),
+                overridden: defined.contains(function.name.as_str()),
+            })
+            .collect()
+    }
+
+    /// The functions of the inherent impls of a type, given its fully qualified name,
+    /// split into associated functions and methods, each in the order they are declared,
+    /// e.g. to document them in separate sections as rustdoc does
+    pub fn inherent_functions(&self, type_path: &str) -> InherentFunctions<'_> {
+        let (methods, associated_functions) = self
+            .impls_for(type_path)
+            .into_iter()
+            .filter(|impl_| impl_.trait_.is_none())
+            .flat_map(|impl_| impl_.functions.iter())
+            .partition(|function| function.is_method());
+        InherentFunctions {
+            associated_functions,
+            methods,
+        }
+    }
+
+    /// The methods that can be called on a type through its ``Deref`` impl,
+    /// i.e. those of the inherent impls of its ``Deref`` target, given the fully qualified name of the type.
+    ///
+    /// This is empty if the type has no ``Deref`` target, or if the target is not a local type.
+    pub fn deref_methods(&self, type_path: &str) -> Vec<&Function> {
+        let target = self
+            .structs
+            .iter()
+            .find(|s| s.path_str() == type_path)
+            .and_then(|s| s.deref_target.as_ref())
+            .or_else(|| {
+                self.enums
+                    .iter()
+                    .find(|e| e.path_str() == type_path)
+                    .and_then(|e| e.deref_target.as_ref())
+            });
+        let Some(target) = target else {
+            return vec![];
+        };
+        let target = target.iter().map(|s| s.content()).collect::<String>();
+        let Some(target_path) = self.resolve_type(&target).and_then(|r| r.path) else {
+            return vec![];
+        };
+        self.impls_for(&target_path)
+            .into_iter()
+            .filter(|impl_| impl_.trait_.is_none())
+            .flat_map(|impl_| impl_.functions.iter())
+            .collect()
+    }
+
+    /// The fully qualified name of the type an impl is attributed to, if it is a known item.
+    ///
+    /// The base type of the impl is resolved relative to the module it is declared in,
+    /// or else through the ``use`` declarations of that module,
+    /// or else as the unique suffix of a fully qualified name.
+    pub(super) fn impl_owner(
+        &self,
+        kinds: &BTreeMap<String, ItemKind>,
+        impl_: &Impl,
+    ) -> Option<String> {
+        let base = impl_.base_type.as_ref()?;
+        self.resolve_in_module(kinds, &impl_.parent, base)
+    }
+
+    /// The trait a (positive) trait impl implements: its fully qualified name if it is a local trait,
+    /// otherwise its path as written, or as imported by a ``use`` declaration
+    fn impl_trait(&self, kinds: &BTreeMap<String, ItemKind>, impl_: &Impl) -> Option<String> {
+        let trait_ = impl_.trait_.as_ref()?;
+        let trait_ = trait_.iter().map(|s| s.content()).collect::<String>();
+        if trait_.starts_with('!') {
+            return None;
+        }
+        let name = trait_.split('<').next().unwrap_or_default().trim();
+        match self.resolve_in_module(kinds, &impl_.parent, name) {
+            Some(path) if kinds.get(&path) == Some(&ItemKind::Trait) => Some(path),
+            _ => Some(self.imported_path(&impl_.parent, name)),
+        }
+    }
+
+    /// The fully qualified name of an item, given its path as written in a module, if it is a known item.
+    ///
+    /// The path is resolved relative to the module, or else through the ``use`` declarations of the module,
+    /// or else as the unique suffix of a fully qualified name.
+    pub(super) fn resolve_in_module(
+        &self,
+        kinds: &BTreeMap<String, ItemKind>,
+        module_path: &[String],
+        path: &str,
+    ) -> Option<String> {
+        let parent = module_path.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+        let relative = resolve_relative_path(&parent, path).join("::");
+        if kinds.contains_key(&relative) {
+            return Some(relative);
+        }
+        if let Some(imported) = self.resolve_imported(module_path, path) {
+            return Some(imported.join("::"));
+        }
+        self.resolve_path(kinds, path).map(|(path, _)| path)
+    }
+
+    /// A path as written in a module, with its first segment replaced by the path it is imported from,
+    /// as written in the ``use`` declaration, e.g. ``serde::Serialize`` for ``Serialize``
+    /// with ``use serde::Serialize;``
+    fn imported_path(&self, module_path: &[String], path: &str) -> String {
+        let (first, rest) = match path.split_once("::") {
+            Some((first, rest)) => (first, Some(rest)),
+            None => (path, None),
+        };
+        let use_ = self
+            .modules
+            .iter()
+            .find(|m| m.path == module_path)
+            .and_then(|m| m.uses.iter().find(|u| u.name.as_deref() == Some(first)));
+        match use_ {
+            Some(use_) => use_
+                .path
+                .iter()
+                .map(|s| s.as_str())
+                .chain(rest)
+                .collect::<Vec<_>>()
+                .join("::"),
+            None => path.to_string(),
+        }
+    }
+
+    /// Resolve a path, as written in a module, through the named ``use`` declarations of the module,
+    /// e.g. ``Foo`` to ``my_crate::a::Foo`` for ``use crate::a::Foo;``
+    fn resolve_imported(&self, module_path: &[String], path: &str) -> Option<Vec<String>> {
+        let module = self.modules.iter().find(|m| m.path == module_path)?;
+        let (first, rest) = match path.split_once("::") {
+            Some((first, rest)) => (first, Some(rest)),
+            None => (path, None),
+        };
+        let use_ = module
+            .uses
+            .iter()
+            .find(|u| u.name.as_deref() == Some(first))?;
+        let parent = module_path.iter().map(|s| s.as_str()).collect::<Vec<_>>();
+        let mut resolved = resolve_relative_path(&parent, &use_.path.join("::"));
+        resolved.extend(
+            rest.into_iter()
+                .flat_map(|r| r.split("::").map(String::from)),
+        );
+        Some(resolved)
+    }
+
+    /// Resolve a supertrait, as written in the trait declared in the ``parent`` module,
+    /// to a local trait
+    pub(super) fn resolve_supertrait<'a>(
+        &self,
+        traits: &BTreeMap<String, &'a Trait>,
+        kinds: &BTreeMap<String, ItemKind>,
+        parent: &[&str],
+        supertrait: &str,
+    ) -> Option<&'a Trait> {
+        let name = supertrait.split('<').next().unwrap_or_default().trim();
+        let relative = resolve_relative_path(parent, name).join("::");
+        traits
+            .get(&relative)
+            .or_else(|| match self.resolve_path(kinds, name) {
+                Some((path, ItemKind::Trait)) => traits.get(&path),
+                _ => None,
+            })
+            .copied()
+    }
+
+    /// A map of the fully qualified names of all items to their kind
+    pub(super) fn item_kinds(&self) -> BTreeMap<String, ItemKind> {
+        let mut kinds = BTreeMap::new();
+        kinds.extend(
+            self.modules
+                .iter()
+                .map(|m| (m.path_str(), ItemKind::Module)),
+        );
+        kinds.extend(
+            self.structs
+                .iter()
+                .map(|s| (s.path_str(), ItemKind::Struct)),
+        );
+        kinds.extend(self.enums.iter().map(|e| (e.path_str(), ItemKind::Enum)));
+        kinds.extend(
+            self.functions
+                .iter()
+                .map(|f| (f.path_str(), ItemKind::Function)),
+        );
+        kinds.extend(self.traits.iter().map(|t| (t.path_str(), ItemKind::Trait)));
+        kinds.extend(
+            self.type_aliases
+                .iter()
+                .map(|a| (a.path_str(), ItemKind::TypeAlias)),
+        );
+        kinds.extend(self.macros.iter().map(|m| (m.path_str(), ItemKind::Macro)));
+        kinds.extend(self.consts.iter().map(|c| (c.path_str(), ItemKind::Const)));
+        kinds.extend(
+            self.statics
+                .iter()
+                .map(|s| (s.path_str(), ItemKind::Static)),
+        );
+        kinds.extend(self.foreign_items());
+        kinds
+    }
+
+    fn resolve_syn_type(
+        &self,
+        kinds: &BTreeMap<String, ItemKind>,
+        external: Option<&ExternalDocs>,
+        ty: &syn::Type,
+    ) -> Option<ItemRef> {
+        let path = match ty {
+            syn::Type::Reference(ref_) => {
+                return self.resolve_syn_type(kinds, external, &ref_.elem)
+            }
+            syn::Type::Ptr(ptr) => return self.resolve_syn_type(kinds, external, &ptr.elem),
+            syn::Type::Paren(paren) => return self.resolve_syn_type(kinds, external, &paren.elem),
+            syn::Type::Group(group) => return self.resolve_syn_type(kinds, external, &group.elem),
+            syn::Type::Slice(slice) => return self.resolve_syn_type(kinds, external, &slice.elem),
+            syn::Type::Array(array) => return self.resolve_syn_type(kinds, external, &array.elem),
+            syn::Type::Path(path) if path.qself.is_none() => &path.path,
+            _ => return None,
+        };
+        let segments = path
+            .segments
+            .iter()
+            .map(|s| s.ident.to_string())
+            .collect::<Vec<_>>();
+        let name = segments.join("::");
+        let args: Vec<Option<ItemRef>> = match path.segments.last().map(|s| &s.arguments) {
+            Some(syn::PathArguments::AngleBracketed(args)) => args
+                .args
+                .iter()
+                .filter_map(|arg| match arg {
+                    syn::GenericArgument::Type(ty) => {
+                        Some(self.resolve_syn_type(kinds, external, ty))
+                    }
+                    _ => None,
+                })
+                .collect(),
+            _ => vec![],
+        };
+        let resolved = self.resolve_path(kinds, &name);
+        let url = match (&resolved, external, segments.split_first()) {
+            (None, Some(external), Some((first, rest)))
+                if (!rest.is_empty() || path.leading_colon.is_some())
+                    && external.crates.contains(first) =>
+            {
+                external.resolver.resolve(first, rest)
+            }
+            _ => None,
+        };
+        if resolved.is_none() && url.is_none() && args.iter().all(Option::is_none) {
+            return None;
+        }
+        Some(ItemRef {
+            name,
+            kind: resolved.as_ref().map(|(_, kind)| *kind),
+            path: resolved.map(|(path, _)| path),
+            args,
+            url,
+        })
+    }
+
+    /// The names of the external crates referenced by the analyzed items,
+    /// taken from the first segment of paths in ``use`` declarations, ``extern crate`` items
+    /// and signatures, e.g. ``serde`` for ``serde::Value``.
+    ///
+    /// Paths are resolved relative to the module they are written in,
+    /// and those that refer to an analyzed module or item, or to a name imported by a ``use``,
+    /// are excluded, as is the crate itself.
+    /// Paths starting with an uppercase name are assumed to be associated types, e.g. ``T::Output``.
+    pub fn referenced_crates(&self) -> BTreeSet<String> {
+        let mut local_prefixes: BTreeSet<&[String]> = BTreeSet::new();
+        let item_paths = self
+            .modules
+            .iter()
+            .map(|m| &m.path)
+            .chain(self.structs.iter().map(|s| &s.path))
+            .chain(self.enums.iter().map(|e| &e.path))
+            .chain(self.functions.iter().map(|f| &f.path))
+            .chain(self.traits.iter().map(|t| &t.path))
+            .chain(self.type_aliases.iter().map(|a| &a.path))
+            .chain(self.consts.iter().map(|c| &c.path))
+            .chain(self.statics.iter().map(|s| &s.path));
+        for path in item_paths {
+            for end in 1..=path.len() {
+                local_prefixes.insert(&path[..end]);
+            }
+        }
+        let imports: BTreeMap<&[String], BTreeSet<&str>> = self
+            .modules
+            .iter()
+            .map(|m| {
+                let names = m.uses.iter().filter_map(|u| u.name.as_deref()).collect();
+                (m.path.as_slice(), names)
+            })
+            .collect();
+
+        // the first segments of paths, the module they are written in,
+        // and whether they are from a signature, and so may refer to a name imported by a ``use``
+        let mut roots: Vec<(Vec<String>, String, bool)> = vec![];
+        for module in &self.modules {
+            for use_ in &module.uses {
+                if let Some(first) = use_.path.first() {
+                    roots.push((module.path.clone(), first.clone(), false));
+                }
+            }
+        }
+        let mut add_signature = |module: &[String], signature: &[TypeSegment]| {
+            for segment in signature {
+                if let Some(path) = segment.path() {
+                    if nesting_depth(path) > DEFAULT_MAX_PARSE_DEPTH {
+                        continue;
+                    }
+                    if let Ok(ty) = syn::parse_str::<syn::Type>(path) {
+                        let mut names = vec![];
+                        type_path_roots(&ty, &mut names);
+                        roots.extend(names.into_iter().map(|name| (module.to_vec(), name, true)));
+                    }
+                }
+            }
+        };
+        let parent = |path: &[String]| path.len().saturating_sub(1);
+        for struct_ in &self.structs {
+            let module = &struct_.path[..parent(&struct_.path)];
+            for field in &struct_.fields {
+                add_signature(module, &field.type_);
+            }
+        }
+        for enum_ in &self.enums {
+            let module = &enum_.path[..parent(&enum_.path)];
+            for field in enum_.variants.iter().flat_map(|v| &v.fields) {
+                add_signature(module, &field.type_);
+            }
+        }
+        for function in &self.functions {
+            add_signature(
+                &function.path[..parent(&function.path)],
+                &function.signature(),
+            );
+        }
+        for trait_ in &self.traits {
+            let module = &trait_.path[..parent(&trait_.path)];
+            for function in &trait_.functions {
+                add_signature(module, &function.signature());
+            }
+        }
+        for alias in &self.type_aliases {
+            add_signature(&alias.path[..parent(&alias.path)], &alias.target);
+        }
+        for const_ in &self.consts {
+            add_signature(&const_.path[..parent(&const_.path)], &const_.type_);
+        }
+        for static_ in &self.statics {
+            add_signature(&static_.path[..parent(&static_.path)], &static_.type_);
+        }
+        for impl_ in &self.impls {
+            add_signature(&impl_.parent, &impl_.self_type);
+            if let Some(trait_) = &impl_.trait_ {
+                add_signature(&impl_.parent, trait_);
+            }
+            for function in &impl_.functions {
+                add_signature(&impl_.parent, &function.signature());
+            }
+        }
+        for block in &self.extern_blocks {
+            for function in &block.functions {
+                add_signature(&block.parent, &function.signature());
+            }
+            for static_ in &block.statics {
+                add_signature(&block.parent, &static_.type_);
+            }
+        }
+
+        roots
+            .into_iter()
+            .filter(|(module, name, in_signature)| {
+                if ["crate", "self", "super", "Self"].contains(&name.as_str())
+                    || *name == self.crate_.name
+                    || name.starts_with(char::is_uppercase)
+                    || (*in_signature
+                        && imports
+                            .get(module.as_slice())
+                            .is_some_and(|names| names.contains(name.as_str())))
+                {
+                    return false;
+                }
+                let local = [module.as_slice(), std::slice::from_ref(name)].concat();
+                !local_prefixes.contains(local.as_slice())
+            })
+            .map(|(_, name, _)| name)
+            .collect()
+    }
+
+    /// Match a path to the fully qualified name of an item
+    fn resolve_path(
+        &self,
+        kinds: &BTreeMap<String, ItemKind>,
+        name: &str,
+    ) -> Option<(String, ItemKind)> {
+        let name = match name.strip_prefix("crate::") {
+            Some(rest) => format!("{}::{}", self.crate_.name, rest),
+            None => name.to_string(),
+        };
+        if let Some(kind) = kinds.get(&name) {
+            return Some((name, *kind));
+        }
+        let suffix = format!("::{}", name);
+        let mut candidates = kinds.iter().filter(|(path, _)| path.ends_with(&suffix));
+        match (candidates.next(), candidates.next()) {
+            (Some((path, kind)), None) => Some((path.clone(), *kind)),
+            _ => None,
+        }
+    }
+}
+
+/// The external crates that paths may refer to, and how to link to their documentation
+struct ExternalDocs<'a> {
+    crates: BTreeSet<String>,
+    resolver: &'a DocUrlResolver,
+}
+
+/// Merge a module node with its only child while it has no items of its own, then collapse its children
+fn collapse_chain(mut node: ModuleNode) -> ModuleNode {
+    while node.children.len() == 1 && node.items.is_empty() {
+        let child = node.children.pop().unwrap();
+        let mut collapsed = std::mem::take(&mut node.collapsed);
+        collapsed.push(node.path);
+        node = ModuleNode { collapsed, ..child };
+    }
+    node.children = node.children.into_iter().map(collapse_chain).collect();
+    node
+}
+
+/// Collect the first segments of the multi-segment paths in a type, including in its generic arguments
+fn type_path_roots(ty: &syn::Type, roots: &mut Vec<String>) {
+    match ty {
+        syn::Type::Reference(ref_) => type_path_roots(&ref_.elem, roots),
+        syn::Type::Ptr(ptr) => type_path_roots(&ptr.elem, roots),
+        syn::Type::Paren(paren) => type_path_roots(&paren.elem, roots),
+        syn::Type::Group(group) => type_path_roots(&group.elem, roots),
+        syn::Type::Slice(slice) => type_path_roots(&slice.elem, roots),
+        syn::Type::Array(array) => type_path_roots(&array.elem, roots),
+        syn::Type::Tuple(tuple) => tuple.elems.iter().for_each(|t| type_path_roots(t, roots)),
+        syn::Type::Path(path) => {
+            let segments = &path.path.segments;
+            // for a qualified path, e.g. ``<T as io::Read>::Error``, the path of the trait
+            // is the segments before the position of the self type
+            let path_len = match &path.qself {
+                Some(qself) => {
+                    type_path_roots(&qself.ty, roots);
+                    qself.position
+                }
+                None => segments.len(),
+            };
+            if path_len > 1 || (path_len > 0 && path.path.leading_colon.is_some()) {
+                if let Some(first) = segments.first() {
+                    roots.push(first.ident.to_string());
+                }
+            }
+            for segment in segments {
+                if let syn::PathArguments::AngleBracketed(args) = &segment.arguments {
+                    for arg in &args.args {
+                        if let syn::GenericArgument::Type(ty) = arg {
+                            type_path_roots(ty, roots);
+                        }
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyze::AnalysisOptions;
+    use crate::data_model::{Crate, Module};
+    use insta::assert_yaml_snapshot;
+
+    /// An empty analysis of the crate ``my_crate``, to which the tests add modules and items
+    fn new_result() -> AnalysisResult {
+        AnalysisResult::new(Crate {
+            name: "my_crate".to_string(),
+            version: "0.1.0".to_string(),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_public_paths() {
+        let mut result = new_result();
+        let (module, items) = Module::parse(
+            None,
+            &["my_crate"],
+            r#"
+            pub mod b;
+            pub mod a;
+            pub struct Zed;
+            pub enum Alpha {}
+            pub fn func() {}
+            pub trait Tr {
+                fn method(&self);
+            }
+            extern "C" {
+                pub fn ffi();
+            }
+            impl Zed {
+                pub fn new() -> Self {}
+            }
+            struct Private;
+            impl Private {
+                pub fn hidden() {}
+            }
+            "#,
+        )
+        .unwrap();
+        result.modules.push(module.clone());
+        // duplicates are removed
+        result.modules.push(module);
+        result.extend_items(items);
+        assert_eq!(
+            result.public_paths(),
+            vec![
+                "my_crate",
+                "my_crate::Alpha",
+                "my_crate::Tr",
+                "my_crate::Tr::method",
+                "my_crate::Zed",
+                "my_crate::Zed::new",
+                "my_crate::ffi",
+                "my_crate::func"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_public_api() {
+        let mut result = new_result();
+        for (path, content) in [
+            (
+                vec!["my_crate"],
+                r#"
+                pub mod net;
+                pub mod prelude;
+                mod internal;
+                pub use internal::Hidden;
+                pub use internal::Renamed as Alias;
+                pub use net::Socket as Sock;
+                "#,
+            ),
+            (
+                vec!["my_crate", "net"],
+                "pub struct Socket; pub fn connect() {}",
+            ),
+            (vec!["my_crate", "prelude"], "pub use crate::net::*;"),
+            (
+                vec!["my_crate", "internal"],
+                "pub struct Hidden; pub struct Renamed; pub fn helper() {}",
+            ),
+        ] {
+            let (module, items) = Module::parse(None, &path, content).unwrap();
+            result.modules.push(module);
+            result.extend_items(items);
+        }
+        result.resolve_visibility();
+        let api = result
+            .public_api()
+            .into_iter()
+            .map(|item| {
+                format!(
+                    "{} ({:?}): {}",
+                    item.name,
+                    item.kind.unwrap(),
+                    item.path.unwrap()
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_yaml_snapshot!(api, @r###"
+        ---
+        - "my_crate (Module): my_crate"
+        - "my_crate::Alias (Struct): my_crate::internal::Renamed"
+        - "my_crate::Hidden (Struct): my_crate::internal::Hidden"
+        - "my_crate::net (Module): my_crate::net"
+        - "my_crate::net::Socket (Struct): my_crate::net::Socket"
+        - "my_crate::net::connect (Function): my_crate::net::connect"
+        - "my_crate::prelude (Module): my_crate::prelude"
+        "###);
+    }
+
+    #[test]
+    fn test_object_manifest() {
+        let mut result = new_result();
+        let content = r#"
+//! The crate
+//!
+//! More details
+/// A socket
+pub struct Socket {
+    /// The port
+    pub port: u16,
+}
+impl Socket {
+    /// Connect the socket
+    pub fn connect(&self) {}
+}
+impl Clone for Socket {
+    fn clone(&self) -> Self {}
+}
+pub enum State { Open(u8), Closed }
+pub trait Read { fn read(&mut self); }
+pub type Port = u16;
+/// Make a socket
+#[macro_export]
+macro_rules! socket { () => {}; }
+extern "C" { pub fn ffi(); pub static ERRNO: i32; }
+pub fn socket() {}
+"#;
+        let (module, items) = Module::parse(None, &["my_crate"], content).unwrap();
+        result.modules.push(module);
+        result.extend_items(items);
+        let manifest = result
+            .object_manifest()
+            .into_iter()
+            .map(|e| format!("{} {} ({}): {}", e.kind, e.path, e.anchor_id, e.summary))
+            .collect::<Vec<_>>();
+        assert_yaml_snapshot!(manifest, @r###"
+        ---
+        - "mod my_crate (mod.my_crate): The crate"
+        - "static my_crate::ERRNO (static.my_crate.ERRNO): "
+        - "type my_crate::Port (type.my_crate.Port): "
+        - "trait my_crate::Read (trait.my_crate.Read): "
+        - "method my_crate::Read::read (method.my_crate.Read.read): "
+        - "struct my_crate::Socket (struct.my_crate.Socket): A socket"
+        - "method my_crate::Socket::clone (method.my_crate.Socket.impl.Clone.clone): "
+        - "method my_crate::Socket::connect (method.my_crate.Socket.connect): Connect the socket"
+        - "structfield my_crate::Socket::port (structfield.my_crate.Socket.port): The port"
+        - "enum my_crate::State (enum.my_crate.State): "
+        - "variant my_crate::State::Closed (variant.my_crate.State.Closed): "
+        - "variant my_crate::State::Open (variant.my_crate.State.Open): "
+        - "structfield my_crate::State::Open::0 (structfield.my_crate.State.Open.0): "
+        - "fn my_crate::ffi (fn.my_crate.ffi): "
+        - "fn my_crate::socket (fn.my_crate.socket): "
+        - "macro my_crate::socket (macro.my_crate.socket): Make a socket"
+        "###);
+    }
+
+    #[test]
+    fn test_feature_matrix() {
+        let mut result = new_result();
+        let modules: Vec<(Vec<&str>, &str)> = vec![
+            (
+                vec!["my_crate"],
+                r#"
+pub struct Socket;
+#[cfg(feature = "serde")]
+impl Serialize for Socket {
+    fn serialize(&self) {}
+}
+#[cfg(any(feature = "tokio", feature = "async-std"))]
+pub fn spawn() {}
+#[cfg(all(unix, not(feature = "std")))]
+pub fn raw() {}
+"#,
+            ),
+            (
+                vec!["my_crate", "tls"],
+                r#"
+#![cfg(feature = "tls")]
+#[cfg(feature = "serde")]
+pub struct Config;
+"#,
+            ),
+        ];
+        for (path, content) in modules {
+            let (module, items) = Module::parse(None, &path, content).unwrap();
+            result.modules.push(module);
+            result.extend_items(items);
+        }
+        result.resolve_cfg_info();
+        assert_yaml_snapshot!(result.feature_matrix(), @r###"
+        ---
+        async-std:
+          - "my_crate::spawn"
+        serde:
+          - "my_crate::Socket::serialize"
+          - "my_crate::tls::Config"
+        tls:
+          - "my_crate::tls"
+          - "my_crate::tls::Config"
+        tokio:
+          - "my_crate::spawn"
+        "###);
+    }
+
+    #[test]
+    fn test_resolve_type() {
+        let mut result = new_result();
+        let (module, items) = Module::parse(
+            None,
+            &["my_crate", "net"],
+            r#"
+            pub struct Socket;
+            pub struct Other;
+            pub trait Stream {}
+            "#,
+        )
+        .unwrap();
+        result.modules.push(module);
+        result.extend_items(items);
+        let (_, items) = Module::parse(None, &["my_crate", "other"], "pub struct Other;").unwrap();
+        result.extend_items(items);
+
+        let socket = |name: &str| ItemRef {
+            name: name.to_string(),
+            kind: Some(ItemKind::Struct),
+            path: Some("my_crate::net::Socket".to_string()),
+            args: vec![],
+            url: None,
+        };
+        assert_eq!(
+            result.resolve_type("my_crate::net::Socket"),
+            Some(socket("my_crate::net::Socket"))
+        );
+        assert_eq!(
+            result.resolve_type("crate::net::Socket"),
+            Some(socket("crate::net::Socket"))
+        );
+        assert_eq!(
+            result.resolve_type("&'a mut Socket"),
+            Some(socket("Socket"))
+        );
+        // ambiguous and unknown paths are not resolved
+        assert_eq!(result.resolve_type("Other"), None);
+        assert_eq!(result.resolve_type("String"), None);
+        assert_eq!(
+            result.resolve_type("HashMap<u8 , Vec<net::Socket>>"),
+            Some(ItemRef {
+                name: "HashMap".to_string(),
+                kind: None,
+                path: None,
+                args: vec![
+                    None,
+                    Some(ItemRef {
+                        name: "Vec".to_string(),
+                        kind: None,
+                        path: None,
+                        args: vec![Some(socket("net::Socket"))],
+                        url: None,
+                    }),
+                ],
+                url: None,
+            })
+        );
+        // trait objects are not paths
+        assert_eq!(result.resolve_type("Box<dyn Stream>"), None);
+    }
+
+    #[test]
+    fn test_resolve_type_with_urls() {
+        let mut result = new_result();
+        let (module, items) = Module::parse(
+            None,
+            &["my_crate"],
+            "pub struct Socket; pub fn to_json(value: serde_json::Value) {}",
+        )
+        .unwrap();
+        result.modules.push(module);
+        result.extend_items(items);
+
+        let resolver = DocUrlResolver::default();
+        let resolved = result
+            .resolve_type_with_urls(
+                "std::collections::HashMap<serde_json::Value, Socket>",
+                &resolver,
+            )
+            .unwrap();
+        assert_eq!(
+            resolved.url.as_deref(),
+            Some("https://doc.rust-lang.org/std/?search=collections::HashMap")
+        );
+        assert_eq!(
+            resolved.args[0].as_ref().and_then(|r| r.url.as_deref()),
+            Some("https://docs.rs/serde_json/latest/serde_json/?search=Value")
+        );
+        assert_eq!(
+            resolved.args[1].as_ref().and_then(|r| r.path.as_deref()),
+            Some("my_crate::Socket")
+        );
+        // unknown crates and single-segment paths are not linked
+        assert_eq!(
+            result.resolve_type_with_urls("tokio::net::TcpStream", &resolver),
+            None
+        );
+        assert_eq!(result.resolve_type_with_urls("String", &resolver), None);
+
+        let resolver = DocUrlResolver::new(|crate_name, path| {
+            Some(format!(
+                "https://example.com/{}/{}",
+                crate_name,
+                path.join("/")
+            ))
+        });
+        assert_eq!(
+            result
+                .resolve_type_with_urls("serde_json::value::Value", &resolver)
+                .and_then(|r| r.url),
+            Some("https://example.com/serde_json/value/Value".to_string())
+        );
+    }
+
+    #[test]
+    fn test_link_external_types() {
+        let analyze = |options: AnalysisOptions| {
+            let mut result = new_result();
+            let (module, items) = Module::parse(
+                None,
+                &["my_crate"],
+                r#"
+pub struct Socket;
+pub struct Wrapper {
+    pub value: serde_json::Value,
+    pub stream: tokio::net::TcpStream,
+    pub maybe: Option<serde_json::Value>,
+    pub map: std::collections::HashMap<u8, Socket>,
+    pub socket: &'static Socket,
+}
+"#,
+            )
+            .unwrap();
+            result.modules.push(module);
+            result.extend_items(items);
+            if let Some(resolver) = options.external_doc_resolver() {
+                result.link_external_types(&resolver);
+            }
+            result.structs[1]
+                .fields
+                .iter()
+                .map(|f| f.type_.clone())
+                .collect::<Vec<_>>()
+        };
+
+        assert!(AnalysisOptions::default().external_doc_resolver().is_none());
+        let options = AnalysisOptions::default()
+            .with_external_docs("serde_json", "https://docs.rs/serde_json/1.0.0/serde_json");
+        assert_yaml_snapshot!(analyze(options.clone()), @r###"
+        ---
+        - - ExternalPath:
+              path: "serde_json::Value"
+              url: "https://docs.rs/serde_json/1.0.0/serde_json/?search=Value"
+        - - Path: "tokio::net::TcpStream"
+        - - Path: "Option<serde_json::Value>"
+        - - Path: "std::collections::HashMap<u8, Socket>"
+        - - String: "&'static "
+          - Path: Socket
+        "###);
+        // crates that are not mapped are linked to docs.rs
+        let options = AnalysisOptions {
+            link_docs_rs: true,
+            ..options
+        };
+        assert_yaml_snapshot!(analyze(options), @r###"
+        ---
+        - - ExternalPath:
+              path: "serde_json::Value"
+              url: "https://docs.rs/serde_json/1.0.0/serde_json/?search=Value"
+        - - ExternalPath:
+              path: "tokio::net::TcpStream"
+              url: "https://docs.rs/tokio/latest/tokio/?search=net::TcpStream"
+        - - Path: "Option<serde_json::Value>"
+        - - ExternalPath:
+              path: "std::collections::HashMap<u8, Socket>"
+              url: "https://doc.rust-lang.org/std/?search=collections::HashMap"
+        - - String: "&'static "
+          - Path: Socket
+        "###);
+    }
+
+    #[test]
+    fn test_module_tree() {
+        let mut result = new_result();
+        for (path, content) in [
+            (vec!["my_crate"], "pub mod a; pub fn root_fn() {}"),
+            (
+                vec!["my_crate", "a"],
+                "pub mod b; pub struct A; pub enum E {}",
+            ),
+            (vec!["my_crate", "a", "b"], "pub trait T {}"),
+        ] {
+            let (module, items) = Module::parse(None, &path, content).unwrap();
+            result.modules.push(module);
+            result.extend_items(items);
+        }
+        assert_yaml_snapshot!(result.module_tree(), @r###"
+        ---
+        path:
+          - my_crate
+        children:
+          - path:
+              - my_crate
+              - a
+            children:
+              - path:
+                  - my_crate
+                  - a
+                  - b
+                children: []
+                items:
+                  - "my_crate::a::b::T"
+            items:
+              - "my_crate::a::A"
+              - "my_crate::a::E"
+        items:
+          - "my_crate::root_fn"
+        "###);
+    }
+
+    #[test]
+    fn test_module_tree_collapse_chains() {
+        let mut result = new_result();
+        for (path, content) in [
+            (vec!["my_crate"], "pub mod a; pub mod single;"),
+            (vec!["my_crate", "a"], "pub mod b;"),
+            (vec!["my_crate", "a", "b"], "pub mod c;"),
+            (vec!["my_crate", "a", "b", "c"], "pub mod d; pub mod e;"),
+            (vec!["my_crate", "a", "b", "c", "d"], "pub struct D;"),
+            (vec!["my_crate", "a", "b", "c", "e"], "pub mod f;"),
+            (vec!["my_crate", "a", "b", "c", "e", "f"], "pub fn f() {}"),
+            (vec!["my_crate", "single"], ""),
+        ] {
+            let (module, items) = Module::parse(None, &path, content).unwrap();
+            result.modules.push(module);
+            result.extend_items(items);
+        }
+        assert_eq!(result.module_tree_with(false), result.module_tree());
+        let label = |node: &ModuleNode| {
+            let mut label = node.path.join("::");
+            if let Some(outermost) = node.collapsed.first() {
+                label.push_str(&format!(" (from {})", outermost.join("::")));
+            }
+            label
+        };
+        let mut labels = vec![];
+        let mut stack = vec![(0, result.module_tree_with(true))];
+        while let Some((depth, node)) = stack.pop() {
+            labels.push(format!("{}{}", "  ".repeat(depth), label(&node)));
+            stack.extend(node.children.into_iter().rev().map(|c| (depth + 1, c)));
+        }
+        assert_eq!(
+            labels,
+            vec![
+                "my_crate",
+                "  my_crate::a::b::c (from my_crate::a)",
+                "    my_crate::a::b::c::d",
+                "    my_crate::a::b::c::e::f (from my_crate::a::b::c::e)",
+                "  my_crate::single",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_by_module() {
+        let mut result = new_result();
+        for (path, content) in [
+            (
+                vec!["my_crate"],
+                "pub mod a; pub mod empty; pub fn root_fn() {}",
+            ),
+            (
+                vec!["my_crate", "a"],
+                "pub struct A; pub enum E {} pub trait T {} pub fn a_fn() {}",
+            ),
+            (vec!["my_crate", "empty"], ""),
+        ] {
+            let (module, items) = Module::parse(None, &path, content).unwrap();
+            result.modules.push(module);
+            result.extend_items(items);
+        }
+        let names = result
+            .by_module()
+            .into_iter()
+            .map(|(path, contents)| {
+                let items = contents
+                    .structs
+                    .iter()
+                    .map(|s| s.name.clone())
+                    .chain(contents.enums.iter().map(|e| e.name.clone()))
+                    .chain(contents.functions.iter().map(|f| f.name.clone()))
+                    .chain(contents.traits.iter().map(|t| t.name.clone()))
+                    .collect::<Vec<_>>();
+                (path.join("::"), items)
+            })
+            .collect::<Vec<_>>();
+        assert_yaml_snapshot!(names, @r###"
+        ---
+        - - my_crate
+          - - root_fn
+        - - "my_crate::a"
+          - - A
+            - E
+            - a_fn
+            - T
+        - - "my_crate::empty"
+          - []
+        "###);
+    }
+
+    #[test]
+    fn test_impls_for() {
+        let mut result = new_result();
+        for (path, content) in [
+            (
+                vec!["my_crate"],
+                "pub mod a; pub mod b; pub struct Foo; impl Foo { pub fn root() {} }",
+            ),
+            (
+                vec!["my_crate", "a"],
+                "pub struct Foo; impl Foo { pub fn a() {} } impl Clone for Foo {}",
+            ),
+            (
+                vec!["my_crate", "b"],
+                "use crate::a::Foo; impl std::fmt::Debug for Foo {} impl Foo { pub fn b() {} }",
+            ),
+        ] {
+            let (module, items) = Module::parse(None, &path, content).unwrap();
+            result.modules.push(module);
+            result.extend_items(items);
+        }
+        let impls = result
+            .impls_for("my_crate::a::Foo")
+            .iter()
+            .map(|i| {
+                (
+                    i.parent.join("::"),
+                    i.trait_
+                        .as_ref()
+                        .map(|t| t.iter().map(|s| s.content()).collect::<String>()),
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_yaml_snapshot!(impls, @r###"
+        ---
+        - - "my_crate::a"
+          - ~
+        - - "my_crate::b"
+          - ~
+        - - "my_crate::a"
+          - Clone
+        - - "my_crate::b"
+          - "std::fmt::Debug"
+        "###);
+    }
+
+    #[test]
+    fn test_parent_module() {
+        let mut result = new_result();
+        for (path, content) in [
+            (vec!["my_crate"], "pub mod a; pub struct Root;"),
+            (vec!["my_crate", "a"], "pub mod b; pub enum E { V }"),
+            (
+                vec!["my_crate", "a", "b"],
+                "pub struct S; impl S { pub fn new() {} }",
+            ),
+        ] {
+            let (module, items) = Module::parse(None, &path, content).unwrap();
+            result.modules.push(module);
+            result.extend_items(items);
+        }
+        let parent = |path: &str| result.parent_module(path).map(|m| m.path_str());
+        assert_eq!(parent("my_crate::Root").as_deref(), Some("my_crate"));
+        assert_eq!(parent("my_crate::a").as_deref(), Some("my_crate"));
+        assert_eq!(parent("my_crate::a::E").as_deref(), Some("my_crate::a"));
+        assert_eq!(
+            parent("my_crate::a::b::S").as_deref(),
+            Some("my_crate::a::b")
+        );
+        assert_eq!(parent("my_crate::a::b::S::new"), None);
+        assert_eq!(parent("my_crate"), None);
+    }
+
+    #[test]
+    fn test_implementors() {
+        let mut result = new_result();
+        for (path, content) in [
+            (
+                vec!["my_crate"],
+                r#"
+                pub mod a;
+                pub trait Encode {}
+                pub struct MyType<T, const N: usize>([T; N]);
+                impl Encode for MyType<u8, 4> {}
+                impl<T: Encode> Encode for MyType<T, 32> {}
+                impl Encode for [u8; 32] {}
+                impl Encode for (u8, i16, MyType<u8, 4>) {}
+                impl Encode for Vec<MyType<u8, 4>> {}
+                impl Encode for Vec<Box<dyn Encode>> {}
+                impl !Send for MyType<(), 0> {}
+                "#,
+            ),
+            (
+                vec!["my_crate", "a"],
+                r#"
+                use serde::Serialize;
+                use crate::Encode as Enc;
+                impl Serialize for Vec<crate::MyType<u8, 4>> {}
+                impl Enc for &'static str {}
+                "#,
+            ),
+        ] {
+            let (module, items) = Module::parse(None, &path, content).unwrap();
+            result.modules.push(module);
+            result.extend_items(items);
+        }
+        let implementors = |trait_path: &str| {
+            result
+                .implementors(trait_path)
+                .iter()
+                .map(|i| {
+                    (
+                        i.self_type.iter().map(|s| s.content()).collect::<String>(),
+                        i.type_path.clone(),
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_yaml_snapshot!(implementors("my_crate::Encode"), @r###"
+        ---
+        - - "MyType<u8, 4>"
+          - "my_crate::MyType"
+        - - "MyType<T, 32>"
+          - "my_crate::MyType"
+        - - "[u8; 32]"
+          - ~
+        - - "(u8, i16, MyType<u8, 4>)"
+          - ~
+        - - "Vec<MyType<u8, 4>>"
+          - ~
+        - - Vec<Box<dyn Encode>>
+          - ~
+        - - "&'static str"
+          - ~
+        "###);
+        assert_yaml_snapshot!(implementors("serde::Serialize"), @r###"
+        ---
+        - - "Vec<crate::MyType<u8, 4>>"
+          - ~
+        "###);
+        assert!(implementors("my_crate::Send").is_empty());
+    }
+
+    #[test]
+    fn test_implemented_methods() {
+        let mut result = new_result();
+        for (path, content) in [
+            (
+                vec!["my_crate"],
+                r#"
+                pub mod a;
+                pub trait Shape {
+                    fn area(&self) -> f64;
+                    fn name(&self) -> &str { "shape" }
+                    fn scale(&mut self, factor: f64) {}
+                }
+                pub struct Square;
+                impl Shape for Square {
+                    fn area(&self) -> f64 { 1.0 }
+                    fn scale(&mut self, factor: f64) {}
+                }
+                pub struct Circle;
+                "#,
+            ),
+            (
+                vec!["my_crate", "a"],
+                "use crate::Shape; impl Shape for crate::Circle { fn area(&self) -> f64 { 3.14 } }",
+            ),
+        ] {
+            let (module, items) = Module::parse(None, &path, content).unwrap();
+            result.modules.push(module);
+            result.extend_items(items);
+        }
+        let methods = |type_path: &str| {
+            result
+                .implemented_methods(type_path, "my_crate::Shape")
+                .iter()
+                .map(|m| (m.function.name.clone(), m.required, m.overridden))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            methods("my_crate::Square"),
+            vec![
+                ("area".to_string(), true, true),
+                ("name".to_string(), false, false),
+                ("scale".to_string(), false, true),
+            ]
+        );
+        assert_eq!(
+            methods("my_crate::Circle"),
+            vec![
+                ("area".to_string(), true, true),
+                ("name".to_string(), false, false),
+                ("scale".to_string(), false, false),
+            ]
+        );
+        assert!(methods("my_crate::Shape").is_empty());
+    }
+
+    #[test]
+    fn test_inherent_functions() {
+        let mut result = new_result();
+        let (module, items) = Module::parse(
+            None,
+            &["my_crate"],
+            r#"
+            pub struct Buffer;
+            impl Buffer {
+                pub fn new() -> Self {}
+                pub fn len(&self) -> usize {}
+                pub fn poll(self: Pin<&mut Self>) {}
+            }
+            impl Buffer {
+                pub fn with_capacity(capacity: usize) -> Self {}
+            }
+            impl Default for Buffer { fn default() -> Self {} }
+            impl Clone for Buffer { fn clone(&self) -> Self {} }
+            pub fn free() {}
+            "#,
+        )
+        .unwrap();
+        result.modules.push(module);
+        result.extend_items(items);
+
+        let names =
+            |functions: &[&Function]| functions.iter().map(|f| f.name.clone()).collect::<Vec<_>>();
+        let functions = result.inherent_functions("my_crate::Buffer");
+        assert_eq!(
+            names(&functions.associated_functions),
+            vec!["new", "with_capacity"]
+        );
+        assert_eq!(names(&functions.methods), vec!["len", "poll"]);
+
+        let kinds = result
+            .functions
+            .iter()
+            .chain(result.impls.iter().flat_map(|i| &i.functions))
+            .map(|f| (f.name.clone(), f.kind()))
+            .collect::<Vec<_>>();
+        assert_yaml_snapshot!(kinds, @r###"
+        ---
+        - - free
+          - free
+        - - new
+          - associated_function
+        - - len
+          - method
+        - - poll
+          - method
+        - - with_capacity
+          - associated_function
+        - - default
+          - associated_function
+        - - clone
+          - method
+        "###);
+    }
+
+    #[test]
+    fn test_deref_methods() {
+        let mut result = new_result();
+        let (module, items) = Module::parse(
+            None,
+            &["my_crate"],
+            r#"
+            pub struct Inner;
+            impl Inner { pub fn inner_method(&self) {} }
+            impl Clone for Inner { fn clone(&self) -> Self {} }
+            pub struct Wrapper;
+            impl std::ops::Deref for Wrapper { type Target = Inner; }
+            pub struct External;
+            impl Deref for External { type Target = str; }
+            "#,
+        )
+        .unwrap();
+        result.modules.push(module);
+        result.extend_items(items);
+        result.resolve_deref_targets();
+
+        let targets = result
+            .structs
+            .iter()
+            .map(|s| (s.name.as_str(), s.deref_target.clone()))
+            .collect::<Vec<_>>();
+        assert_yaml_snapshot!(targets, @r###"
+        ---
+        - - Inner
+          - ~
+        - - Wrapper
+          - - Path: Inner
+        - - External
+          - - Path: str
+        "###);
+        let methods = result
+            .deref_methods("my_crate::Wrapper")
+            .iter()
+            .map(|f| f.path_str())
+            .collect::<Vec<_>>();
+        assert_eq!(methods, vec!["my_crate::Inner::inner_method"]);
+        assert!(result.deref_methods("my_crate::External").is_empty());
+    }
+
+    #[test]
+    fn test_referenced_crates() {
+        let mut result = new_result();
+        for (path, content) in [
+            (
+                vec!["my_crate"],
+                r#"
+                extern crate alloc;
+                use std::collections::HashMap;
+                use inner::Local;
+                use crate::inner::Other;
+                pub mod inner;
+                pub struct Wrapper(pub HashMap<String, serde_json::Value>);
+                pub fn convert<T: Into<u8>>(value: T::Output) -> ::anyhow::Result<inner::Local> {}
+                pub fn read<T>(bytes: <bytes::Bytes as IntoIterator>::Item) -> <T as tokio::io::AsyncRead>::Output {}
+                impl serde::Serialize for Wrapper {}
+                "#,
+            ),
+            (
+                vec!["my_crate", "inner"],
+                r#"
+                use super::Wrapper;
+                pub struct Local(pub Option<regex::Regex>);
+                "#,
+            ),
+        ] {
+            let (module, items) = Module::parse(None, &path, content).unwrap();
+            result.modules.push(module);
+            result.extend_items(items);
+        }
+        assert_eq!(
+            result.referenced_crates().into_iter().collect::<Vec<_>>(),
+            vec![
+                "alloc",
+                "anyhow",
+                "bytes",
+                "regex",
+                "serde",
+                "serde_json",
+                "std",
+                "tokio"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_supertrait_methods() {
+        let mut result = new_result();
+        for (path, content) in [
+            (
+                vec!["my_crate"],
+                r#"
+                pub mod base;
+                pub trait Foo: base::Bar + Baz + std::fmt::Debug {
+                    fn foo(&self);
+                }
+                pub trait Baz: base::Bar {
+                    fn baz(&self);
+                }
+                "#,
+            ),
+            (
+                vec!["my_crate", "base"],
+                "pub trait Bar: Clone { fn bar(&self); }",
+            ),
+        ] {
+            let (module, items) = Module::parse(None, &path, content).unwrap();
+            result.modules.push(module);
+            result.extend_items(items);
+        }
+        let inherited = result
+            .supertrait_methods("my_crate::Foo")
+            .into_iter()
+            .map(|i| {
+                let names = i.functions.iter().map(|f| f.name()).collect::<Vec<_>>();
+                format!("{} {} {:?}", i.trait_, i.local, names)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            inherited,
+            vec![
+                "my_crate::base::Bar true [\"bar\"]",
+                "my_crate::Baz true [\"baz\"]",
+                "std::fmt::Debug false []",
+                "Clone false []"
+            ]
+        );
+        assert!(result.supertrait_methods("my_crate::Missing").is_empty());
+    }
+
+    #[test]
+    fn test_impls_for_blanket() {
+        let mut result = new_result();
+        let (module, items) = Module::parse(
+            None,
+            &["my_crate"],
+            r#"
+            pub struct Foo;
+            pub struct Wrapper<T>(T);
+            pub trait Describe {}
+            pub trait Named {}
+            impl Clone for Foo {}
+            impl<T: std::fmt::Debug> Describe for T {}
+            impl<'a, T: Describe + ?Sized> Named for &'a T {}
+            impl<T> From<T> for Wrapper<T> {}
+            "#,
+        )
+        .unwrap();
+        result.modules.push(module);
+        result.extend_items(items);
+        let impls = |result: &AnalysisResult, type_path: &str, include_blanket: bool| {
+            result
+                .impls_for_with(type_path, include_blanket)
+                .iter()
+                .map(|i| {
+                    let self_type = i.self_type.iter().map(|s| s.content()).collect::<String>();
+                    let trait_ = i
+                        .trait_
+                        .iter()
+                        .flatten()
+                        .map(|s| s.content())
+                        .collect::<String>();
+                    format!("{} for {}", trait_, self_type)
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            impls(&result, "my_crate::Foo", false),
+            vec!["Clone for Foo"]
+        );
+        assert_eq!(
+            result
+                .implementors("my_crate::Describe")
+                .iter()
+                .map(|i| i.self_type.iter().map(|s| s.content()).collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["T"]
+        );
+
+        assert_eq!(
+            impls(&result, "my_crate::Foo", true),
+            vec!["Clone for Foo", "Describe for T", "Named for &'a T"]
+        );
+        assert_eq!(
+            impls(&result, "my_crate::Wrapper", true),
+            vec![
+                "From<T> for Wrapper<T>",
+                "Describe for T",
+                "Named for &'a T"
+            ]
+        );
+        assert!(impls(&result, "my_crate::Describe", true).is_empty());
+    }
+}
@@ -0,0 +1,188 @@
+//! Detect recursive type definitions
+use std::collections::{BTreeMap, BTreeSet};
+
+use syn::visit::Visit;
+
+use crate::data_model::{Generics, ItemKind, TypeSegment, TypeSignature};
+
+use super::crate_::AnalysisResult;
+
+impl AnalysisResult {
+    /// Mark the structs and enums whose fields reference the type itself,
+    /// directly or through the fields of other local structs and enums, or the targets of local type aliases,
+    /// e.g. ``struct Node { children: Vec<Node> }`` or ``enum Expr { Neg(Box<Expr>) }``.
+    ///
+    /// Paths in field types are resolved relative to the module the type is declared in,
+    /// or else through its ``use`` declarations, or as the unique suffix of a fully qualified name.
+    /// ``Self`` refers to the type, generic parameters are not resolved,
+    /// and ``PhantomData<...>`` marker fields are ignored.
+    pub fn resolve_recursive_types(&mut self) {
+        let kinds = self.item_kinds();
+        let mut references: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for struct_ in &self.structs {
+            let types = struct_
+                .fields
+                .iter()
+                .filter(|f| !f.is_phantom())
+                .map(|f| &f.type_);
+            references.insert(
+                struct_.path_str(),
+                self.referenced_types(&kinds, &struct_.path, &struct_.generics, types),
+            );
+        }
+        for enum_ in &self.enums {
+            let types = enum_
+                .variants
+                .iter()
+                .flat_map(|v| &v.fields)
+                .filter(|f| !f.is_phantom())
+                .map(|f| &f.type_);
+            references.insert(
+                enum_.path_str(),
+                self.referenced_types(&kinds, &enum_.path, &enum_.generics, types),
+            );
+        }
+        for alias in &self.type_aliases {
+            references.insert(
+                alias.path_str(),
+                self.referenced_types(&kinds, &alias.path, &alias.generics, Some(&alias.target)),
+            );
+        }
+
+        for struct_ in self.structs.iter_mut() {
+            struct_.recursive = reaches_itself(&references, &struct_.path_str());
+        }
+        for enum_ in self.enums.iter_mut() {
+            enum_.recursive = reaches_itself(&references, &enum_.path_str());
+        }
+    }
+
+    /// The fully qualified names of the local structs, enums and type aliases named in the given types
+    /// of the item with the given path
+    fn referenced_types<'a>(
+        &self,
+        kinds: &BTreeMap<String, ItemKind>,
+        path: &[String],
+        generics: &Generics,
+        types: impl IntoIterator<Item = &'a TypeSignature>,
+    ) -> BTreeSet<String> {
+        let module = &path[..path.len().saturating_sub(1)];
+        let params = generics.param_names();
+        let mut paths = TypePaths(vec![]);
+        for segment in types.into_iter().flatten() {
+            if let TypeSegment::Path(type_path) = segment {
+                if let Ok(ty) = syn::parse_str::<syn::Type>(type_path) {
+                    paths.visit_type(&ty);
+                }
+            }
+        }
+        paths
+            .0
+            .into_iter()
+            .filter(|name| !params.contains(name))
+            .filter_map(|name| match name.as_str() {
+                "Self" => Some(path.join("::")),
+                _ => self.resolve_in_module(kinds, module, &name),
+            })
+            .filter(|resolved| {
+                matches!(
+                    kinds.get(resolved),
+                    Some(ItemKind::Struct | ItemKind::Enum | ItemKind::TypeAlias)
+                )
+            })
+            .collect()
+    }
+}
+
+/// Whether the type is reachable from the types it references
+fn reaches_itself(references: &BTreeMap<String, BTreeSet<String>>, start: &String) -> bool {
+    let mut visited = BTreeSet::new();
+    let mut stack: Vec<&String> = references.get(start).into_iter().flatten().collect();
+    while let Some(path) = stack.pop() {
+        if path == start {
+            return true;
+        }
+        if visited.insert(path) {
+            stack.extend(references.get(path).into_iter().flatten());
+        }
+    }
+    false
+}
+
+/// Collects the paths named in a type, including in its generic arguments,
+/// e.g. ``Option``, ``Box`` and ``Node`` for ``Option<Box<Node>>``
+struct TypePaths(Vec<String>);
+
+impl<'ast> Visit<'ast> for TypePaths {
+    fn visit_type_path(&mut self, ty: &'ast syn::TypePath) {
+        if ty.qself.is_none() {
+            let segments = ty.path.segments.iter().map(|s| s.ident.to_string());
+            self.0.push(segments.collect::<Vec<_>>().join("::"));
+        }
+        syn::visit::visit_type_path(self, ty);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyze::module::ModuleItems;
+    use crate::data_model::{Crate, Module};
+
+    #[test]
+    fn test_resolve_recursive_types() {
+        let mut result = AnalysisResult::new(Crate {
+            name: "my_crate".to_string(),
+            version: "0.1.0".to_string(),
+            ..Default::default()
+        });
+        let modules: Vec<(Vec<&str>, &str)> = vec![
+            (
+                vec!["my_crate"],
+                r#"
+pub mod ast;
+pub struct Node<T> { pub value: T, pub next: Option<Box<Node<T>>> }
+pub struct List(Vec<Self>);
+pub struct Leaf { pub value: u8, pub ids: Vec<T> }
+pub struct Marker<T> { _marker: std::marker::PhantomData<Marker<T>> }
+"#,
+            ),
+            (
+                vec!["my_crate", "ast"],
+                r#"
+use super::Leaf;
+pub enum Expr { Literal(Leaf), Call(Call) }
+pub struct Call { pub callee: Callee, pub args: Args }
+pub type Args = Vec<Expr>;
+pub type Callee = Box<crate::Leaf>;
+pub struct Wrapper { pub leaf: Leaf, pub call: Option<Call> }
+"#,
+            ),
+        ];
+        for (path, content) in modules {
+            let (module, items): (Module, ModuleItems) =
+                Module::parse(None, &path, content).unwrap();
+            result.modules.push(module);
+            result.extend_items(items);
+        }
+        result.resolve_recursive_types();
+        let recursive = result
+            .structs
+            .iter()
+            .map(|s| (s.path_str(), s.recursive))
+            .chain(result.enums.iter().map(|e| (e.path_str(), e.recursive)))
+            .collect::<BTreeMap<_, _>>();
+        assert_eq!(
+            recursive,
+            BTreeMap::from([
+                ("my_crate::Leaf".to_string(), false),
+                ("my_crate::List".to_string(), true),
+                ("my_crate::Marker".to_string(), false),
+                ("my_crate::Node".to_string(), true),
+                ("my_crate::ast::Call".to_string(), true),
+                ("my_crate::ast::Expr".to_string(), true),
+                ("my_crate::ast::Wrapper".to_string(), false),
+            ])
+        );
+    }
+}
@@ -1,15 +1,24 @@
 //! Analyze structs
 use syn::{ItemStruct, Visibility};
 
-use crate::data_model::{Field, Struct};
+use crate::data_model::{Field, Struct, VariantKind};
 
-use super::{docstring_from_attrs, type_::convert_type};
+use super::{
+    anchor_id, attributes_from_attrs,
+    cfg::cfg_from_attrs,
+    doc_aliases_from_attrs, docstring_from_attrs, serde_from_attrs, stability_from_attrs,
+    type_::{convert_generics, convert_type},
+};
 
 impl Struct {
     /// Fully qualified name of the variant
     pub fn path_str(&self) -> String {
         self.path.join("::")
     }
+    /// A stable, URL-safe identifier for the struct, e.g. for anchor links
+    pub fn anchor_id(&self) -> String {
+        anchor_id("struct", &self.path)
+    }
     /// Extract the relevant information from the AST
     pub fn parse(parent: &[&str], ast: &ItemStruct) -> Self {
         let name = ast.ident.to_string();
@@ -20,20 +29,56 @@ impl Struct {
             .collect::<Vec<&str>>();
         let docstring = docstring_from_attrs(&ast.attrs);
         let mut struct_ = Self {
+            name: name.clone(),
             path: path.iter().map(|s| s.to_string()).collect(),
+            file: None,
             docstring,
+            doc_aliases: doc_aliases_from_attrs(&ast.attrs),
+            attributes: attributes_from_attrs(&ast.attrs),
+            stability: stability_from_attrs(&ast.attrs),
+            generics: convert_generics(&ast.generics),
+            fields_kind: fields_kind(&ast.fields),
             fields: vec![],
+            deref_target: None,
+            recursive: false,
+            effectively_public: matches!(ast.vis, Visibility::Public(_)),
+            cfg_info: cfg_from_attrs(&ast.attrs).map(|p| p.to_string()),
         };
         for (i, field) in ast.fields.iter().enumerate() {
-            if let Visibility::Public(_) = field.vis {
-                struct_.fields.push(Field::parse(&path, i, field));
-            }
+            struct_.fields.push(Field::parse(&path, i, field));
         }
         struct_
     }
 }
 
+/// Whether the fields of a struct or enum variant are absent, positional or named
+pub(super) fn fields_kind(fields: &syn::Fields) -> VariantKind {
+    match fields {
+        syn::Fields::Unit => VariantKind::Unit,
+        syn::Fields::Unnamed(_) => VariantKind::Tuple,
+        syn::Fields::Named(_) => VariantKind::Struct,
+    }
+}
+
 impl Field {
+    /// A stable, URL-safe identifier for the field, e.g. for anchor links
+    pub fn anchor_id(&self) -> String {
+        anchor_id("structfield", &self.path)
+    }
+    /// Whether the field is a ``PhantomData<...>`` marker, e.g. ``_marker: PhantomData<&'a T>``,
+    /// which holds no data, but determines the variance and auto traits of the type
+    pub fn is_phantom(&self) -> bool {
+        match &self.type_[..] {
+            [segment] => segment
+                .path()
+                .unwrap_or_default()
+                .split('<')
+                .next()
+                .and_then(|path| path.rsplit("::").next())
+                .is_some_and(|name| name.trim() == "PhantomData"),
+            _ => false,
+        }
+    }
     /// Extract the relevant information from the AST
     pub fn parse(parent: &[&str], position: usize, ast: &syn::Field) -> Self {
         let name = ast
@@ -49,9 +94,13 @@ impl Field {
         let docstring = docstring_from_attrs(&ast.attrs);
         let type_ = convert_type(&ast.ty);
         Self {
+            name: name.clone(),
             path: path.iter().map(|s| s.to_string()).collect(),
             docstring,
             type_,
+            public: matches!(ast.vis, Visibility::Public(_)),
+            cfg: cfg_from_attrs(&ast.attrs).map(|p| p.to_string()),
+            serde: serde_from_attrs(&ast.attrs),
         }
     }
 }
@@ -67,16 +116,29 @@ mod tests {
         let ast: ItemStruct = parse_quote! {
             /// Multi-line
             /// docstring
+            #[doc(alias = "Alias")]
             pub struct MyStruct;
         };
         let struct_ = Struct::parse(&["crate"], &ast);
         assert_yaml_snapshot!(struct_, @r###"
         ---
+        kind: struct
+        name: MyStruct
         path:
           - crate
           - MyStruct
         docstring: "Multi-line\ndocstring"
+        doc_aliases:
+          - Alias
+        attributes: []
+        generics:
+          params: []
+          where_predicates: []
+        fields_kind: unit
         fields: []
+        deref_target: ~
+        recursive: false
+        effectively_public: true
         "###);
     }
 
@@ -95,12 +157,21 @@ mod tests {
         let struct_ = Struct::parse(&["crate"], &ast);
         assert_yaml_snapshot!(struct_, @r###"
         ---
+        kind: struct
+        name: MyStruct
         path:
           - crate
           - MyStruct
         docstring: "Multi-line\ndocstring"
+        doc_aliases: []
+        attributes: []
+        generics:
+          params: []
+          where_predicates: []
+        fields_kind: struct
         fields:
-          - path:
+          - name: my_field
+            path:
               - crate
               - MyStruct
               - my_field
@@ -109,6 +180,111 @@ mod tests {
               - String: "["
               - Path: T
               - String: "; 1]"
+            public: true
+          - name: other
+            path:
+              - crate
+              - MyStruct
+              - other
+            docstring: a non-public field
+            type_:
+              - Path: String
+            public: false
+        deref_target: ~
+        recursive: false
+        effectively_public: true
+        "###);
+    }
+
+    #[test]
+    fn test_parse_struct_const_generics() {
+        let ast: ItemStruct = parse_quote! {
+            pub struct Matrix<const R: usize, const C: usize = { 2 * 2 }> {
+                pub data: [[f64; C]; R],
+                pub transposed: Box<Matrix<C, R>>,
+                pub flattened: Matrix<1, { R * C }>,
+            }
+        };
+        let struct_ = Struct::parse(&["crate"], &ast);
+        assert_eq!(
+            struct_.generics.params,
+            vec!["const R: usize", "const C: usize = { 2 * 2 }"]
+        );
+        assert_yaml_snapshot!(struct_.fields.iter().map(|f| &f.type_).collect::<Vec<_>>(), @r###"
+        ---
+        - - String: "[["
+          - Path: f64
+          - String: "; C]; R]"
+        - - Path: "Box<Matrix<C, R>>"
+        - - Path: "Matrix<1, { R * C }>"
         "###);
     }
+
+    #[test]
+    fn test_parse_struct_fields_kind() {
+        let kinds = [
+            parse_quote! { pub struct Marker; },
+            parse_quote! { pub struct Pair(pub u32, String); },
+            parse_quote! { pub struct Point { pub x: i32 } },
+            parse_quote! { pub struct Empty {} },
+        ]
+        .map(|ast: ItemStruct| Struct::parse(&["crate"], &ast).fields_kind);
+        assert_eq!(
+            kinds,
+            [
+                VariantKind::Unit,
+                VariantKind::Tuple,
+                VariantKind::Struct,
+                VariantKind::Struct
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_struct_lifetime_bounds() {
+        let ast: ItemStruct = parse_quote! {
+            pub struct Borrowed<'a, 'b: 'a, T: 'a + ?Sized>
+            where
+                'a: 'b,
+                T: 'b + Clone,
+                for<'c> &'c T: Send,
+            {
+                pub value: &'a &'b T,
+            }
+        };
+        let struct_ = Struct::parse(&["crate"], &ast);
+        assert_yaml_snapshot!(struct_.generics, @r###"
+        ---
+        params:
+          - "'a"
+          - "'b: 'a"
+          - "T: 'a + ?Sized"
+        where_predicates:
+          - "'a: 'b"
+          - "T: 'b + Clone"
+          - "for<'c> &'c T: Send"
+        "###);
+    }
+
+    #[test]
+    fn test_field_is_phantom() {
+        let ast: ItemStruct = parse_quote! {
+            pub struct Handle<'a, T, U> {
+                pub id: u32,
+                _lifetime: PhantomData<&'a T>,
+                _owned: std::marker::PhantomData<fn() -> U>,
+                pub data: Vec<PhantomData<T>>,
+                pub pinned: core::marker::PhantomPinned,
+            }
+        };
+        let struct_ = Struct::parse(&["crate"], &ast);
+        assert_eq!(
+            struct_
+                .fields
+                .iter()
+                .map(|f| f.is_phantom())
+                .collect::<Vec<_>>(),
+            vec![false, true, true, false, false]
+        );
+    }
 }
@@ -0,0 +1,569 @@
+//! Analyze traits
+use proc_macro2::{TokenStream, TokenTree};
+use quote::ToTokens;
+
+use crate::data_model::{
+    Function, FunctionOwner, Trait, TraitAssocType, TypeSegment, TypeSignature,
+};
+
+use super::{
+    anchor_id, attributes_from_attrs,
+    cfg::{cfg_from_attrs, combine_cfg},
+    doc_aliases_from_attrs, docstring_from_attrs, stability_from_attrs,
+    type_::{convert_generics, convert_type, merge_strings, render_tokens},
+};
+
+impl Trait {
+    /// Fully qualified name of the trait
+    pub fn path_str(&self) -> String {
+        self.path.join("::")
+    }
+    /// A stable, URL-safe identifier for the trait, e.g. for anchor links
+    pub fn anchor_id(&self) -> String {
+        anchor_id("trait", &self.path)
+    }
+    /// The names of the generic parameters of the trait
+    /// that are referenced in its supertraits or the signatures of its methods
+    pub fn used_generic_params(&self) -> Vec<String> {
+        let signatures = self
+            .functions
+            .iter()
+            .map(|f| f.signature())
+            .collect::<Vec<_>>();
+        self.generics.referenced_in(
+            self.supertraits
+                .iter()
+                .map(String::as_str)
+                .chain(signatures.iter().flatten().map(TypeSegment::content)),
+        )
+    }
+    /// Whether ``Self: Sized`` is required, in which case the trait cannot be used as ``dyn Trait``
+    pub fn requires_sized(&self) -> bool {
+        self.self_bounds.iter().any(|bound| {
+            matches!(
+                bound.as_str(),
+                "Sized" | "std::marker::Sized" | "core::marker::Sized"
+            )
+        })
+    }
+    /// Extract the relevant information from the AST.
+    ///
+    /// The trait is considered object safe unless:
+    ///
+    /// - it requires ``Self: Sized``, or a supertrait takes ``Self`` as a generic argument
+    /// - it has an associated const, or an associated type with generic parameters
+    /// - it has a method that does not require ``Self: Sized``, and that either has no receiver,
+    ///   has type or const parameters, is ``async`` (without ``#[async_trait]``), returns ``impl Trait``,
+    ///   or uses ``Self`` in its parameters or return type, other than in paths such as ``Self::Item``
+    ///
+    /// Bounds that imply ``Sized`` through a supertrait, such as ``Clone``,
+    /// and the default ``Rhs = Self`` of traits such as ``PartialEq``, are not detected.
+    pub fn parse(parent: &[&str], ast: &syn::ItemTrait) -> Self {
+        let name = ast.ident.to_string();
+        let path: Vec<&str> = parent.iter().copied().chain(Some(name.as_str())).collect();
+        let path_str = path.join("::");
+        let cfg = cfg_from_attrs(&ast.attrs).map(|p| p.to_string());
+        let mut functions = vec![];
+        let mut provided_methods = vec![];
+        let mut assoc_types = vec![];
+        for item in &ast.items {
+            match item {
+                syn::TraitItem::Fn(fn_item) => {
+                    let mut function =
+                        Function::parse_signature(&path, &fn_item.attrs, &fn_item.sig);
+                    function.owner = FunctionOwner::Trait(path_str.clone());
+                    function.cfg_info = combine_cfg([cfg.as_deref(), function.cfg_info.as_deref()]);
+                    function.name_anonymous_params();
+                    if fn_item.default.is_some() {
+                        provided_methods.push(function.name.clone());
+                    }
+                    functions.push(function);
+                }
+                syn::TraitItem::Type(type_item) => {
+                    assoc_types.push(TraitAssocType::parse(type_item))
+                }
+                _ => {}
+            }
+        }
+        let where_bounds = ast
+            .generics
+            .where_clause
+            .iter()
+            .flat_map(|clause| clause.predicates.iter())
+            .filter_map(|predicate| match predicate {
+                syn::WherePredicate::Type(predicate) if is_self(&predicate.bounded_ty) => {
+                    Some(predicate.bounds.iter())
+                }
+                _ => None,
+            })
+            .flatten();
+        let async_trait = ast.attrs.iter().any(|attr| {
+            attr.path()
+                .segments
+                .last()
+                .is_some_and(|s| s.ident == "async_trait")
+        });
+        let mut trait_ = Self {
+            name: name.clone(),
+            path: path.iter().map(|s| s.to_string()).collect(),
+            file: None,
+            docstring: docstring_from_attrs(&ast.attrs),
+            doc_aliases: doc_aliases_from_attrs(&ast.attrs),
+            attributes: attributes_from_attrs(&ast.attrs),
+            stability: stability_from_attrs(&ast.attrs),
+            generics: convert_generics(&ast.generics),
+            supertraits: ast
+                .supertraits
+                .iter()
+                .filter_map(|bound| match bound {
+                    syn::TypeParamBound::Trait(bound)
+                        if matches!(bound.modifier, syn::TraitBoundModifier::None) =>
+                    {
+                        Some(render_tokens(&bound.path))
+                    }
+                    _ => None,
+                })
+                .collect(),
+            self_bounds: ast
+                .supertraits
+                .iter()
+                .chain(where_bounds)
+                .map(render_tokens)
+                .collect(),
+            functions,
+            assoc_types,
+            provided_methods,
+            async_trait,
+            is_unsafe: ast.unsafety.is_some(),
+            sealed: false,
+            object_safe: true,
+            effectively_public: matches!(ast.vis, syn::Visibility::Public(_)),
+            cfg_info: cfg_from_attrs(&ast.attrs).map(|p| p.to_string()),
+        };
+        let supertraits_use_self = ast.supertraits.iter().any(|bound| match bound {
+            syn::TypeParamBound::Trait(bound) => mentions_self(bound.path.to_token_stream()),
+            _ => false,
+        });
+        trait_.object_safe = !trait_.requires_sized()
+            && !supertraits_use_self
+            && ast
+                .items
+                .iter()
+                .all(|item| item_object_safe(item, async_trait));
+        trait_
+    }
+}
+
+impl TraitAssocType {
+    /// Extract the relevant information from the AST
+    pub fn parse(ast: &syn::TraitItemType) -> Self {
+        Self {
+            name: ast.ident.to_string(),
+            docstring: docstring_from_attrs(&ast.attrs),
+            generics: convert_generics(&ast.generics),
+            bounds: ast.bounds.iter().map(render_tokens).collect(),
+            default: ast.default.as_ref().map(|(_, ty)| convert_type(ty)),
+        }
+    }
+    /// The declaration of the associated type, e.g. ``type Item<'a>: Clone where Self: 'a``
+    pub fn signature(&self) -> TypeSignature {
+        let mut sig: TypeSignature = vec![format!("type {}", self.name).into()];
+        if !self.generics.params.is_empty() {
+            sig.push(format!("<{}>", self.generics.params.join(", ")).into());
+        }
+        if !self.bounds.is_empty() {
+            sig.push(format!(": {}", self.bounds.join(" + ")).into());
+        }
+        if !self.generics.where_predicates.is_empty() {
+            sig.push(format!(" where {}", self.generics.where_predicates.join(", ")).into());
+        }
+        if let Some(default) = &self.default {
+            sig.push(" = ".into());
+            sig.extend(default.iter().cloned());
+        }
+        merge_strings(sig)
+    }
+}
+
+/// Whether an item of a trait allows the trait to be object safe
+fn item_object_safe(item: &syn::TraitItem, async_trait: bool) -> bool {
+    match item {
+        syn::TraitItem::Const(_) => false,
+        syn::TraitItem::Type(type_) => type_.generics.params.is_empty(),
+        syn::TraitItem::Fn(fn_item) => {
+            let sig = &fn_item.sig;
+            // methods that require ``Self: Sized`` are not callable on trait objects
+            let requires_sized = sig.generics.where_clause.iter().any(|clause| {
+                clause.predicates.iter().any(|predicate| match predicate {
+                    syn::WherePredicate::Type(predicate) => {
+                        is_self(&predicate.bounded_ty)
+                            && predicate.bounds.iter().any(|bound| match bound {
+                                syn::TypeParamBound::Trait(bound) => bound
+                                    .path
+                                    .segments
+                                    .last()
+                                    .is_some_and(|s| s.ident == "Sized"),
+                                _ => false,
+                            })
+                    }
+                    _ => false,
+                })
+            });
+            if requires_sized {
+                return true;
+            }
+            let generic = sig
+                .generics
+                .params
+                .iter()
+                .any(|param| !matches!(param, syn::GenericParam::Lifetime(_)));
+            let params_use_self = sig.inputs.iter().any(|input| match input {
+                syn::FnArg::Typed(pat) => mentions_self(pat.ty.to_token_stream()),
+                syn::FnArg::Receiver(_) => false,
+            });
+            let output_unsafe = match &sig.output {
+                syn::ReturnType::Type(_, ty) => {
+                    matches!(**ty, syn::Type::ImplTrait(_)) || mentions_self(ty.to_token_stream())
+                }
+                syn::ReturnType::Default => false,
+            };
+            sig.receiver().is_some()
+                && !generic
+                && (sig.asyncness.is_none() || async_trait)
+                && !params_use_self
+                && !output_unsafe
+        }
+        _ => true,
+    }
+}
+
+/// Whether tokens contain a standalone ``Self``, i.e. not the start of a path such as ``Self::Item``
+fn mentions_self(tokens: TokenStream) -> bool {
+    let tokens = tokens.into_iter().collect::<Vec<_>>();
+    tokens.iter().enumerate().any(|(i, token)| match token {
+        TokenTree::Ident(ident) => {
+            ident == "Self"
+                && !matches!(tokens.get(i + 1), Some(TokenTree::Punct(p)) if p.as_char() == ':')
+        }
+        TokenTree::Group(group) => mentions_self(group.stream()),
+        _ => false,
+    })
+}
+
+/// Whether a type is exactly ``Self``
+fn is_self(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(path) => path.qself.is_none() && path.path.is_ident("Self"),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_yaml_snapshot;
+
+    #[test]
+    fn test_parse_trait_supertraits() {
+        let ast: syn::ItemTrait = syn::parse_quote! {
+            pub trait MyTrait<T>: Bar<T> + std::fmt::Debug + ?Sized + 'static {}
+        };
+        let trait_ = Trait::parse(&["crate"], &ast);
+        assert_eq!(trait_.supertraits, vec!["Bar<T>", "std::fmt::Debug"]);
+    }
+
+    #[test]
+    fn test_parse_trait_self_bounds() {
+        let ast: syn::ItemTrait = syn::parse_quote! {
+            pub trait MyTrait<T>: Clone + Send + 'static
+            where
+                Self: Sized + PartialEq<T>,
+                T: Default,
+            {}
+        };
+        let trait_ = Trait::parse(&["crate"], &ast);
+        assert_eq!(
+            trait_.self_bounds,
+            vec!["Clone", "Send", "'static", "Sized", "PartialEq<T>"]
+        );
+        assert!(trait_.requires_sized());
+
+        let ast: syn::ItemTrait = syn::parse_quote! {
+            pub trait Object: std::fmt::Debug {
+                fn new() -> Self where Self: Sized;
+            }
+        };
+        let trait_ = Trait::parse(&["crate"], &ast);
+        assert_eq!(trait_.self_bounds, vec!["std::fmt::Debug"]);
+        assert!(!trait_.requires_sized());
+    }
+
+    #[test]
+    fn test_parse_trait_object_safe() {
+        let ast: syn::ItemTrait = syn::parse_quote! {
+            pub trait Shape: std::fmt::Debug {
+                type Unit;
+                fn area(&self) -> f64;
+                fn scale(&mut self, factor: f64) -> Self::Unit;
+                fn boxed(self: Box<Self>) -> Box<dyn Shape<Unit = Self::Unit>>;
+                fn visit<'a>(&'a self, visitor: &mut dyn FnMut(&'a str));
+                fn new() -> Self where Self: Sized;
+                fn map<F: Fn(f64) -> f64>(&self, f: F) -> Self where Self: Sized;
+            }
+        };
+        assert!(Trait::parse(&["crate"], &ast).object_safe);
+
+        let unsafe_traits: Vec<syn::ItemTrait> = vec![
+            syn::parse_quote! { pub trait Sized_: Sized {} },
+            syn::parse_quote! { pub trait Cmp: PartialOrd<Self> {} },
+            syn::parse_quote! { pub trait Const { const ID: u32; } },
+            syn::parse_quote! { pub trait Lending { type Item<'a>; } },
+            syn::parse_quote! { pub trait Ctor { fn new() -> u8; } },
+            syn::parse_quote! { pub trait Generic { fn get<T>(&self) -> T; } },
+            syn::parse_quote! { pub trait Duplicate { fn dup(&self) -> Self; } },
+            syn::parse_quote! { pub trait Merge { fn merge(&self, other: &Self); } },
+            syn::parse_quote! { pub trait Iter { fn iter(&self) -> impl Iterator<Item = u8>; } },
+            syn::parse_quote! { pub trait Fetch { async fn fetch(&self); } },
+        ];
+        for ast in unsafe_traits {
+            assert!(!Trait::parse(&["crate"], &ast).object_safe, "{}", ast.ident);
+        }
+    }
+
+    #[test]
+    fn test_trait_used_generic_params() {
+        let ast: syn::ItemTrait = syn::parse_quote! {
+            pub trait MyTrait<A, B, C>: Bar<A> {
+                fn method(&self, b: &B) -> Self::C;
+            }
+        };
+        let trait_ = Trait::parse(&["crate"], &ast);
+        assert_eq!(trait_.used_generic_params(), vec!["A", "B"]);
+    }
+
+    #[test]
+    fn test_parse_trait_async() {
+        let ast: syn::ItemTrait = syn::parse_quote! {
+            #[async_trait::async_trait]
+            pub trait Service {
+                async fn call(&self, request: u8) -> u8;
+                fn ready(&self) -> bool;
+            }
+        };
+        let trait_ = Trait::parse(&["crate"], &ast);
+        assert!(trait_.async_trait);
+        assert!(trait_.functions[0].is_async);
+        assert!(!trait_.functions[1].is_async);
+        assert_eq!(
+            trait_.functions[0]
+                .signature()
+                .iter()
+                .map(|s| s.content())
+                .collect::<String>(),
+            "async fn call(&self, request: u8) -> u8"
+        );
+        let ast: syn::ItemTrait = syn::parse_quote! {
+            pub trait Native {
+                async fn call(&self);
+            }
+        };
+        assert!(!Trait::parse(&["crate"], &ast).async_trait);
+    }
+
+    #[test]
+    fn test_parse_trait() {
+        let ast: syn::ItemTrait = syn::parse_quote! {
+            /// Docstring
+            pub trait MyTrait<T> {
+                /// Method docstring
+                fn method(&self) -> T;
+                fn provided() {}
+            }
+        };
+        let trait_ = Trait::parse(&["crate"], &ast);
+        assert_yaml_snapshot!(trait_, @r###"
+        ---
+        kind: trait
+        name: MyTrait
+        path:
+          - crate
+          - MyTrait
+        docstring: Docstring
+        doc_aliases: []
+        attributes: []
+        generics:
+          params:
+            - T
+          where_predicates: []
+        supertraits: []
+        self_bounds: []
+        functions:
+          - kind: function
+            name: method
+            path:
+              - crate
+              - MyTrait
+              - method
+            docstring: Method docstring
+            doc_aliases: []
+            attributes: []
+            generics:
+              params: []
+              where_predicates: []
+            receiver:
+              rendered: "&self"
+              kind: ref
+            params: []
+            output:
+              - Path: T
+            owner:
+              Trait: "crate::MyTrait"
+            variadic: false
+            is_async: false
+            is_unsafe: false
+            effectively_public: true
+          - kind: function
+            name: provided
+            path:
+              - crate
+              - MyTrait
+              - provided
+            docstring: ""
+            doc_aliases: []
+            attributes: []
+            generics:
+              params: []
+              where_predicates: []
+            receiver: ~
+            params: []
+            output: ~
+            owner:
+              Trait: "crate::MyTrait"
+            variadic: false
+            is_async: false
+            is_unsafe: false
+            effectively_public: true
+        assoc_types: []
+        provided_methods:
+          - provided
+        async_trait: false
+        is_unsafe: false
+        sealed: false
+        object_safe: false
+        effectively_public: true
+        "###);
+    }
+
+    #[test]
+    fn test_parse_trait_generic_assoc_types() {
+        let ast: syn::ItemTrait = syn::parse_quote! {
+            pub trait LendingIterator {
+                /// The items, borrowed from the iterator
+                type Item<'a>: Clone where Self: 'a;
+                type Error: std::error::Error + Send = std::io::Error;
+                type Map<K, V: Default>;
+                fn next<'a>(&'a mut self) -> Option<Self::Item<'a>>;
+            }
+        };
+        let trait_ = Trait::parse(&["crate"], &ast);
+        assert_yaml_snapshot!(trait_.assoc_types, @r###"
+        ---
+        - name: Item
+          docstring: "The items, borrowed from the iterator"
+          generics:
+            params:
+              - "'a"
+            where_predicates:
+              - "Self: 'a"
+          bounds:
+            - Clone
+          default: ~
+        - name: Error
+          docstring: ""
+          generics:
+            params: []
+            where_predicates: []
+          bounds:
+            - "std::error::Error"
+            - Send
+          default:
+            - Path: "std::io::Error"
+        - name: Map
+          docstring: ""
+          generics:
+            params:
+              - K
+              - "V: Default"
+            where_predicates: []
+          bounds: []
+          default: ~
+        "###);
+        assert_eq!(
+            trait_
+                .assoc_types
+                .iter()
+                .map(|t| t
+                    .signature()
+                    .iter()
+                    .map(|s| s.content())
+                    .collect::<String>())
+                .collect::<Vec<_>>(),
+            vec![
+                "type Item<'a>: Clone where Self: 'a",
+                "type Error: std::error::Error + Send = std::io::Error",
+                "type Map<K, V: Default>",
+            ]
+        );
+        assert!(!trait_.object_safe);
+
+        let ast: syn::ItemImpl = syn::parse_quote! {
+            impl<T> LendingIterator for Windows<T> {
+                type Item<'a> = &'a [T] where Self: 'a;
+            }
+        };
+        let impl_ = crate::data_model::Impl::parse(&["crate"], &ast);
+        assert_yaml_snapshot!(impl_.assoc_types, @r###"
+        ---
+        - name: Item
+          generics:
+            params:
+              - "'a"
+            where_predicates:
+              - "Self: 'a"
+          type_:
+            - String: "&'a ["
+            - Path: T
+            - String: "]"
+        "###);
+    }
+
+    #[test]
+    fn test_parse_trait_anonymous_params() {
+        let (_, items) = crate::data_model::Module::parse(
+            None,
+            &["crate"],
+            r#"
+pub trait Store {
+    fn get(&self, u32, Option<Vec<u8>>) -> bool;
+    fn set(&mut self, _: u8, (a, b): (u32, u32), std::string::String);
+    fn map<F>(&self, Box<dyn Fn(u8) -> u8>, f: F);
+}
+pub fn free(_: u8) {}
+"#,
+        )
+        .unwrap();
+        let params = items.traits[0]
+            .functions
+            .iter()
+            .map(|f| {
+                f.params
+                    .iter()
+                    .map(|p| p.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(params, vec!["arg0, arg1", "arg0, (a, b), arg2", "arg0, f"]);
+        assert_eq!(items.functions[0].params[0].name, "_");
+    }
+}
@@ -1,7 +1,13 @@
 //! Analyze types
-use quote::quote;
+use std::cell::Cell;
+use std::collections::{BTreeMap, BTreeSet};
 
-use crate::data_model::TypeSegment;
+use proc_macro2::{Delimiter, Spacing, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+
+use crate::data_model::{Generics, TypeForm, TypeSegment, TypeSignature};
+
+use super::ELLIPSIS;
 
 impl From<&str> for TypeSegment {
     fn from(s: &str) -> Self {
@@ -23,65 +29,571 @@ impl From<String> for TypeSegment {
     }
 }
 
+impl TypeSegment {
+    /// The text content of the segment
+    pub fn content(&self) -> &str {
+        match self {
+            TypeSegment::String(s)
+            | TypeSegment::Path(s)
+            | TypeSegment::ExternalPath { path: s, .. } => s,
+        }
+    }
+    /// The path of the segment, if it is a path, whether or not it is linked to external documentation
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            TypeSegment::String(_) => None,
+            TypeSegment::Path(path) | TypeSegment::ExternalPath { path, .. } => Some(path),
+        }
+    }
+    /// A segment of the same kind, and with the same URL, with different text,
+    /// e.g. for a truncated segment
+    pub fn with_content(&self, text: String) -> TypeSegment {
+        match self {
+            TypeSegment::String(_) => TypeSegment::String(text),
+            TypeSegment::Path(_) => TypeSegment::Path(text),
+            TypeSegment::ExternalPath { url, .. } => TypeSegment::ExternalPath {
+                path: text,
+                url: url.clone(),
+            },
+        }
+    }
+}
+
+/// The traits that are implemented automatically, and so may be added to any trait object
+const AUTO_TRAITS: &[&str] = &["Send", "Sync", "Unpin", "UnwindSafe", "RefUnwindSafe"];
+
+impl TypeForm {
+    /// Classify a rendered type, e.g. ``dyn Error + Send`` as a trait object of ``Error``
+    /// with the ``Send`` bound, or ``None`` if it is not a valid type
+    pub fn from_signature(signature: &TypeSignature) -> Option<Self> {
+        let rendered = signature.iter().map(|s| s.content()).collect::<String>();
+        syn::parse_str::<syn::Type>(&rendered)
+            .ok()
+            .map(|ty| type_form(&ty))
+    }
+}
+
+/// Classify a type as a trait object, opaque type, path or other type.
+///
+/// Parentheses around the type are ignored.
+/// The principal trait is the first trait bound that is not an auto trait, e.g. ``Send``,
+/// or else ``None``, and the remaining bounds keep their order.
+pub(super) fn type_form(ty: &syn::Type) -> TypeForm {
+    let split = |bounds: &syn::punctuated::Punctuated<syn::TypeParamBound, syn::Token![+]>| {
+        let principal = bounds.iter().position(|bound| match bound {
+            syn::TypeParamBound::Trait(bound) => {
+                matches!(bound.modifier, syn::TraitBoundModifier::None)
+                    && !bound.path.segments.last().is_some_and(|segment| {
+                        AUTO_TRAITS.contains(&segment.ident.to_string().as_str())
+                    })
+            }
+            _ => false,
+        });
+        let mut bounds = bounds.iter().map(render_tokens).collect::<Vec<_>>();
+        let trait_ = principal.map(|index| bounds.remove(index));
+        (trait_, bounds)
+    };
+    match ty {
+        syn::Type::Group(group) => type_form(&group.elem),
+        syn::Type::Paren(paren) => type_form(&paren.elem),
+        syn::Type::TraitObject(object) => {
+            let (trait_, bounds) = split(&object.bounds);
+            TypeForm::Dyn { trait_, bounds }
+        }
+        syn::Type::ImplTrait(opaque) => {
+            let (trait_, bounds) = split(&opaque.bounds);
+            TypeForm::Impl { trait_, bounds }
+        }
+        syn::Type::Path(path) => TypeForm::Path {
+            path: render_tokens(path),
+        },
+        _ => TypeForm::Other {
+            rendered: convert_type(ty).iter().map(|s| s.content()).collect(),
+        },
+    }
+}
+
+impl Generics {
+    /// The names of the generic parameters, e.g. ``'a``, ``T`` and ``N`` for ``<'a, T: Clone, const N: usize>``
+    pub fn param_names(&self) -> Vec<String> {
+        self.params
+            .iter()
+            .filter_map(|param| {
+                let param = param.strip_prefix("const ").unwrap_or(param);
+                identifiers(param).next().map(str::to_string)
+            })
+            .collect()
+    }
+    /// The bounds on each generic parameter, or other type or lifetime, from both the parameters
+    /// and the ``where`` clause, e.g. ``Clone`` and ``Debug`` on ``T`` for ``<T: Clone> ... where T: Debug``
+    pub fn bounds(&self) -> BTreeMap<String, BTreeSet<String>> {
+        let mut bounds: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+        for param in &self.params {
+            match syn::parse_str::<syn::GenericParam>(param) {
+                Ok(syn::GenericParam::Type(param)) => bounds
+                    .entry(param.ident.to_string())
+                    .or_default()
+                    .extend(param.bounds.iter().map(render_tokens)),
+                Ok(syn::GenericParam::Lifetime(param)) => bounds
+                    .entry(render_tokens(&param.lifetime))
+                    .or_default()
+                    .extend(param.bounds.iter().map(render_tokens)),
+                _ => {}
+            }
+        }
+        for predicate in &self.where_predicates {
+            match syn::parse_str::<syn::WherePredicate>(predicate) {
+                Ok(syn::WherePredicate::Type(predicate)) => {
+                    let bounded = match &predicate.lifetimes {
+                        Some(lifetimes) => format!(
+                            "{} {}",
+                            render_tokens(lifetimes),
+                            render_tokens(&predicate.bounded_ty)
+                        ),
+                        None => render_tokens(&predicate.bounded_ty),
+                    };
+                    bounds
+                        .entry(bounded)
+                        .or_default()
+                        .extend(predicate.bounds.iter().map(render_tokens))
+                }
+                Ok(syn::WherePredicate::Lifetime(predicate)) => bounds
+                    .entry(render_tokens(&predicate.lifetime))
+                    .or_default()
+                    .extend(predicate.bounds.iter().map(render_tokens)),
+                _ => {}
+            }
+        }
+        bounds.retain(|_, bounds| !bounds.is_empty());
+        bounds
+    }
+    /// The names of the generic parameters that are referenced in the given rendered texts,
+    /// in the order they are declared.
+    ///
+    /// Parameters that are declared but not referenced are only used in bounds,
+    /// or else would need to be marked with ``PhantomData``.
+    pub fn referenced_in<'a>(&self, texts: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+        let referenced: BTreeSet<&str> = texts.into_iter().flat_map(identifiers).collect();
+        self.param_names()
+            .into_iter()
+            .filter(|name| referenced.contains(name.as_str()))
+            .collect()
+    }
+}
+
+/// The identifiers and lifetimes in a rendered text that could name a generic parameter,
+/// i.e. excluding those qualified by a path or field access, such as ``Item`` in ``T::Item``
+fn identifiers(text: &str) -> impl Iterator<Item = &str> {
+    let is_ident_char = |c: char| c.is_alphanumeric() || c == '_';
+    let mut identifiers = vec![];
+    let mut start = None;
+    for (i, c) in text.char_indices().chain(Some((text.len(), ' '))) {
+        match start {
+            Some(s) if !is_ident_char(c) => {
+                let token = &text[s..i];
+                let qualified = text[..s].ends_with("::") || text[..s].ends_with('.');
+                if token != "'" && !qualified {
+                    identifiers.push(token);
+                }
+                start = (c == '\'').then_some(i);
+            }
+            None if is_ident_char(c) || c == '\'' => start = Some(i),
+            _ => {}
+        }
+    }
+    identifiers.into_iter()
+}
+
+/// Replace the identifiers and lifetimes in a rendered text that could name a generic parameter,
+/// e.g. ``Vec<u8>`` for ``Vec<T>`` with ``T`` replaced by ``u8``
+pub(super) fn substitute_identifiers(
+    text: &str,
+    substitutions: &BTreeMap<String, String>,
+) -> String {
+    let is_ident_char = |c: char| c.is_alphanumeric() || c == '_';
+    let mut output = String::new();
+    let mut start = None;
+    for (i, c) in text.char_indices().chain(Some((text.len(), ' '))) {
+        match start {
+            Some(s) if !is_ident_char(c) => {
+                let token = &text[s..i];
+                let qualified = text[..s].ends_with("::") || text[..s].ends_with('.');
+                match substitutions.get(token) {
+                    Some(value) if !qualified => output.push_str(value),
+                    _ => output.push_str(token),
+                }
+                start = (c == '\'').then_some(i);
+                if start.is_none() && i < text.len() {
+                    output.push(c);
+                }
+            }
+            None if is_ident_char(c) || c == '\'' => start = Some(i),
+            None if i < text.len() => output.push(c),
+            _ => {}
+        }
+    }
+    output
+}
+
+/// The default nesting depth beyond which types are rendered as an ellipsis when parsed,
+/// which bounds the recursion of the renderer for pathological types
+pub const DEFAULT_MAX_PARSE_DEPTH: usize = 64;
+
+thread_local! {
+    /// The nesting depth beyond which types are rendered as an ellipsis when parsed on this thread
+    static MAX_PARSE_DEPTH: Cell<usize> = const { Cell::new(DEFAULT_MAX_PARSE_DEPTH) };
+}
+
+/// Run ``parse`` with types nested more than ``max_depth`` deep rendered as an ellipsis
+pub(super) fn with_max_parse_depth<T>(max_depth: usize, parse: impl FnOnce() -> T) -> T {
+    let previous = MAX_PARSE_DEPTH.replace(max_depth);
+    let output = parse();
+    MAX_PARSE_DEPTH.set(previous);
+    output
+}
+
 /// Converts a syn type to a list of text and Paths
 pub(super) fn convert_type(ty: &syn::Type) -> Vec<TypeSegment> {
-    let mut v = convert_type_inner(ty);
-    // Merge adjacent strings
-    v = v.iter().fold(Vec::new(), |mut acc, elem| {
+    convert_type_at(ty, 0)
+}
+
+fn convert_type_at(ty: &syn::Type, depth: usize) -> Vec<TypeSegment> {
+    if depth > MAX_PARSE_DEPTH.get() {
+        return vec![ELLIPSIS.into()];
+    }
+    merge_strings(convert_type_inner(ty, depth))
+}
+
+/// The maximum nesting depth of brackets in a rendered type, e.g. ``2`` for ``Vec<Option<u8>>``
+pub(super) fn nesting_depth(text: &str) -> usize {
+    let mut depth: usize = 0;
+    let mut max_depth = 0;
+    let mut prev = None;
+    for c in text.chars() {
+        match c {
+            '<' | '(' | '[' => {
+                depth += 1;
+                max_depth = max_depth.max(depth);
+            }
+            '>' if prev == Some('-') => {}
+            '>' | ')' | ']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        prev = Some(c);
+    }
+    max_depth
+}
+
+/// Truncate the content of a type signature that is nested more than ``max_depth`` brackets deep,
+/// e.g. ``Vec<Vec<Vec<u8>>>`` becomes ``Vec<Vec<…>>`` for a depth of 1.
+///
+/// This is performed iteratively on the rendered text, so it is safe for arbitrarily nested types.
+pub fn truncate_type_depth(signature: &TypeSignature, max_depth: usize) -> TypeSignature {
+    let mut depth = 0;
+    let mut output = vec![];
+    for segment in signature {
+        let mut text = String::new();
+        let mut prev = None;
+        for c in segment.content().chars() {
+            match c {
+                '<' | '(' | '[' => {
+                    depth += 1;
+                    if depth == max_depth + 1 {
+                        text.push(c);
+                        text.push_str(ELLIPSIS);
+                    } else if depth <= max_depth {
+                        text.push(c);
+                    }
+                }
+                // the arrow of a return type is not a bracket
+                '>' if prev == Some('-') => {
+                    if depth <= max_depth {
+                        text.push(c);
+                    }
+                }
+                '>' | ')' | ']' => {
+                    if depth <= max_depth + 1 {
+                        text.push(c);
+                    }
+                    depth = depth.saturating_sub(1);
+                }
+                _ => {
+                    if depth <= max_depth {
+                        text.push(c);
+                    }
+                }
+            }
+            prev = Some(c);
+        }
+        if !text.is_empty() {
+            output.push(segment.with_content(text));
+        }
+    }
+    merge_strings(output)
+}
+
+/// Merge adjacent string segments
+pub(super) fn merge_strings(segments: Vec<TypeSegment>) -> Vec<TypeSegment> {
+    segments.into_iter().fold(Vec::new(), |mut acc, elem| {
         if let Some(TypeSegment::String(s)) = acc.last_mut() {
-            if let TypeSegment::String(next) = elem {
+            if let TypeSegment::String(next) = &elem {
                 s.push_str(next);
                 return acc;
             }
         }
-        acc.push(elem.clone());
+        acc.push(elem);
         acc
-    });
+    })
+}
+
+/// Converts syn generics to their rendered form
+pub(super) fn convert_generics(generics: &syn::Generics) -> Generics {
+    Generics {
+        params: generics.params.iter().map(render_tokens).collect(),
+        where_predicates: generics
+            .where_clause
+            .iter()
+            .flat_map(|clause| clause.predicates.iter().map(render_tokens))
+            .collect(),
+    }
+}
+
+/// Render tokens to a string, with whitespace normalised to the conventional Rust style,
+/// e.g. ``T: Iterator<Item = u8>`` rather than ``T : Iterator < Item = u8 >``
+pub(super) fn render_tokens(tokens: &impl ToTokens) -> String {
+    render_token_stream(tokens.to_token_stream(), false)
+}
+
+/// Render an expression as for ``render_tokens``, with ``<`` and ``>`` as comparison operators,
+/// e.g. ``N > 0``
+pub(super) fn render_expr(expr: &syn::Expr) -> String {
+    render_token_stream(expr.to_token_stream(), true)
+}
+
+/// Render a token stream, see ``render_tokens``.
+///
+/// In expressions, i.e. the blocks of const generic arguments such as ``{ N > 0 }``,
+/// ``<`` and ``>`` are comparison operators, other than those of a turbofish ``::<T>``.
+fn render_token_stream(tokens: TokenStream, expr: bool) -> String {
+    let atoms = token_atoms(tokens, expr);
+    let mut output = String::new();
+    let mut prev: Option<&Atom> = None;
+    for (i, atom) in atoms.iter().enumerate() {
+        if let Some(prev) = prev {
+            if needs_space(prev, atom, i.checked_sub(2).map(|j| &atoms[j])) {
+                output.push(' ');
+            }
+        }
+        output.push_str(&atom.text);
+        prev = Some(atom);
+    }
+    output
+}
+
+#[derive(Debug, PartialEq)]
+enum AtomKind {
+    Word,
+    Punct,
+    Group,
+    /// A binary operator that is always surrounded by whitespace, e.g. ``<`` in ``{ N < 4 }``
+    Operator,
+}
+
+/// A rendered unit of tokens, between which whitespace may be inserted
+#[derive(Debug)]
+struct Atom {
+    kind: AtomKind,
+    text: String,
+}
+
+/// Split a token stream into atoms, joining multi-character punctuation and lifetimes
+fn token_atoms(tokens: TokenStream, expr: bool) -> Vec<Atom> {
+    let mut atoms: Vec<Atom> = vec![];
+    let mut joint = false;
+    // the depth of the turbofish generic arguments, in expressions
+    let mut turbofish_depth = 0;
+    for token in tokens {
+        let continues = joint;
+        joint = false;
+        match token {
+            TokenTree::Punct(punct) => {
+                let ch = punct.as_char();
+                joint = punct.spacing() == Spacing::Joint;
+                match atoms.last_mut() {
+                    Some(last)
+                        if continues
+                            && matches!(last.kind, AtomKind::Punct | AtomKind::Operator)
+                            && (OPERATORS.contains(&format!("{}{}", last.text, ch).as_str())
+                                // shifts, which are only operators in expressions
+                                || (last.kind == AtomKind::Operator
+                                    && matches!((last.text.as_str(), ch), ("<", '<') | (">", '>')))) =>
+                    {
+                        last.text.push(ch);
+                    }
+                    _ => {
+                        let mut kind = AtomKind::Punct;
+                        if expr {
+                            match ch {
+                                '<' if atoms.last().is_some_and(|a| a.text == "::") => {
+                                    turbofish_depth += 1
+                                }
+                                '>' if turbofish_depth > 0 => turbofish_depth -= 1,
+                                '<' | '>' if turbofish_depth == 0 => kind = AtomKind::Operator,
+                                _ => {}
+                            }
+                        }
+                        atoms.push(Atom {
+                            kind,
+                            text: ch.to_string(),
+                        })
+                    }
+                }
+            }
+            TokenTree::Ident(ident) => match atoms.last_mut() {
+                // lifetimes are a joint ``'`` followed by an identifier
+                Some(last) if continues && last.text == "'" => {
+                    last.kind = AtomKind::Word;
+                    last.text.push_str(&ident.to_string());
+                }
+                _ => atoms.push(Atom {
+                    kind: AtomKind::Word,
+                    text: ident.to_string(),
+                }),
+            },
+            TokenTree::Literal(lit) => atoms.push(Atom {
+                kind: AtomKind::Word,
+                text: lit.to_string(),
+            }),
+            TokenTree::Group(group) => {
+                let inner = render_token_stream(
+                    group.stream(),
+                    expr || group.delimiter() == Delimiter::Brace,
+                );
+                let text = match group.delimiter() {
+                    Delimiter::Parenthesis => format!("({})", inner),
+                    Delimiter::Bracket => format!("[{}]", inner),
+                    Delimiter::Brace if inner.is_empty() => "{}".to_string(),
+                    Delimiter::Brace => format!("{{ {} }}", inner),
+                    Delimiter::None => inner,
+                };
+                atoms.push(Atom {
+                    kind: AtomKind::Group,
+                    text,
+                })
+            }
+        }
+    }
+    atoms
+}
+
+/// Multi-character operators, which are rendered as a single atom.
+///
+/// Note ``>>`` is omitted, since in types it is always the close of two generic argument lists.
+const OPERATORS: &[&str] = &[
+    "::", "->", "=>", "..", "..=", "==", "!=", "<=", ">=", "&&", "||",
+];
+
+/// Keywords that are always followed by whitespace
+const KEYWORDS: &[&str] = &[
+    "as", "const", "dyn", "impl", "in", "let", "mut", "ref", "return", "where",
+];
+
+/// Whether a punctuation atom is used as a prefix operator, given the atom before it
+fn is_prefix(before: Option<&Atom>) -> bool {
+    match before {
+        None => true,
+        Some(atom) => atom.kind == AtomKind::Punct && !matches!(atom.text.as_str(), ">" | "?"),
+    }
+}
+
+/// Whether whitespace is required between two atoms
+fn needs_space(prev: &Atom, next: &Atom, before_prev: Option<&Atom>) -> bool {
+    let (p, n) = (prev.text.as_str(), next.text.as_str());
+    if prev.kind == AtomKind::Operator || next.kind == AtomKind::Operator {
+        return true;
+    }
+    if matches!(n, "," | ";" | ":" | "::" | ">" | "?" | ".") && next.kind == AtomKind::Punct {
+        return n == "?" && prev.kind == AtomKind::Punct && p != "<";
+    }
+    if prev.kind == AtomKind::Punct {
+        match p {
+            "::" | "<" | "'" | "#" | "$" | "." => return false,
+            // a reference with a lifetime, even after a higher-ranked ``for<'a>``
+            "&" | "&&" if n.starts_with('\'') => return false,
+            "&" | "&&" | "*" | "!" | "-" if is_prefix(before_prev) => return false,
+            "?" => return false,
+            "!" => return next.kind != AtomKind::Group,
+            _ => {}
+        }
+    }
+    if next.kind == AtomKind::Group && next.text.starts_with(['(', '[']) {
+        // a function call, tuple struct, or ``Fn(A) -> B`` bound
+        return (prev.kind == AtomKind::Punct && !matches!(p, ">" | "?"))
+            || (prev.kind == AtomKind::Word && KEYWORDS.contains(&p));
+    }
+    if n == "<" {
+        return prev.kind != AtomKind::Word;
+    }
+    if n == "!" && prev.kind == AtomKind::Word {
+        // a macro invocation
+        return false;
+    }
+    true
+}
+
+/// Render the bounds of an ``impl Trait`` or ``dyn Trait`` type, prefixed by the keyword.
+///
+/// Traits are paths, e.g. ``dyn std::error::Error + Send + 'static``,
+/// whereas lifetimes and ``?Trait`` or higher-ranked bounds are rendered as written.
+fn convert_bounds(
+    keyword: &str,
+    bounds: &syn::punctuated::Punctuated<syn::TypeParamBound, syn::Token![+]>,
+) -> Vec<TypeSegment> {
+    let mut v = vec![keyword.into()];
+    for (i, elem) in bounds.iter().enumerate() {
+        if i > 0 {
+            v.push(" + ".into());
+        }
+        match elem {
+            syn::TypeParamBound::Trait(bound)
+                if bound.lifetimes.is_none()
+                    && bound.paren_token.is_none()
+                    && matches!(bound.modifier, syn::TraitBoundModifier::None) =>
+            {
+                v.push(TypeSegment::Path(render_tokens(&bound.path)))
+            }
+            _ => v.push(render_tokens(elem).into()),
+        }
+    }
     v
 }
 
-fn convert_type_inner(ty: &syn::Type) -> Vec<TypeSegment> {
+fn convert_type_inner(ty: &syn::Type, depth: usize) -> Vec<TypeSegment> {
     match ty {
         syn::Type::Array(array) => {
             let mut v = vec!["[".into()];
-            v.extend(convert_type(&array.elem));
+            v.extend(convert_type_at(&array.elem, depth + 1));
             v.push("; ".into());
-            let len = &array.len;
-            v.push(quote! { #len }.to_string().into());
+            // the length may be an arbitrary const expression, e.g. ``N * 2``
+            v.push(render_tokens(&array.len).into());
             v.push("]".into());
             v
         }
         syn::Type::BareFn(func) => vec![quote! { #func }.to_string().into()], // TODO this needs to be expanded
-        syn::Type::Group(group) => convert_type(&group.elem),
-        syn::Type::ImplTrait(imp) => {
-            let mut v = vec!["impl ".into()];
-            for (i, elem) in imp.bounds.iter().enumerate() {
-                if i > 0 {
-                    v.push(" + ".into());
-                }
-                v.push(quote! { #elem }.to_string().into()); // TODO this needs to be expanded to capture traits
-            }
-            v
-        }
+        syn::Type::Group(group) => convert_type_at(&group.elem, depth + 1),
+        syn::Type::ImplTrait(imp) => convert_bounds("impl ", &imp.bounds),
         syn::Type::Infer(_) => vec!["_".into()],
         syn::Type::Macro(mac) => vec![quote! { #mac }.to_string().into()],
         syn::Type::Never(_) => vec!["!".into()],
         syn::Type::Paren(paren) => {
             let mut v = vec!["(".into()];
-            v.extend(convert_type(&paren.elem));
+            v.extend(convert_type_at(&paren.elem, depth + 1));
             v.push(")".into());
             v
         }
-        syn::Type::Path(path) => {
-            // TODO this is wrong, it puts spaces between the path segments
-            vec![TypeSegment::Path(
-                quote! { #path }
-                    .to_string()
-                    .replace(" :: ", "::")
-                    .replace(" < ", "<")
-                    .replace(" >", ">"),
-            )]
-        }
+        // const generic arguments are rendered as written, e.g. ``Matrix<{ R * C }, 3>``,
+        // as are qualified paths, e.g. ``<T as Iterator>::Item``
+        syn::Type::Path(path) => vec![TypeSegment::Path(render_tokens(path))],
         syn::Type::Ptr(ptr) => {
             let mut v = vec![];
             if ptr.const_token.is_some() {
@@ -91,45 +603,34 @@ fn convert_type_inner(ty: &syn::Type) -> Vec<TypeSegment> {
             } else {
                 v.push("*".into());
             }
-            v.extend(convert_type(&ptr.elem));
+            v.extend(convert_type_at(&ptr.elem, depth + 1));
             v
         }
         syn::Type::Reference(ref_) => {
             let mut v = vec!["&".into()];
             if let Some(lifetime) = &ref_.lifetime {
-                v.push(lifetime.ident.to_string().into());
+                v.push(format!("{} ", lifetime).into());
             }
             if ref_.mutability.is_some() {
-                v.push(" mut ".into());
-            } else {
-                v.push(" ".into());
+                v.push("mut ".into());
             }
-            v.extend(convert_type(&ref_.elem));
+            v.extend(convert_type_at(&ref_.elem, depth + 1));
             v
         }
         syn::Type::Slice(slice) => {
             let mut v = vec!["[".into()];
-            v.extend(convert_type(&slice.elem));
+            v.extend(convert_type_at(&slice.elem, depth + 1));
             v.push("]".into());
             v
         }
-        syn::Type::TraitObject(trait_) => {
-            let mut v = vec!["dyn ".into()];
-            for (i, elem) in trait_.bounds.iter().enumerate() {
-                if i > 0 {
-                    v.push(" + ".into());
-                }
-                v.push(quote! { #elem }.to_string().into()); // TODO this needs to be expanded to capture traits
-            }
-            v
-        }
+        syn::Type::TraitObject(trait_) => convert_bounds("dyn ", &trait_.bounds),
         syn::Type::Tuple(tuple) => {
             let mut v = vec!["(".into()];
             for (i, elem) in tuple.elems.iter().enumerate() {
                 if i > 0 {
                     v.push(", ".into());
                 }
-                v.extend(convert_type(elem));
+                v.extend(convert_type_at(elem, depth + 1));
             }
             v.push(")".into());
             v
@@ -144,6 +645,42 @@ mod tests {
     use super::*;
     use insta::assert_yaml_snapshot;
 
+    #[test]
+    fn test_render_tokens() {
+        let cases: Vec<(TokenStream, &str)> = vec![
+            (
+                quote! { T: Iterator<Item = u8> + 'a },
+                "T: Iterator<Item = u8> + 'a",
+            ),
+            (quote! { 'a: 'b }, "'a: 'b"),
+            (quote! { const N: usize = 3 }, "const N: usize = 3"),
+            (quote! { T: ?Sized }, "T: ?Sized"),
+            (quote! { &'a mut Vec<T> }, "&'a mut Vec<T>"),
+            (quote! { <T as Trait>::Output }, "<T as Trait>::Output"),
+            (
+                quote! { F: Fn(u8, &str) -> Option<u8> },
+                "F: Fn(u8, &str) -> Option<u8>",
+            ),
+            (quote! { Matrix<{ R * C }> }, "Matrix<{ R * C }>"),
+            (
+                quote! { Assert<{ N > 0 && N <= size_of::<T>() }> },
+                "Assert<{ N > 0 && N <= size_of::<T>() }>",
+            ),
+            (
+                quote! { Bits<{ 1 << (N >> 2) }> },
+                "Bits<{ 1 << (N >> 2) }>",
+            ),
+            (quote! { *const [u8; 4] }, "*const [u8; 4]"),
+            (quote! { HashMap<K, Vec<V>> }, "HashMap<K, Vec<V>>"),
+            (quote! { for<'a> Fn(&'a u8) }, "for<'a> Fn(&'a u8)"),
+            (quote! { mac!(a, b) }, "mac!(a, b)"),
+            (quote! { -1 }, "-1"),
+        ];
+        for (tokens, expected) in cases {
+            assert_eq!(render_tokens(&tokens), expected);
+        }
+    }
+
     #[test]
     fn ty_to_type_array() {
         let ty = syn::parse_quote! { [u8; 10] };
@@ -172,7 +709,12 @@ mod tests {
         let result = convert_type(&ty);
         assert_yaml_snapshot!(result, @r###"
         ---
-        - String: impl Bound1 + Bound2 + Bound3
+        - String: "impl "
+        - Path: Bound1
+        - String: " + "
+        - Path: Bound2
+        - String: " + "
+        - Path: Bound3
         "###);
     }
 
@@ -204,7 +746,30 @@ mod tests {
         let result = convert_type(&ty);
         assert_yaml_snapshot!(result, @r###"
         ---
-        - Path: "std::collections::HashMap<u8 , u16>"
+        - Path: "std::collections::HashMap<u8, u16>"
+        "###);
+    }
+
+    #[test]
+    fn ty_to_type_qualified_path() {
+        let types: Vec<syn::Type> = vec![
+            syn::parse_quote! { <T as Iterator>::Item },
+            syn::parse_quote! { <Vec<T> as IntoIterator>::IntoIter },
+            syn::parse_quote! { <T as crate::Trait<'a, u8>>::Output<U> },
+            syn::parse_quote! { <T>::Assoc },
+            syn::parse_quote! { Option<<T as Iterator>::Item> },
+            syn::parse_quote! { &'a <<T as Trait>::A as Trait>::B },
+        ];
+        let result = types.iter().map(convert_type).collect::<Vec<_>>();
+        assert_yaml_snapshot!(result, @r###"
+        ---
+        - - Path: "<T as Iterator>::Item"
+        - - Path: "<Vec<T> as IntoIterator>::IntoIter"
+        - - Path: "<T as crate::Trait<'a, u8>>::Output<U>"
+        - - Path: "<T>::Assoc"
+        - - Path: "Option<<T as Iterator>::Item>"
+        - - String: "&'a "
+          - Path: "<<T as Trait>::A as Trait>::B"
         "###);
     }
 
@@ -225,7 +790,18 @@ mod tests {
         let result = convert_type(&ty);
         assert_yaml_snapshot!(result, @r###"
         ---
-        - String: "&a mut "
+        - String: "&'a mut "
+        - Path: u8
+        "###);
+    }
+
+    #[test]
+    fn ty_to_type_ref_plain() {
+        let ty = syn::parse_quote! { &u8 };
+        let result = convert_type(&ty);
+        assert_yaml_snapshot!(result, @r###"
+        ---
+        - String: "&"
         - Path: u8
         "###);
     }
@@ -248,7 +824,9 @@ mod tests {
         let result = convert_type(&ty);
         assert_yaml_snapshot!(result, @r###"
         ---
-        - String: "dyn std::fmt::Debug + 'a"
+        - String: "dyn "
+        - Path: "std::fmt::Debug"
+        - String: " + 'a"
         "###);
     }
 
@@ -265,4 +843,77 @@ mod tests {
         - String: )
         "###);
     }
+
+    #[test]
+    fn test_truncate_type_depth() {
+        let signature = vec![
+            TypeSegment::String("fn f(a: ".to_string()),
+            TypeSegment::Path("Option<Vec<u8>>".to_string()),
+            TypeSegment::String(") -> [".to_string()),
+            TypeSegment::Path("Box<u8>".to_string()),
+            TypeSegment::String("; 2]".to_string()),
+        ];
+        let render = |signature: TypeSignature| {
+            signature
+                .iter()
+                .map(|s| s.content().to_string())
+                .collect::<String>()
+        };
+        assert_eq!(render(truncate_type_depth(&signature, 0)), "fn f(…) -> […]");
+        assert_eq!(
+            render(truncate_type_depth(&signature, 1)),
+            "fn f(a: Option<…>) -> [Box<…>; 2]"
+        );
+        assert_eq!(
+            render(truncate_type_depth(&signature, 3)),
+            render(signature)
+        );
+    }
+
+    #[test]
+    fn test_type_form() {
+        let forms = [
+            "dyn std::error::Error + Send + Sync + 'static",
+            "dyn Send + Fn(&str) -> bool",
+            "(dyn Send + Sync)",
+            "impl Iterator<Item = u8> + 'a",
+            "impl for<'a> Fn(&'a u8) + ?Sized",
+            "std::io::Result<()>",
+            "&dyn Any",
+        ]
+        .map(|text| {
+            TypeForm::from_signature(&vec![TypeSegment::String(text.to_string())]).unwrap()
+        });
+        assert_yaml_snapshot!(forms, @r###"
+        ---
+        - form: dyn
+          trait_: "std::error::Error"
+          bounds:
+            - Send
+            - Sync
+            - "'static"
+        - form: dyn
+          trait_: Fn(&str) -> bool
+          bounds:
+            - Send
+        - form: dyn
+          trait_: ~
+          bounds:
+            - Send
+            - Sync
+        - form: impl
+          trait_: Iterator<Item = u8>
+          bounds:
+            - "'a"
+        - form: impl
+          trait_: "for<'a> Fn(&'a u8)"
+          bounds:
+            - "?Sized"
+        - form: path
+          path: "std::io::Result<()>"
+        - form: other
+          rendered: "&dyn Any"
+        "###);
+        assert_eq!(TypeForm::from_signature(&vec!["dyn".into()]), None);
+    }
 }
@@ -0,0 +1,247 @@
+//! Analyze type aliases
+use std::collections::BTreeMap;
+
+use crate::data_model::{TypeAlias, TypeSegment, TypeSignature};
+
+use super::{
+    anchor_id, attributes_from_attrs,
+    cfg::cfg_from_attrs,
+    doc_aliases_from_attrs, docstring_from_attrs, stability_from_attrs,
+    type_::{convert_generics, convert_type, merge_strings, render_tokens, substitute_identifiers},
+};
+
+impl TypeAlias {
+    /// Fully qualified name of the type alias
+    pub fn path_str(&self) -> String {
+        self.path.join("::")
+    }
+    /// A stable, URL-safe identifier for the type alias, e.g. for anchor links
+    pub fn anchor_id(&self) -> String {
+        anchor_id("type", &self.path)
+    }
+    /// The rendered target type, e.g. ``Vec<u8>`` for ``type Bytes = Vec<u8>;``
+    pub fn target_str(&self) -> String {
+        self.target.iter().map(|s| s.content()).collect()
+    }
+    /// The rendered target type, with the generic parameters substituted by the given arguments,
+    /// e.g. ``std::result::Result<u8, Error>`` for ``Result<u8>``
+    /// of ``type Result<T> = std::result::Result<T, Error>;``.
+    ///
+    /// Omitted lifetimes are elided, and omitted parameters with a default take it.
+    /// ``None`` is returned if the arguments do not match the parameters.
+    pub fn expand(&self, args: &[String]) -> Option<String> {
+        let (lifetimes, others): (Vec<&String>, Vec<&String>) =
+            args.iter().partition(|arg| arg.starts_with('\''));
+        let (mut lifetimes, mut others) = (lifetimes.into_iter(), others.into_iter());
+        let mut substitutions = BTreeMap::new();
+        for (param, name) in self.generics.params.iter().zip(self.generics.param_names()) {
+            let value = if name.starts_with('\'') {
+                lifetimes.next().cloned().unwrap_or("'_".to_string())
+            } else {
+                match (others.next(), param_default(param)) {
+                    (Some(arg), _) => arg.clone(),
+                    (None, Some(default)) => default.to_string(),
+                    (None, None) => return None,
+                }
+            };
+            substitutions.insert(name, value);
+        }
+        if lifetimes.next().is_some() || others.next().is_some() {
+            return None;
+        }
+        Some(substitute_identifiers(&self.target_str(), &substitutions))
+    }
+    /// Extract the relevant information from the AST
+    pub fn parse(parent: &[&str], ast: &syn::ItemType) -> Self {
+        let name = ast.ident.to_string();
+        Self {
+            name: name.clone(),
+            path: parent
+                .iter()
+                .map(|s| s.to_string())
+                .chain(Some(name))
+                .collect(),
+            file: None,
+            docstring: docstring_from_attrs(&ast.attrs),
+            doc_aliases: doc_aliases_from_attrs(&ast.attrs),
+            attributes: attributes_from_attrs(&ast.attrs),
+            stability: stability_from_attrs(&ast.attrs),
+            generics: convert_generics(&ast.generics),
+            target: convert_type(&ast.ty),
+            effectively_public: matches!(ast.vis, syn::Visibility::Public(_)),
+            cfg_info: cfg_from_attrs(&ast.attrs).map(|p| p.to_string()),
+        }
+    }
+}
+
+/// The default of a rendered generic parameter, e.g. ``u8`` for ``T: Into<u8> = u8``
+pub(super) fn param_default(param: &str) -> Option<&str> {
+    let mut depth = 0;
+    for (i, c) in param.char_indices() {
+        match c {
+            '<' | '(' | '[' | '{' => depth += 1,
+            '>' | ')' | ']' | '}' => depth -= 1,
+            '=' if depth == 0 => return Some(param[i + 1..].trim()),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Replace the type aliases named in a signature with their target types,
+/// including those nested in generic arguments, e.g. ``Vec<Bytes>`` becomes ``Vec<Vec<u8>>``.
+///
+/// Only one level of aliasing is expanded, so aliases in the target types are kept.
+/// As the scope of the signature is not known, a path names an alias if the alias is
+/// the only one whose fully qualified name ends with it (ignoring ``crate``, ``self`` and ``super``),
+/// and its generic arguments match the parameters of the alias.
+pub(super) fn expand_type_aliases(
+    signature: &TypeSignature,
+    aliases: &BTreeMap<String, Vec<&TypeAlias>>,
+) -> TypeSignature {
+    let mut output = vec![];
+    for segment in signature {
+        if let TypeSegment::Path(path) = segment {
+            if let Ok(mut ty) = syn::parse_str::<syn::Type>(path) {
+                if expand_in_type(&mut ty, aliases) {
+                    output.extend(convert_type(&ty));
+                    continue;
+                }
+            }
+        }
+        output.push(segment.clone());
+    }
+    merge_strings(output)
+}
+
+/// Expand the type aliases in a type, returning whether any were expanded
+fn expand_in_type(ty: &mut syn::Type, aliases: &BTreeMap<String, Vec<&TypeAlias>>) -> bool {
+    match ty {
+        syn::Type::Array(array) => expand_in_type(&mut array.elem, aliases),
+        syn::Type::Group(group) => expand_in_type(&mut group.elem, aliases),
+        syn::Type::Paren(paren) => expand_in_type(&mut paren.elem, aliases),
+        syn::Type::Ptr(ptr) => expand_in_type(&mut ptr.elem, aliases),
+        syn::Type::Reference(ref_) => expand_in_type(&mut ref_.elem, aliases),
+        syn::Type::Slice(slice) => expand_in_type(&mut slice.elem, aliases),
+        syn::Type::Tuple(tuple) => {
+            let mut expanded = false;
+            for elem in tuple.elems.iter_mut() {
+                expanded |= expand_in_type(elem, aliases);
+            }
+            expanded
+        }
+        syn::Type::Path(type_path) => {
+            let mut expanded = false;
+            let mut args = vec![];
+            for segment in type_path.path.segments.iter_mut() {
+                if let syn::PathArguments::AngleBracketed(generic_args) = &mut segment.arguments {
+                    args.clear();
+                    for arg in generic_args.args.iter_mut() {
+                        if let syn::GenericArgument::Type(arg_ty) = arg {
+                            expanded |= expand_in_type(arg_ty, aliases);
+                        }
+                        args.push(render_tokens(arg));
+                    }
+                } else {
+                    args.clear();
+                }
+            }
+            if type_path.qself.is_some() {
+                return expanded;
+            }
+            let names: Vec<String> = type_path
+                .path
+                .segments
+                .iter()
+                .map(|s| s.ident.to_string())
+                .skip_while(|name| matches!(name.as_str(), "crate" | "self" | "super"))
+                .collect();
+            let candidates = names
+                .last()
+                .and_then(|name| aliases.get(name))
+                .map(|candidates| {
+                    candidates
+                        .iter()
+                        .filter(|alias| alias.path.ends_with(&names))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            if let [alias] = candidates[..] {
+                if let Some(target) = alias.expand(&args) {
+                    if let Ok(target) = syn::parse_str::<syn::Type>(&target) {
+                        *ty = target;
+                        return true;
+                    }
+                }
+            }
+            expanded
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_yaml_snapshot;
+
+    #[test]
+    fn test_parse_type_alias_trait_object() {
+        let ast: syn::ItemType = syn::parse_quote! {
+            /// A boxed error
+            pub type BoxedError = Box<dyn std::error::Error + Send + Sync>;
+        };
+        let alias = TypeAlias::parse(&["crate"], &ast);
+        assert_yaml_snapshot!(alias, @r###"
+        ---
+        kind: type_alias
+        name: BoxedError
+        path:
+          - crate
+          - BoxedError
+        docstring: A boxed error
+        doc_aliases: []
+        attributes: []
+        generics:
+          params: []
+          where_predicates: []
+        target:
+          - Path: "Box<dyn std::error::Error + Send + Sync>"
+        effectively_public: true
+        "###);
+
+        let ast: syn::ItemType = syn::parse_quote! {
+            pub type Callback<'a, T> = &'a (dyn Fn(T) -> Result<(), BoxedError> + Send + 'static);
+        };
+        let alias = TypeAlias::parse(&["crate"], &ast);
+        assert_eq!(alias.generics.params, vec!["'a", "T"]);
+        assert_yaml_snapshot!(alias.target, @r###"
+        ---
+        - String: "&'a (dyn "
+        - Path: "Fn(T) -> Result<(), BoxedError>"
+        - String: " + "
+        - Path: Send
+        - String: " + 'static)"
+        "###);
+    }
+
+    #[test]
+    fn test_expand_type_alias() {
+        let ast: syn::ItemType = syn::parse_quote! {
+            pub type Parser<'a, T, E = Error> = Box<dyn Fn(&'a str) -> Result<T, E> + 'a>;
+        };
+        let alias = TypeAlias::parse(&["crate"], &ast);
+        let expand =
+            |args: &[&str]| alias.expand(&args.iter().map(|s| s.to_string()).collect::<Vec<_>>());
+        assert_eq!(
+            expand(&["'static", "u8", "io::Error"]).as_deref(),
+            Some("Box<dyn Fn(&'static str) -> Result<u8, io::Error> + 'static>")
+        );
+        assert_eq!(
+            expand(&["u8"]).as_deref(),
+            Some("Box<dyn Fn(&'_ str) -> Result<u8, Error> + '_>")
+        );
+        assert_eq!(expand(&[]), None);
+        assert_eq!(expand(&["u8", "Error", "String"]), None);
+    }
+}
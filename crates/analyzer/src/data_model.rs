@@ -1,7 +1,16 @@
 //! Data model for the analyzer
+//!
+//! Items (modules, structs, enums, functions, traits, type aliases, impls and extern blocks) are serialized with a ``kind`` field,
+//! so that they can be identified independently of the collection they are stored in.
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 /// Representation of a crate
 ///
 /// .. req:: Represent a crate
@@ -11,9 +20,72 @@ use serde::{Deserialize, Serialize};
 pub struct Crate {
     pub name: String,
     pub version: String,
+    /// The description of the package, from the manifest
+    #[serde(default)]
+    pub description: Option<String>,
+    /// The authors of the package, from the manifest
+    #[serde(default)]
+    pub authors: Vec<String>,
+    /// The SPDX license expression of the package, from the manifest
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// The path of the license file of the package, relative to its manifest, from the manifest,
+    /// e.g. for a non-standard license
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license_file: Option<String>,
+    /// The URL of the package repository, from the manifest
+    #[serde(default)]
+    pub repository: Option<String>,
+    /// The URL of the package homepage, from the manifest
+    #[serde(default)]
+    pub homepage: Option<String>,
+    /// The minimum supported Rust version of the package, from the ``rust-version`` of the manifest,
+    /// normalized to three components, e.g. ``1.70.0`` for ``rust-version = "1.70"``
+    #[serde(default)]
+    pub rust_version: Option<String>,
+    /// Whether the crate is ``#![no_std]``, from the attributes of its root module
+    #[serde(default)]
+    pub no_std: NoStd,
+}
+
+impl Crate {
+    /// The license to display: the SPDX license expression if given, else the path of the license file
+    pub fn license_or_file(&self) -> Option<&str> {
+        self.license.as_deref().or(self.license_file.as_deref())
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+/// Whether a crate links to the standard library
+pub enum NoStd {
+    /// The crate uses the standard library
+    #[default]
+    Std,
+    /// The crate is always ``#![no_std]``
+    Always,
+    /// The crate is ``#![no_std]`` only under a condition,
+    /// from ``#![cfg_attr(condition, no_std)]``
+    Conditional {
+        /// The condition, as written, e.g. ``not(feature = "std")``
+        condition: String,
+        /// Whether the condition holds for the configuration the crate was analyzed with
+        active: bool,
+    },
+}
+
+impl NoStd {
+    /// Whether the crate is ``#![no_std]`` for the configuration it was analyzed with
+    pub fn is_active(&self) -> bool {
+        match self {
+            Self::Std => false,
+            Self::Always => true,
+            Self::Conditional { active, .. } => *active,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename = "module")]
 /// Representation of a module
 ///
 /// .. req:: Represent a module
@@ -23,14 +95,79 @@ pub struct Crate {
 pub struct Module {
     /// The path to the module file
     pub file: Option<String>,
+    /// The number of lines in the module file, if file-backed
+    #[serde(default)]
+    pub line_count: Option<usize>,
+    /// The size of the module file in bytes, if file-backed
+    #[serde(default)]
+    pub byte_size: Option<usize>,
+    /// The name of the module, i.e. the last segment of its path
+    #[serde(default)]
+    pub name: String,
     /// The fully qualified name of the module
     pub path: Vec<String>,
     pub docstring: String,
     /// The public declarations in the module
     pub declarations: Vec<String>,
+    /// The ``use`` declarations in the module, flattened to one per imported path,
+    /// and the ``extern crate`` items, as an import of the crate root
+    #[serde(default)]
+    pub uses: Vec<UseDeclaration>,
+    /// Whether the module is likely generated, rather than hand-written,
+    /// because the header of its file has a marker such as ``// @generated``,
+    /// or it includes a file from the ``OUT_DIR`` of the build script
+    #[serde(default)]
+    pub is_generated: bool,
+    /// Whether the module is reachable from the crate root through public modules and re-exports.
+    ///
+    /// When parsed outside of a crate analysis, this only reflects the module's own ``pub`` keyword.
+    #[serde(default = "default_true")]
+    pub effectively_public: bool,
+    /// The kind of the cargo target the module belongs to,
+    /// if it is not part of the library or binary target of the crate, e.g. an example
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_kind: Option<TargetKind>,
+    /// The ``#[cfg(...)]`` predicate the module is conditional on, if any, e.g. ``feature = "std"``,
+    /// from its declaration and inner ``#![cfg(...)]`` attributes,
+    /// combined with those of the enclosing modules with ``all(...)``
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cfg_info: Option<String>,
+    /// The ``#[cfg(...)]`` predicates of the alternative declarations of the module, including the one read,
+    /// if it is declared more than once, e.g. ``unix`` and ``windows`` for
+    /// ``#[cfg(unix)] #[path = "unix.rs"] mod imp;`` and ``#[cfg(windows)] #[path = "windows.rs"] mod imp;``,
+    /// so that its implementation differs per platform
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cfg_variants: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+/// The kind of a cargo target that is analyzed on request,
+/// in addition to the library or binary target of the crate
+pub enum TargetKind {
+    /// An example, e.g. ``examples/demo.rs``
+    Example,
+    /// An integration test, e.g. ``tests/api.rs``
+    Test,
+    /// A benchmark, e.g. ``benches/parse.rs``
+    Bench,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// A single imported path of a ``use`` declaration,
+/// e.g. ``pub use inner::{Foo as Bar, nested::*}`` is flattened to ``inner::Foo`` and ``inner::nested::*``
+pub struct UseDeclaration {
+    /// The imported path as written, e.g. ``["super", "inner", "Foo"]``,
+    /// excluding the ``*`` of glob imports
+    pub path: Vec<String>,
+    /// The name the path is imported as, or ``None`` for glob imports
+    pub name: Option<String>,
+    /// Whether the declaration is ``pub``, i.e. it re-exports the path
+    pub public: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename = "struct")]
 /// Representation of a Struct
 ///
 /// .. req:: Represent a struct
@@ -38,14 +175,55 @@ pub struct Module {
 ///     :tags: rust
 ///     :status: in-progress
 pub struct Struct {
+    /// The name of the struct, i.e. the last segment of its path
+    #[serde(default)]
+    pub name: String,
     /// The fully qualified name of the struct
     pub path: Vec<String>,
+    /// The file the item is declared in, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
     /// The docstring of the struct
     pub docstring: String,
+    /// The search aliases from ``#[doc(alias = "...")]`` attributes
+    #[serde(default)]
+    pub doc_aliases: Vec<String>,
+    /// The attributes of the item, other than ``doc``, e.g. ``#[derive(Debug)]`` or ``#[tokio::main]``
+    #[serde(default)]
+    pub attributes: Vec<Attribute>,
+    /// The API stability, from a ``#[stable(...)]`` or ``#[unstable(...)]`` attribute, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stability: Option<Stability>,
+    /// The generic parameters of the struct
+    #[serde(default)]
+    pub generics: Generics,
+    /// Whether the struct has no fields, positional fields or named fields
+    #[serde(default)]
+    pub fields_kind: VariantKind,
     pub fields: Vec<Field>,
+    /// The target of the ``Deref`` impl of the type, i.e. ``Y`` for ``impl Deref for X { type Target = Y; }``,
+    /// whose methods can be called on the type
+    #[serde(default)]
+    pub deref_target: Option<TypeSignature>,
+    /// Whether the fields of the type reference the type itself, directly or through other local types,
+    /// e.g. ``struct Node { next: Option<Box<Node>> }``.
+    ///
+    /// This is only resolved for a crate analysis with ``AnalysisOptions::detect_recursive_types``.
+    #[serde(default)]
+    pub recursive: bool,
+    /// Whether the item is reachable from the crate root through public modules and re-exports.
+    ///
+    /// When parsed outside of a crate analysis, this only reflects the item's own ``pub`` keyword.
+    #[serde(default = "default_true")]
+    pub effectively_public: bool,
+    /// The ``#[cfg(...)]`` predicate the item is conditional on, if any, e.g. ``feature = "std"``,
+    /// combined with those of the enclosing modules with ``all(...)``
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cfg_info: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename = "enum")]
 /// Representation of a Enum
 ///
 /// .. req:: Represent an enum
@@ -53,27 +231,93 @@ pub struct Struct {
 ///     :tags: rust
 ///     :status: in-progress
 pub struct Enum {
+    /// The name of the enum, i.e. the last segment of its path
+    #[serde(default)]
+    pub name: String,
     /// The fully qualified name of the enum
     pub path: Vec<String>,
+    /// The file the item is declared in, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
     /// The docstring of the enum
     pub docstring: String,
+    /// The search aliases from ``#[doc(alias = "...")]`` attributes
+    #[serde(default)]
+    pub doc_aliases: Vec<String>,
+    /// The attributes of the item, other than ``doc``, e.g. ``#[derive(Debug)]`` or ``#[tokio::main]``
+    #[serde(default)]
+    pub attributes: Vec<Attribute>,
+    /// The API stability, from a ``#[stable(...)]`` or ``#[unstable(...)]`` attribute, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stability: Option<Stability>,
+    /// The generic parameters of the enum
+    #[serde(default)]
+    pub generics: Generics,
     pub variants: Vec<Variant>,
+    /// The target of the ``Deref`` impl of the type, i.e. ``Y`` for ``impl Deref for X { type Target = Y; }``,
+    /// whose methods can be called on the type
+    #[serde(default)]
+    pub deref_target: Option<TypeSignature>,
+    /// Whether the fields of the type reference the type itself, directly or through other local types,
+    /// e.g. ``struct Node { next: Option<Box<Node>> }``.
+    ///
+    /// This is only resolved for a crate analysis with ``AnalysisOptions::detect_recursive_types``.
+    #[serde(default)]
+    pub recursive: bool,
+    /// Whether the item is reachable from the crate root through public modules and re-exports.
+    ///
+    /// When parsed outside of a crate analysis, this only reflects the item's own ``pub`` keyword.
+    #[serde(default = "default_true")]
+    pub effectively_public: bool,
+    /// The ``#[cfg(...)]`` predicate the item is conditional on, if any, e.g. ``feature = "std"``,
+    /// combined with those of the enclosing modules with ``all(...)``
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cfg_info: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 /// Representation of a Enum variant
 pub struct Variant {
+    /// The name of the variant, i.e. the last segment of its path
+    #[serde(default)]
+    pub name: String,
     /// The fully qualified name of the variant
     pub path: Vec<String>,
     /// The docstring of the variant
     pub docstring: String,
     pub discriminant: Option<String>, // TODO shouldn't just be a string
+    /// Whether the variant has no fields, positional fields or named fields
+    #[serde(default)]
+    pub fields_kind: VariantKind,
     pub fields: Vec<Field>,
+    /// The ``#[cfg(...)]`` predicate the variant is conditional on, if any, e.g. ``feature = "std"``
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cfg: Option<String>,
+    /// The arguments of the ``#[serde(...)]`` attributes of the variant, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub serde: Option<SerdeAttributes>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+/// The form of the fields of an enum variant, or of a struct
+pub enum VariantKind {
+    /// A variant without fields, e.g. ``None``
+    #[default]
+    Unit,
+    /// A variant with positional fields, e.g. ``Some(T)``,
+    /// whose fields are named by their index
+    Tuple,
+    /// A variant with named fields, e.g. ``Point { x: i32, y: i32 }``
+    Struct,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 /// Representation of a Struct or Enum field
 pub struct Field {
+    /// The name of the field, or its index for tuple fields, i.e. the last segment of its path
+    #[serde(default)]
+    pub name: String,
     /// The fully qualified name of the field.
     ///
     /// Note, for fields of tuple structs, the final component is the index of the field
@@ -81,26 +325,812 @@ pub struct Field {
     /// The docstring of the field
     pub docstring: String,
     pub type_: TypeSignature,
+    /// Whether the field is public, i.e. declared ``pub`` or a field of an enum variant.
+    ///
+    /// Private fields are dropped from the analysis of a crate, unless private items are included.
+    #[serde(default = "default_true")]
+    pub public: bool,
+    /// The ``#[cfg(...)]`` predicate the field is conditional on, if any, e.g. ``feature = "std"``
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cfg: Option<String>,
+    /// The arguments of the ``#[serde(...)]`` attributes of the field, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub serde: Option<SerdeAttributes>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+/// The arguments of the ``#[serde(...)]`` attributes of a field or variant,
+/// which change how it is represented in the serialized data
+pub struct SerdeAttributes {
+    /// The name it is serialized as, from ``rename = "..."`` or ``rename(serialize = "...")``
+    pub rename: Option<String>,
+    /// The other names accepted when deserializing,
+    /// from ``alias = "..."`` or ``rename(deserialize = "...")``
+    pub aliases: Vec<String>,
+    /// Whether it is never serialized, from ``skip`` or ``skip_serializing``
+    pub skip_serializing: bool,
+    /// Whether it is never deserialized, from ``skip`` or ``skip_deserializing``
+    pub skip_deserializing: bool,
+    /// The predicate for skipping serialization, from ``skip_serializing_if = "..."``
+    pub skip_serializing_if: Option<String>,
+    /// The function providing the value when it is missing, from ``default = "..."``,
+    /// or ``Default::default`` for a bare ``default``
+    pub default: Option<String>,
+    /// Whether its fields are inlined into the parent, from ``flatten``
+    pub flatten: bool,
+    /// All arguments, as written, e.g. ``with = "hex"``
+    pub arguments: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename = "function")]
 /// Representation of a function
 pub struct Function {
+    /// The name of the function, i.e. the last segment of its path
+    #[serde(default)]
+    pub name: String,
     /// The fully qualified name of the function.
     pub path: Vec<String>,
+    /// The file the item is declared in, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
     /// The docstring of the function
     pub docstring: String,
-    // TODO signature
+    /// The search aliases from ``#[doc(alias = "...")]`` attributes
+    #[serde(default)]
+    pub doc_aliases: Vec<String>,
+    /// The attributes of the item, other than ``doc``, e.g. ``#[derive(Debug)]`` or ``#[tokio::main]``
+    #[serde(default)]
+    pub attributes: Vec<Attribute>,
+    /// The API stability, from a ``#[stable(...)]`` or ``#[unstable(...)]`` attribute, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stability: Option<Stability>,
+    /// The generic parameters of the function
+    pub generics: Generics,
+    /// The receiver of a method, e.g. ``&self`` or ``self: Box<Self>``
+    pub receiver: Option<Receiver>,
+    /// The parameters of the function, excluding any receiver
+    pub params: Vec<Param>,
+    /// The return type of the function, or ``None`` if it returns ``()``
+    pub output: Option<TypeSignature>,
+    /// The context the function is declared in
+    #[serde(default)]
+    pub owner: FunctionOwner,
+    /// Whether the function is C-variadic, i.e. its parameters end with ``...``
+    #[serde(default)]
+    pub variadic: bool,
+    /// Whether the function is declared ``async``
+    #[serde(default)]
+    pub is_async: bool,
+    /// Whether the function is declared ``unsafe``, and so callers must uphold its safety contract
+    #[serde(default)]
+    pub is_unsafe: bool,
+    /// Whether the item is reachable from the crate root through public modules and re-exports.
+    ///
+    /// Methods are always ``true``, as they follow the visibility of their type or trait.
+    /// When parsed outside of a crate analysis, this only reflects the item's own ``pub`` keyword.
+    #[serde(default = "default_true")]
+    pub effectively_public: bool,
+    /// The ``#[cfg(...)]`` predicate the item is conditional on, if any, e.g. ``feature = "std"``,
+    /// combined with those of the enclosing modules (and impl or trait, for methods) with ``all(...)``
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cfg_info: Option<String>,
+    /// The signature as written in the source, with its original formatting and comments,
+    /// e.g. to render it in place of the re-serialized ``signature()``.
+    ///
+    /// ``None`` if the source span is not available, e.g. for a function parsed from tokens,
+    /// or unless ``AnalysisOptions::preserve_signature_formatting`` is set, for a crate analysis.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_signature: Option<String>,
+    /// For methods of impls, the signature with ``Self`` substituted by the self type of the impl,
+    /// e.g. ``fn get(&self) -> &Wrapper<T>`` for ``fn get(&self) -> &Self`` in ``impl<T> Wrapper<T>``
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_signature: Option<TypeSignature>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+/// The context a function is declared in
+pub enum FunctionOwner {
+    /// A free function at module scope
+    #[default]
+    Free,
+    /// A method of an inherent impl, with the fully qualified name of the type
+    Inherent(String),
+    /// A method declared in a trait, with the fully qualified name of the trait
+    Trait(String),
+    /// A method of a trait impl
+    TraitImpl {
+        /// The fully qualified name of the type
+        type_: String,
+        /// The trait, as written in the impl
+        trait_: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+/// The classification of a function, by which rustdoc groups the functions of a type
+pub enum FunctionKind {
+    /// A free function at module scope, or of an ``extern`` block
+    #[default]
+    Free,
+    /// A function of an impl or trait without a ``self`` receiver, e.g. ``fn new() -> Self``,
+    /// called by its path, e.g. ``MyType::new()``
+    AssociatedFunction,
+    /// A function of an impl or trait with a ``self`` receiver, e.g. ``fn len(&self) -> usize``
+    Method,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// A heading of a docstring, as an entry of its table of contents
+pub struct Heading {
+    /// The heading level, from 1 (``#``) to 6 (``######``)
+    pub level: usize,
+    /// The text of the heading, as written
+    pub text: String,
+    /// The anchor of the heading, unique within the docstring, e.g. ``error-handling`` for ``# Error handling``
+    pub slug: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// A fenced code block in a docstring, e.g. a doc example
+pub struct CodeBlock {
+    /// The info string after the opening fence, e.g. ``rust,no_run`` or ``sh``
+    pub info: String,
+    /// Whether the block is Rust code, and so is run as a doctest
+    pub rust: bool,
+    /// The code as it is run, including the lines that are hidden with a leading ``# ``
+    pub code: String,
+    /// The code as it is displayed, excluding the hidden lines
+    pub display: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// An intra-doc link in a docstring, i.e. a Markdown link to an item path,
+/// e.g. ``[`Vec::new`]``, ``[the socket](net::Socket)`` or ``[fn@Foo::bar()]``
+pub struct DocLink {
+    /// The text of the link, as written
+    pub text: String,
+    /// The target of the link, as written, e.g. ``fn@Foo::bar()``
+    pub target: String,
+    /// The item path of the target, without backticks, disambiguators, generics or a fragment,
+    /// e.g. ``Foo::bar``
+    pub path: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// An intra-doc link that does not resolve to a known item
+pub struct BrokenLink {
+    /// The fully qualified name of the item whose docstring contains the link
+    pub item: String,
+    /// The text of the link, as written
+    pub text: String,
+    /// The target of the link, as written
+    pub target: String,
+    /// The file the item is declared in, if known
+    pub file: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+/// The conventional sections of a function docstring,
+/// i.e. those under the ``# Panics``, ``# Errors``, ``# Safety`` and ``# Examples`` headings
+pub struct DocSections {
+    /// The remainder of the docstring, including any unrecognized sections
+    pub description: String,
+    pub panics: Option<String>,
+    pub errors: Option<String>,
+    pub safety: Option<String>,
+    pub examples: Option<String>,
+    /// The project-specific sections that were requested when parsing, e.g. ``# Complexity``,
+    /// keyed by the heading as it was requested
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// The receiver of a method, i.e. how it takes ``self``
+pub struct Receiver {
+    /// The receiver as written, e.g. ``&'a mut self`` or ``self: Pin<&mut Self>``
+    pub rendered: String,
+    /// The form of the receiver, by its outermost reference or smart pointer
+    pub kind: ReceiverKind,
+    /// The lifetime of the reference to ``self``, if named,
+    /// e.g. ``'a`` for ``&'a mut self`` or ``self: Pin<&'a mut Self>``
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lifetime: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+/// The form of a method receiver
+pub enum ReceiverKind {
+    /// ``self`` or ``mut self``, taking ownership of the value
+    Value,
+    /// ``&self``, or ``self: &Self``
+    Ref,
+    /// ``&mut self``, or ``self: &mut Self``
+    RefMut,
+    /// ``self: Box<Self>``
+    Box,
+    /// ``self: Rc<Self>``
+    Rc,
+    /// ``self: Arc<Self>``
+    Arc,
+    /// A pinned pointer, e.g. ``self: Pin<&mut Self>`` as taken by ``Future::poll``
+    Pin(Box<ReceiverKind>),
+    /// Any other type of ``self``, e.g. ``self: Cow<'_, Self>``
+    Other,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Representation of a function parameter
+pub struct Param {
+    /// The name (or pattern) of the parameter
+    pub name: String,
+    pub type_: TypeSignature,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// An attribute of an item, e.g. ``#[derive(Debug)]`` or ``#[tokio::main]``
+pub struct Attribute {
+    /// The full path of the attribute, as written, e.g. ``tokio::main``
+    pub path: String,
+    /// The attribute as written, e.g. ``#[tokio::main(flavor = "current_thread")]``
+    pub rendered: String,
+    /// Whether the attribute is built into the compiler or a tool, e.g. ``derive`` or ``clippy::allow``,
+    /// rather than an attribute macro, e.g. ``tokio::main``
+    pub builtin: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "level", rename_all = "lowercase")]
+/// The API stability of an item, as marked in the standard library and other nightly crates
+pub enum Stability {
+    /// ``#[stable(feature = "...", since = "...")]``
+    Stable {
+        /// The name of the feature that was stabilized
+        feature: Option<String>,
+        /// The version the item was stabilized in, e.g. ``1.0.0``
+        since: Option<String>,
+    },
+    /// ``#[unstable(feature = "...", issue = "...", reason = "...")]``
+    Unstable {
+        /// The name of the feature gate that enables the item
+        feature: Option<String>,
+        /// The number of the tracking issue, if any
+        issue: Option<String>,
+        /// Why the item is unstable
+        reason: Option<String>,
+    },
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+/// Representation of the generics of an item
+pub struct Generics {
+    /// The rendered generic parameters, e.g. ``'a``, ``T: Clone`` or ``const N: usize``
+    pub params: Vec<String>,
+    /// The rendered predicates of the ``where`` clause, e.g. ``T: Clone``
+    #[serde(default)]
+    pub where_predicates: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename = "trait")]
+/// Representation of a trait
+pub struct Trait {
+    /// The name of the trait, i.e. the last segment of its path
+    #[serde(default)]
+    pub name: String,
+    /// The fully qualified name of the trait
+    pub path: Vec<String>,
+    /// The file the item is declared in, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// The docstring of the trait
+    pub docstring: String,
+    /// The search aliases from ``#[doc(alias = "...")]`` attributes
+    #[serde(default)]
+    pub doc_aliases: Vec<String>,
+    /// The attributes of the item, other than ``doc``, e.g. ``#[derive(Debug)]`` or ``#[tokio::main]``
+    #[serde(default)]
+    pub attributes: Vec<Attribute>,
+    /// The API stability, from a ``#[stable(...)]`` or ``#[unstable(...)]`` attribute, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stability: Option<Stability>,
+    /// The generic parameters of the trait
+    pub generics: Generics,
+    /// The supertraits, as written, e.g. ``Bar<T>`` and ``std::fmt::Debug`` for ``trait Foo<T>: Bar<T> + std::fmt::Debug``
+    #[serde(default)]
+    pub supertraits: Vec<String>,
+    /// The bounds required on ``Self``, from the supertraits and the ``where Self: ...`` predicates,
+    /// e.g. ``Sized``, ``Send`` and ``'static`` for ``trait Foo: Send + 'static where Self: Sized``
+    #[serde(default)]
+    pub self_bounds: Vec<String>,
+    /// The methods declared in the trait
+    pub functions: Vec<Function>,
+    /// The associated types declared in the trait, including generic associated types
+    #[serde(default)]
+    pub assoc_types: Vec<TraitAssocType>,
+    /// The names of the methods that have a default implementation,
+    /// and so are provided to implementors rather than required of them
+    #[serde(default)]
+    pub provided_methods: Vec<String>,
+    /// Whether the trait has the ``#[async_trait]`` attribute,
+    /// in which case its ``async`` methods are desugared to return boxed futures
+    #[serde(default)]
+    pub async_trait: bool,
+    /// Whether the trait is declared ``unsafe``, and so implementors must uphold its safety contract
+    #[serde(default)]
+    pub is_unsafe: bool,
+    /// Whether the trait is sealed, i.e. it cannot be implemented outside of its crate,
+    /// because one of its supertraits is a local trait that is not public
+    #[serde(default)]
+    pub sealed: bool,
+    /// Whether the trait is object safe, i.e. it can be used as ``dyn Trait``.
+    ///
+    /// This is a best-effort check of the syntax of the trait, see ``Trait::parse`` for the rules checked.
+    #[serde(default = "default_true")]
+    pub object_safe: bool,
+    /// Whether the item is reachable from the crate root through public modules and re-exports.
+    ///
+    /// When parsed outside of a crate analysis, this only reflects the item's own ``pub`` keyword.
+    #[serde(default = "default_true")]
+    pub effectively_public: bool,
+    /// The ``#[cfg(...)]`` predicate the item is conditional on, if any, e.g. ``feature = "std"``,
+    /// combined with those of the enclosing modules with ``all(...)``
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cfg_info: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename = "type_alias")]
+/// Representation of a type alias, e.g. ``type BoxedError = Box<dyn Error + Send + Sync>;``
+pub struct TypeAlias {
+    /// The name of the type alias, i.e. the last segment of its path
+    pub name: String,
+    /// The fully qualified name of the type alias
+    pub path: Vec<String>,
+    /// The file the item is declared in, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// The docstring of the type alias
+    pub docstring: String,
+    /// The search aliases from ``#[doc(alias = "...")]`` attributes
+    #[serde(default)]
+    pub doc_aliases: Vec<String>,
+    /// The attributes of the item, other than ``doc``, e.g. ``#[cfg(unix)]``
+    #[serde(default)]
+    pub attributes: Vec<Attribute>,
+    /// The API stability, from a ``#[stable(...)]`` or ``#[unstable(...)]`` attribute, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stability: Option<Stability>,
+    /// The generic parameters of the type alias
+    pub generics: Generics,
+    /// The aliased type
+    pub target: TypeSignature,
+    /// Whether the item is reachable from the crate root through public modules and re-exports.
+    ///
+    /// When parsed outside of a crate analysis, this only reflects the item's own ``pub`` keyword.
+    #[serde(default = "default_true")]
+    pub effectively_public: bool,
+    /// The ``#[cfg(...)]`` predicate the item is conditional on, if any, e.g. ``feature = "std"``,
+    /// combined with those of the enclosing modules with ``all(...)``
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cfg_info: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename = "const")]
+/// Representation of a constant, e.g. ``pub const MAX_SIZE: usize = 1024;``
+pub struct Const {
+    /// The name of the constant, i.e. the last segment of its path
+    pub name: String,
+    /// The fully qualified name of the constant
+    pub path: Vec<String>,
+    /// The file the item is declared in, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// The docstring of the constant
+    pub docstring: String,
+    /// The search aliases from ``#[doc(alias = "...")]`` attributes
+    #[serde(default)]
+    pub doc_aliases: Vec<String>,
+    /// The attributes of the item, other than ``doc``
+    #[serde(default)]
+    pub attributes: Vec<Attribute>,
+    /// The API stability, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stability: Option<Stability>,
+    /// The type of the constant
+    pub type_: TypeSignature,
+    /// A preview of the initializer, rendered from its tokens and truncated, e.g. ``[0, 1, 2, …``
+    pub value: String,
+    /// The initializer as written in the source, with its formatting and comments,
+    /// if kept with ``full_initializers``
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_value: Option<String>,
+    /// Whether the item is reachable from the crate root through public modules and re-exports
+    #[serde(default = "default_true")]
+    pub effectively_public: bool,
+    /// The ``#[cfg(...)]`` predicate the item is conditional on, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cfg_info: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename = "static")]
+/// Representation of a static item, e.g. ``pub static GREETING: &str = "hello";``
+pub struct Static {
+    /// The name of the static, i.e. the last segment of its path
+    pub name: String,
+    /// The fully qualified name of the static
+    pub path: Vec<String>,
+    /// The file the item is declared in, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// The docstring of the static
+    pub docstring: String,
+    /// The search aliases from ``#[doc(alias = "...")]`` attributes
+    #[serde(default)]
+    pub doc_aliases: Vec<String>,
+    /// The attributes of the item, other than ``doc``
+    #[serde(default)]
+    pub attributes: Vec<Attribute>,
+    /// The API stability, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stability: Option<Stability>,
+    /// Whether the static is declared ``mut``
+    pub mutable: bool,
+    /// The type of the static
+    pub type_: TypeSignature,
+    /// A preview of the initializer, as for ``Const``
+    pub value: String,
+    /// The initializer as written in the source, as for ``Const``
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub full_value: Option<String>,
+    /// Whether the item is reachable from the crate root through public modules and re-exports
+    #[serde(default = "default_true")]
+    pub effectively_public: bool,
+    /// The ``#[cfg(...)]`` predicate the item is conditional on, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cfg_info: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename = "macro")]
+/// Representation of a declarative macro, e.g. ``macro_rules! square { ($x:expr) => { $x * $x }; }``
+pub struct Macro {
+    /// The name of the macro, i.e. the last segment of its path
+    pub name: String,
+    /// The fully qualified name of the macro:
+    /// at the crate root if it is exported, otherwise in the module it is defined in
+    pub path: Vec<String>,
+    /// The file the item is declared in, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// The docstring of the macro
+    pub docstring: String,
+    /// The patterns of the rules of the macro, e.g. ``($x:expr)``
+    pub rules: Vec<String>,
+    /// Whether the macro is exported from the crate with ``#[macro_export]``
+    pub exported: bool,
+    /// Whether the macro can be used outside of the crate, i.e. whether it is exported,
+    /// since a ``macro_rules!`` macro cannot otherwise be re-exported publicly
+    #[serde(default = "default_true")]
+    pub effectively_public: bool,
+    /// The ``#[cfg(...)]`` predicate the item is conditional on, if any, e.g. ``feature = "std"``,
+    /// combined with those of the enclosing modules with ``all(...)``
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cfg_info: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename = "impl")]
+/// Representation of an impl block
+pub struct Impl {
+    /// The fully qualified name of the module the impl is declared in
+    pub parent: Vec<String>,
+    /// The file the item is declared in, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// The docstring of the impl block
+    pub docstring: String,
+    /// The type the impl is for, as written, e.g. ``&MyType`` or ``Box<T>``
+    pub self_type: TypeSignature,
+    /// The trait being implemented, or ``None`` for inherent impls
+    pub trait_: Option<TypeSignature>,
+    /// The path of the type the impl is attributed to, as written in the source.
+    ///
+    /// References, pointers and the fundamental wrappers ``Box`` and ``Pin`` are stripped,
+    /// so ``impl Display for &MyType`` is attributed to ``MyType``.
+    /// This is ``None`` if there is no single base type,
+    /// e.g. for slices, tuples or blanket impls over a generic parameter.
+    pub base_type: Option<String>,
+    /// The generic parameters of the impl block, e.g. ``T`` in ``impl<T> Foo for Bar<T>``,
+    /// and the predicates of its ``where`` clause, which determine when the impl applies
+    pub generics: Generics,
+    /// The functions declared in the impl block.
+    ///
+    /// For inherent impls, only public functions are included.
+    pub functions: Vec<Function>,
+    /// The associated types defined in the impl block, e.g. ``type Target = u8;``
+    #[serde(default)]
+    pub assoc_types: Vec<AssocType>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// An associated type defined in an impl block
+pub struct AssocType {
+    pub name: String,
+    /// The generic parameters and ``where`` clause of a generic associated type,
+    /// e.g. ``'a`` and ``Self: 'a`` for ``type Item<'a> = &'a T where Self: 'a;``
+    #[serde(default)]
+    pub generics: Generics,
+    pub type_: TypeSignature,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// An associated type declared in a trait, e.g. ``type Item<'a>: Clone where Self: 'a;``
+pub struct TraitAssocType {
+    pub name: String,
+    /// The docstring of the associated type
+    pub docstring: String,
+    /// The generic parameters and ``where`` clause of a generic associated type,
+    /// e.g. ``'a`` and ``Self: 'a``
+    pub generics: Generics,
+    /// The bounds on the associated type, as written, e.g. ``Clone``
+    pub bounds: Vec<String>,
+    /// The default type, if any, e.g. ``u8`` for ``type Output = u8;``
+    pub default: Option<TypeSignature>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename = "extern_block")]
+/// Representation of an ``extern`` block of foreign (FFI) declarations
+pub struct ExternBlock {
+    /// The fully qualified name of the module the block is declared in
+    pub parent: Vec<String>,
+    /// The file the item is declared in, if known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// The docstring of the block
+    pub docstring: String,
+    /// The ABI of the block, e.g. ``C``, or ``None`` if not specified
+    pub abi: Option<String>,
+    /// The public foreign functions declared in the block
+    pub functions: Vec<Function>,
+    /// The public foreign statics declared in the block
+    pub statics: Vec<ForeignStatic>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Representation of a static declared in an ``extern`` block
+pub struct ForeignStatic {
+    /// The name of the static, i.e. the last segment of its path
+    #[serde(default)]
+    pub name: String,
+    /// The fully qualified name of the static
+    pub path: Vec<String>,
+    /// The docstring of the static
+    pub docstring: String,
+    /// Whether the static is declared ``mut``
+    pub mutable: bool,
+    pub type_: TypeSignature,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// A node in the module hierarchy of a crate
+pub struct ModuleNode {
+    /// The fully qualified name of the module
+    pub path: Vec<String>,
+    /// The direct child modules
+    pub children: Vec<ModuleNode>,
+    /// The fully qualified names of the items declared directly in the module,
+    /// i.e. its structs, enums, functions, traits, type aliases and foreign items
+    pub items: Vec<String>,
+    /// The fully qualified names of the modules collapsed into this node, outermost first,
+    /// e.g. ``a`` and ``a::b`` for the node of ``a::b::c``, if the tree was collapsed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub collapsed: Vec<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+/// The items declared directly in a module
+pub struct ModuleContents<'a> {
+    pub structs: Vec<&'a Struct>,
+    pub enums: Vec<&'a Enum>,
+    pub functions: Vec<&'a Function>,
+    pub traits: Vec<&'a Trait>,
+    pub type_aliases: Vec<&'a TypeAlias>,
+    pub consts: Vec<&'a Const>,
+    pub statics: Vec<&'a Static>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+/// The functions of the inherent impls of a type, grouped as in rustdoc
+pub struct InherentFunctions<'a> {
+    /// The functions without a ``self`` receiver, e.g. constructors such as ``fn new() -> Self``
+    pub associated_functions: Vec<&'a Function>,
+    /// The functions with a ``self`` receiver, e.g. ``fn len(&self) -> usize``
+    pub methods: Vec<&'a Function>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// The methods that a trait inherits from one of its supertraits
+pub struct InheritedMethods {
+    /// The fully qualified name of the supertrait if it is local, otherwise the path as written
+    pub trait_: String,
+    /// Whether the supertrait is a trait of the analysis, and so its methods are known
+    pub local: bool,
+    /// The methods declared in the supertrait
+    pub functions: Vec<Function>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// A method of a trait, as implemented for a type
+pub struct ImplementedMethod {
+    /// The method as declared in the trait
+    pub function: Function,
+    /// Whether the trait has no default implementation of the method, so implementors must define it
+    pub required: bool,
+    /// Whether an impl of the trait for the type defines the method,
+    /// rather than relying on the default implementation
+    pub overridden: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// A type that implements a trait, as declared by an impl block
+pub struct Implementor {
+    /// The type the impl is for, as written, including its generic arguments,
+    /// e.g. ``Vec<MyType>``, ``[u8; 32]`` or ``(A, B)``
+    pub self_type: TypeSignature,
+    /// The fully qualified name of the type the impl is attributed to, if it is a known item
+    pub type_path: Option<String>,
+    /// The generic parameters and ``where`` clause of the impl block
+    pub generics: Generics,
+    /// The fully qualified name of the module the impl is declared in
+    pub parent: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+/// How the signature of a function differs from that of another version of it, as seen by its callers,
+/// ordered from the least to the most significant
+pub enum SignatureChange {
+    /// The signatures are the same
+    Unchanged,
+    /// Only the names (or patterns) of the parameters differ, which are not part of the function's type
+    ParamNamesOnly,
+    /// The signatures differ, but existing callers are not affected,
+    /// e.g. a bound was removed from a generic parameter
+    Compatible,
+    /// The signatures differ in a way that may break existing callers,
+    /// e.g. the type of a parameter changed
+    Breaking,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+/// The kind of a documented item
+pub enum ItemKind {
+    Module,
+    Struct,
+    Enum,
+    Function,
+    Trait,
+    #[serde(rename = "type")]
+    TypeAlias,
+    Const,
+    Static,
+    Macro,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// A reference from a path in a type to a documented item
+pub struct ItemRef {
+    /// The path as written in the type, e.g. ``net::Socket``
+    pub name: String,
+    /// The kind of the item, or ``None`` if the path is not a known item
+    pub kind: Option<ItemKind>,
+    /// The fully qualified name of the item, or ``None`` if the path is not a known item
+    pub path: Option<String>,
+    /// The references for the generic type arguments of the path, in order,
+    /// or ``None`` for arguments that are not paths
+    pub args: Vec<Option<ItemRef>>,
+    /// The documentation URL of the item, if the path is to an item of an external crate
+    /// and was resolved with a ``DocUrlResolver``
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+/// An entry of the manifest of documented objects, from which cross-reference targets are registered
+pub struct ObjectEntry {
+    /// The kind of the object, as the prefix of its anchor id, e.g. ``struct``, ``method`` or ``structfield``
+    pub kind: String,
+    /// The fully qualified name of the object
+    pub path: String,
+    /// The stable, URL-safe identifier of the object, e.g. ``struct.my_crate.Foo``
+    pub anchor_id: String,
+    /// The first line of the docstring, or empty if there is none
+    pub summary: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 /// A segment of a type signature
 ///
 /// Types are split into segments to allow for easy identification of referenceable elements
 pub enum TypeSegment {
     String(String),
     Path(String),
+    /// A path to an item of an external crate, with the URL of its documentation,
+    /// e.g. ``serde_json::Value`` linked to its page on docs.rs
+    ExternalPath {
+        path: String,
+        url: String,
+    },
 }
 
 /// A representation of a type signature
 pub type TypeSignature = Vec<TypeSegment>;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "form", rename_all = "lowercase")]
+/// The form of a type, separating the trait of a trait object or opaque type from its other bounds,
+/// e.g. to link the trait and display the bounds separately
+pub enum TypeForm {
+    /// A trait object, e.g. ``dyn Error + Send + 'static``
+    Dyn {
+        /// The principal trait, e.g. ``Error``,
+        /// or ``None`` if there are only auto traits and lifetimes, e.g. ``dyn Send + Sync``
+        trait_: Option<String>,
+        /// The other bounds, in the order they are written, e.g. ``Send`` and ``'static``
+        bounds: Vec<String>,
+    },
+    /// An opaque type, e.g. ``impl Iterator<Item = u8> + Send``
+    Impl {
+        /// The principal trait, e.g. ``Iterator<Item = u8>``,
+        /// or ``None`` if there are only auto traits and lifetimes
+        trait_: Option<String>,
+        /// The other bounds, in the order they are written, e.g. ``Send``
+        bounds: Vec<String>,
+    },
+    /// A path, e.g. ``Vec<u8>`` or ``std::io::Result<()>``
+    Path { path: String },
+    /// Any other type, e.g. a reference, tuple or array, as written
+    Other { rendered: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_item_kind_serialization() {
+        let struct_ = Struct {
+            name: "MyStruct".to_string(),
+            path: vec!["crate".to_string(), "MyStruct".to_string()],
+            file: None,
+            docstring: String::new(),
+            doc_aliases: vec![],
+            attributes: vec![],
+            stability: None,
+            generics: Generics::default(),
+            fields_kind: VariantKind::Unit,
+            fields: vec![],
+            deref_target: None,
+            recursive: false,
+            effectively_public: true,
+            cfg_info: None,
+        };
+        let value = serde_json::to_value(&struct_).unwrap();
+        assert_eq!(value["kind"], "struct");
+        // the kind is optional when deserializing, for backward compatibility
+        let struct_: Struct = serde_json::from_str(
+            r#"{"path": ["crate", "MyStruct"], "docstring": "", "fields": []}"#,
+        )
+        .unwrap();
+        assert_eq!(struct_.path, vec!["crate", "MyStruct"]);
+        assert!(struct_.effectively_public);
+    }
+}
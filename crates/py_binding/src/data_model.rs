@@ -2,7 +2,7 @@
 
 use pyo3::prelude::*;
 
-use analyzer::data_model;
+use analyzer::{analyze::docstring::table_of_contents, data_model};
 
 #[pyclass]
 #[derive(Clone)]
@@ -12,6 +12,24 @@ pub struct Crate {
     pub name: String,
     #[pyo3(get)]
     pub version: String,
+    #[pyo3(get)]
+    pub description: Option<String>,
+    #[pyo3(get)]
+    pub authors: Vec<String>,
+    #[pyo3(get)]
+    pub license: Option<String>,
+    #[pyo3(get)]
+    pub license_file: Option<String>,
+    #[pyo3(get)]
+    pub repository: Option<String>,
+    #[pyo3(get)]
+    pub homepage: Option<String>,
+    #[pyo3(get)]
+    pub rust_version: Option<String>,
+    #[pyo3(get)]
+    pub no_std: bool,
+    #[pyo3(get)]
+    pub no_std_condition: Option<String>,
 }
 
 #[pymethods]
@@ -34,6 +52,18 @@ impl From<data_model::Crate> for Crate {
         Crate {
             name: crate_.name,
             version: crate_.version,
+            description: crate_.description,
+            authors: crate_.authors,
+            license: crate_.license,
+            license_file: crate_.license_file,
+            repository: crate_.repository,
+            homepage: crate_.homepage,
+            rust_version: crate_.rust_version,
+            no_std: crate_.no_std.is_active(),
+            no_std_condition: match crate_.no_std {
+                data_model::NoStd::Conditional { condition, .. } => Some(condition),
+                _ => None,
+            },
         }
     }
 }
@@ -45,9 +75,26 @@ pub struct Module {
     #[pyo3(get)]
     pub file: Option<String>,
     #[pyo3(get)]
+    pub line_count: Option<usize>,
+    #[pyo3(get)]
+    pub byte_size: Option<usize>,
+    #[pyo3(get)]
     pub path: Vec<String>,
     #[pyo3(get)]
+    pub anchor_id: String,
+    #[pyo3(get)]
     pub docstring: String,
+    #[pyo3(get)]
+    pub is_generated: bool,
+    #[pyo3(get)]
+    pub target_kind: Option<String>,
+    #[pyo3(get)]
+    pub cfg_info: Option<String>,
+    #[pyo3(get)]
+    pub cfg_variants: Vec<String>,
+    /// The headings of the docstring, for a table of contents
+    #[pyo3(get)]
+    pub toc: Vec<Heading>,
 }
 
 #[pymethods]
@@ -68,9 +115,17 @@ impl Module {
 impl From<data_model::Module> for Module {
     fn from(module: data_model::Module) -> Self {
         Module {
+            anchor_id: module.anchor_id(),
+            toc: toc(&module.docstring),
             file: module.file,
+            line_count: module.line_count,
+            byte_size: module.byte_size,
             path: module.path,
             docstring: module.docstring,
+            is_generated: module.is_generated,
+            target_kind: module.target_kind.map(target_kind),
+            cfg_info: module.cfg_info,
+            cfg_variants: module.cfg_variants,
         }
     }
 }
@@ -85,6 +140,17 @@ pub struct Field {
     pub docstring: String,
     #[pyo3(get)]
     pub type_: Vec<TypeSegment>,
+    #[pyo3(get)]
+    pub public: bool,
+    /// The name the field is serialized as, from ``#[serde(rename = "...")]``
+    #[pyo3(get)]
+    pub serde_rename: Option<String>,
+    /// The arguments of the ``#[serde(...)]`` attributes of the field, as written
+    #[pyo3(get)]
+    pub serde_arguments: Vec<String>,
+    /// Whether the field is a ``PhantomData<...>`` marker
+    #[pyo3(get)]
+    pub phantom: bool,
 }
 
 #[pymethods]
@@ -105,9 +171,13 @@ impl Field {
 impl From<data_model::Field> for Field {
     fn from(field: data_model::Field) -> Self {
         Field {
+            phantom: field.is_phantom(),
             path: field.path,
             docstring: field.docstring,
             type_: field.type_.into_iter().map(TypeSegment::from).collect(),
+            public: field.public,
+            serde_rename: field.serde.as_ref().and_then(|s| s.rename.clone()),
+            serde_arguments: field.serde.map(|s| s.arguments).unwrap_or_default(),
         }
     }
 }
@@ -119,9 +189,25 @@ pub struct Struct {
     #[pyo3(get)]
     pub path: Vec<String>,
     #[pyo3(get)]
+    pub anchor_id: String,
+    /// ``unit``, ``tuple`` or ``struct``
+    #[pyo3(get)]
+    pub fields_kind: String,
+    #[pyo3(get)]
     pub docstring: String,
     #[pyo3(get)]
+    pub doc_aliases: Vec<String>,
+    #[pyo3(get)]
     pub fields: Vec<Field>,
+    #[pyo3(get)]
+    pub deref_target: Option<Vec<TypeSegment>>,
+    #[pyo3(get)]
+    pub recursive: bool,
+    #[pyo3(get)]
+    pub cfg_info: Option<String>,
+    /// The headings of the docstring, for a table of contents
+    #[pyo3(get)]
+    pub toc: Vec<Heading>,
 }
 
 #[pymethods]
@@ -142,9 +228,18 @@ impl Struct {
 impl From<data_model::Struct> for Struct {
     fn from(module: data_model::Struct) -> Self {
         Struct {
+            anchor_id: module.anchor_id(),
+            toc: toc(&module.docstring),
+            fields_kind: variant_kind(module.fields_kind),
             path: module.path,
             docstring: module.docstring,
+            doc_aliases: module.doc_aliases,
             fields: module.fields.into_iter().map(Field::from).collect(),
+            deref_target: module
+                .deref_target
+                .map(|target| target.into_iter().map(TypeSegment::from).collect()),
+            recursive: module.recursive,
+            cfg_info: module.cfg_info,
         }
     }
 }
@@ -156,9 +251,24 @@ pub struct Enum {
     #[pyo3(get)]
     pub path: Vec<String>,
     #[pyo3(get)]
+    pub anchor_id: String,
+    #[pyo3(get)]
     pub docstring: String,
     #[pyo3(get)]
+    pub doc_aliases: Vec<String>,
+    #[pyo3(get)]
     pub variants: Vec<Variant>,
+    #[pyo3(get)]
+    pub is_c_like: bool,
+    #[pyo3(get)]
+    pub deref_target: Option<Vec<TypeSegment>>,
+    #[pyo3(get)]
+    pub recursive: bool,
+    #[pyo3(get)]
+    pub cfg_info: Option<String>,
+    /// The headings of the docstring, for a table of contents
+    #[pyo3(get)]
+    pub toc: Vec<Heading>,
 }
 
 #[pymethods]
@@ -179,9 +289,18 @@ impl Enum {
 impl From<data_model::Enum> for Enum {
     fn from(module: data_model::Enum) -> Self {
         Enum {
+            anchor_id: module.anchor_id(),
+            toc: toc(&module.docstring),
+            is_c_like: module.is_c_like(),
             path: module.path,
             docstring: module.docstring,
+            doc_aliases: module.doc_aliases,
             variants: module.variants.into_iter().map(Variant::from).collect(),
+            deref_target: module
+                .deref_target
+                .map(|target| target.into_iter().map(TypeSegment::from).collect()),
+            recursive: module.recursive,
+            cfg_info: module.cfg_info,
         }
     }
 }
@@ -195,8 +314,17 @@ pub struct Variant {
     #[pyo3(get)]
     pub docstring: String,
     // TODO discriminant
+    /// ``unit``, ``tuple`` or ``struct``
+    #[pyo3(get)]
+    pub fields_kind: String,
     #[pyo3(get)]
     pub fields: Vec<Field>,
+    /// The name the variant is serialized as, from ``#[serde(rename = "...")]``
+    #[pyo3(get)]
+    pub serde_rename: Option<String>,
+    /// The arguments of the ``#[serde(...)]`` attributes of the variant, as written
+    #[pyo3(get)]
+    pub serde_arguments: Vec<String>,
 }
 
 #[pymethods]
@@ -219,11 +347,73 @@ impl From<data_model::Variant> for Variant {
         Variant {
             path: var.path,
             docstring: var.docstring,
+            fields_kind: variant_kind(var.fields_kind),
             fields: var.fields.into_iter().map(Field::from).collect(),
+            serde_rename: var.serde.as_ref().and_then(|s| s.rename.clone()),
+            serde_arguments: var.serde.map(|s| s.arguments).unwrap_or_default(),
         }
     }
 }
 
+/// The name of the kind of a cargo target
+fn target_kind(kind: data_model::TargetKind) -> String {
+    match kind {
+        data_model::TargetKind::Example => "example",
+        data_model::TargetKind::Test => "test",
+        data_model::TargetKind::Bench => "bench",
+    }
+    .to_string()
+}
+
+/// The name of the form of the fields of an enum variant or struct
+fn variant_kind(kind: data_model::VariantKind) -> String {
+    match kind {
+        data_model::VariantKind::Unit => "unit",
+        data_model::VariantKind::Tuple => "tuple",
+        data_model::VariantKind::Struct => "struct",
+    }
+    .to_string()
+}
+
+#[pyclass]
+#[derive(Clone)]
+/// pyo3 representation of a docstring heading, as an entry of a table of contents
+pub struct Heading {
+    /// The heading level, from 1 to 6
+    #[pyo3(get)]
+    pub level: usize,
+    #[pyo3(get)]
+    pub text: String,
+    /// The anchor of the heading, unique within the docstring
+    #[pyo3(get)]
+    pub slug: String,
+}
+
+#[pymethods]
+impl Heading {
+    pub fn __repr__(&self) -> String {
+        format!("Heading({}, {:?})", self.level, self.text)
+    }
+}
+
+impl From<data_model::Heading> for Heading {
+    fn from(heading: data_model::Heading) -> Self {
+        Heading {
+            level: heading.level,
+            text: heading.text,
+            slug: heading.slug,
+        }
+    }
+}
+
+/// The table of contents of a docstring
+fn toc(docstring: &str) -> Vec<Heading> {
+    table_of_contents(docstring)
+        .into_iter()
+        .map(Heading::from)
+        .collect()
+}
+
 #[pyclass]
 #[derive(Clone)]
 /// pyo3 representation of a segment of a type
@@ -233,6 +423,9 @@ pub struct TypeSegment {
     pub content: String,
     #[pyo3(get)]
     pub is_path: bool,
+    /// The documentation URL of a path to an item of an external crate
+    #[pyo3(get)]
+    pub url: Option<String>,
 }
 
 #[pymethods]
@@ -252,10 +445,17 @@ impl From<data_model::TypeSegment> for TypeSegment {
             data_model::TypeSegment::Path(content) => TypeSegment {
                 content,
                 is_path: true,
+                url: None,
+            },
+            data_model::TypeSegment::ExternalPath { path, url } => TypeSegment {
+                content: path,
+                is_path: true,
+                url: Some(url),
             },
             data_model::TypeSegment::String(content) => TypeSegment {
                 content,
                 is_path: false,
+                url: None,
             },
         }
     }
@@ -268,7 +468,27 @@ pub struct Function {
     #[pyo3(get)]
     pub path: Vec<String>,
     #[pyo3(get)]
+    pub anchor_id: String,
+    #[pyo3(get)]
     pub docstring: String,
+    #[pyo3(get)]
+    pub doc_aliases: Vec<String>,
+    #[pyo3(get)]
+    pub signature: Vec<TypeSegment>,
+    #[pyo3(get)]
+    pub resolved_signature: Vec<TypeSegment>,
+    /// ``free``, ``associated_function`` or ``method``
+    #[pyo3(get)]
+    pub function_kind: String,
+    #[pyo3(get)]
+    pub generic_params: Vec<String>,
+    #[pyo3(get)]
+    pub used_generic_params: Vec<String>,
+    #[pyo3(get)]
+    pub cfg_info: Option<String>,
+    /// The headings of the docstring, for a table of contents
+    #[pyo3(get)]
+    pub toc: Vec<Heading>,
 }
 
 #[pymethods]
@@ -286,11 +506,46 @@ impl Function {
     }
 }
 
+/// The name of the classification of a function
+fn function_kind(kind: data_model::FunctionKind) -> String {
+    match kind {
+        data_model::FunctionKind::Free => "free",
+        data_model::FunctionKind::AssociatedFunction => "associated_function",
+        data_model::FunctionKind::Method => "method",
+    }
+    .to_string()
+}
+
 impl From<data_model::Function> for Function {
     fn from(field: data_model::Function) -> Self {
+        // the signature as written in the source is kept if the analysis was asked to preserve it
+        let signature = match &field.source_signature {
+            Some(source) => vec![TypeSegment::from(data_model::TypeSegment::String(
+                source.clone(),
+            ))],
+            None => field
+                .signature()
+                .into_iter()
+                .map(TypeSegment::from)
+                .collect(),
+        };
+        let resolved_signature = field
+            .resolved_signature()
+            .into_iter()
+            .map(TypeSegment::from)
+            .collect();
         Function {
+            anchor_id: field.anchor_id(),
+            toc: toc(&field.docstring),
+            resolved_signature,
+            function_kind: function_kind(field.kind()),
+            generic_params: field.generics.param_names(),
+            used_generic_params: field.used_generic_params(),
+            cfg_info: field.cfg_info,
             path: field.path,
             docstring: field.docstring,
+            doc_aliases: field.doc_aliases,
+            signature,
         }
     }
 }
@@ -48,6 +48,20 @@ pub fn load_crate(cache_path: &str, name: &str) -> PyResult<Option<Crate>> {
     Ok(Some(crate_.into()))
 }
 
+#[pyfunction]
+/// load the sorted public item paths of a crate from the cache, if they exist
+pub fn load_public_paths(cache_path: &str, name: &str) -> PyResult<Option<Vec<String>>> {
+    let path = std::path::Path::new(cache_path)
+        .join("crates")
+        .join(format!("{}.paths.json", name));
+    if !path.exists() {
+        return Ok(None);
+    }
+    let contents = read_file(&path)?;
+    let paths: Vec<String> = deserialize_object(name, &contents)?;
+    Ok(Some(paths))
+}
+
 #[pyfunction]
 /// load a module from the cache, if it exists
 pub fn load_module(cache_path: &str, full_name: &str) -> PyResult<Option<Module>> {
@@ -105,15 +119,8 @@ pub fn load_function(cache_path: &str, full_name: &str) -> PyResult<Option<Funct
 }
 
 /// Check if a path is a child of a given parent, and return the fully qualified name of the child.
-fn is_child(path: &std::path::PathBuf, parent: &Vec<String>) -> Option<String> {
-    let name = match path.file_stem() {
-        Some(name) => name,
-        None => return None,
-    };
-    let name = match name.to_str() {
-        Some(name) => name,
-        None => return None,
-    };
+fn is_child(path: &std::path::Path, parent: &[String]) -> Option<String> {
+    let name = path.file_stem()?.to_str()?;
     let name_path = name.split("::").collect::<Vec<_>>();
     if name_path.len() != parent.len() + 1 {
         return None;
@@ -215,11 +222,7 @@ pub fn load_child_functions(cache_path: &str, parent: Vec<String>) -> PyResult<V
 }
 
 /// Check if a path is an ancestor of a given parent, and return the fully qualified name of the child.
-fn is_ancestor(
-    path: &std::path::PathBuf,
-    parent: &Vec<String>,
-    include_self: bool,
-) -> Option<String> {
+fn is_ancestor(path: &std::path::Path, parent: &[String], include_self: bool) -> Option<String> {
     let name = path.file_stem()?.to_str()?;
     let name_path = name.split("::").collect::<Vec<_>>();
     if include_self && name_path == parent.iter().map(|s| s.as_str()).collect::<Vec<_>>() {
@@ -9,7 +9,13 @@
 //! We need to integrate Sphinx with Rust so that we can use the `sphinx_rust` backend to generate documentation for Rust code.
 //! ```
 
-use pyo3::{exceptions::PyIOError, prelude::*};
+use std::time::Duration;
+
+use pyo3::{
+    exceptions::{PyIOError, PyTypeError, PyValueError},
+    prelude::*,
+    types::PyDict,
+};
 
 use analyzer::analyze;
 
@@ -23,16 +29,19 @@ pub mod data_query;
 fn sphinx_rust(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add("__version__", env!("CARGO_PKG_VERSION"))?;
     m.add_function(wrap_pyfunction!(analyze_crate, m)?)?;
+    m.add_function(wrap_pyfunction!(strip_hidden_lines, m)?)?;
     m.add_class::<data_model::Crate>()?;
     m.add_class::<data_model::Module>()?;
     m.add_class::<data_model::Struct>()?;
     m.add_class::<data_model::Field>()?;
     m.add_class::<data_model::TypeSegment>()?;
+    m.add_class::<data_model::Heading>()?;
     m.add_class::<data_model::Enum>()?;
     m.add_class::<data_model::Variant>()?;
     m.add_class::<data_model::Function>()?;
     m.add_class::<AnalysisResult>()?;
     m.add_function(wrap_pyfunction!(data_query::load_crate, m)?)?;
+    m.add_function(wrap_pyfunction!(data_query::load_public_paths, m)?)?;
     m.add_function(wrap_pyfunction!(data_query::load_module, m)?)?;
     m.add_function(wrap_pyfunction!(data_query::load_struct, m)?)?;
     m.add_function(wrap_pyfunction!(data_query::load_enum, m)?)?;
@@ -48,8 +57,15 @@ fn sphinx_rust(m: &Bound<'_, PyModule>) -> PyResult<()> {
 }
 
 #[pyfunction]
+#[pyo3(signature = (crate_path, cache_path, **options))]
 /// analyse a crate and cache the results to disk
-pub fn analyze_crate(crate_path: &str, cache_path: &str) -> PyResult<AnalysisResult> {
+///
+/// the keyword arguments are the options of the analysis, see ``analysis_options``
+pub fn analyze_crate(
+    crate_path: &str,
+    cache_path: &str,
+    options: Option<&Bound<'_, PyDict>>,
+) -> PyResult<AnalysisResult> {
     // check that the cache path is a directory
     let cache_path = std::path::Path::new(cache_path);
     if !cache_path.is_dir() {
@@ -60,7 +76,8 @@ pub fn analyze_crate(crate_path: &str, cache_path: &str) -> PyResult<AnalysisRes
     }
 
     // perform the analysis
-    let result = match analyze::analyze_crate(crate_path) {
+    let options = analysis_options(options)?;
+    let result = match analyze::analyze_crate(crate_path, &options) {
         Ok(result) => result,
         Err(err) => {
             return Err(PyIOError::new_err(format!(
@@ -73,7 +90,11 @@ pub fn analyze_crate(crate_path: &str, cache_path: &str) -> PyResult<AnalysisRes
         }
     };
 
-    let mut output = AnalysisResult::default();
+    let mut output = AnalysisResult {
+        excluded_modules: result.excluded_modules.clone(),
+        warnings: result.warnings.iter().map(|w| w.to_string()).collect(),
+        ..Default::default()
+    };
 
     // now cache the results
     // note we don't write to disk, if the file already exists and has the same contents
@@ -86,6 +107,8 @@ pub fn analyze_crate(crate_path: &str, cache_path: &str) -> PyResult<AnalysisRes
     output.crate_ = result.crate_.name.clone();
     let crate_path = crates_path.join(format!("{}.json", result.crate_.name));
     serialize_to_file(&crate_path, &result.crate_)?;
+    let paths_path = crates_path.join(format!("{}.paths.json", result.crate_.name));
+    serialize_to_file(&paths_path, &result.public_paths())?;
 
     let modules_path = cache_path.join("modules");
     if !modules_path.exists() {
@@ -126,6 +149,66 @@ pub fn analyze_crate(crate_path: &str, cache_path: &str) -> PyResult<AnalysisRes
     Ok(output)
 }
 
+/// Map the keyword arguments of ``analyze_crate`` to the options of the analysis,
+/// by the names of their fields, failing on an unknown option or a value of the wrong type
+fn analysis_options(kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<analyze::AnalysisOptions> {
+    let mut options = analyze::AnalysisOptions::default();
+    let Some(kwargs) = kwargs else {
+        return Ok(options);
+    };
+    for (key, value) in kwargs.iter() {
+        let key: String = key.extract()?;
+        match key.as_str() {
+            "exclude_patterns" => options.exclude_patterns = extract(&key, &value)?,
+            "cfg" => options.cfg = extract(&key, &value)?,
+            "crate_name_override" => options.crate_name_override = extract(&key, &value)?,
+            "strict" => options.strict = extract(&key, &value)?,
+            "changed_files" => options.changed_files = extract(&key, &value)?,
+            "preserve_symlinks" => options.preserve_symlinks = extract(&key, &value)?,
+            "expand_type_aliases" => options.expand_type_aliases = extract(&key, &value)?,
+            "metadata_timeout" => {
+                options.metadata_timeout = extract::<Option<f64>>(&key, &value)?
+                    .map(Duration::try_from_secs_f64)
+                    .transpose()
+                    .map_err(|err| {
+                        PyValueError::new_err(format!(
+                            "invalid value for option '{}': {}",
+                            key, err
+                        ))
+                    })?
+            }
+            "warn_undocumented" => options.warn_undocumented = extract(&key, &value)?,
+            "warn_missing_safety" => options.warn_missing_safety = extract(&key, &value)?,
+            "include_examples" => options.include_examples = extract(&key, &value)?,
+            "include_tests" => options.include_tests = extract(&key, &value)?,
+            "include_benches" => options.include_benches = extract(&key, &value)?,
+            "preserve_signature_formatting" => {
+                options.preserve_signature_formatting = extract(&key, &value)?
+            }
+            "full_initializers" => options.full_initializers = extract(&key, &value)?,
+            "hide_phantom_fields" => options.hide_phantom_fields = extract(&key, &value)?,
+            "detect_recursive_types" => options.detect_recursive_types = extract(&key, &value)?,
+            "warn_broken_examples" => options.warn_broken_examples = extract(&key, &value)?,
+            "external_docs" => options.external_docs = extract(&key, &value)?,
+            "link_docs_rs" => options.link_docs_rs = extract(&key, &value)?,
+            _ => {
+                return Err(PyTypeError::new_err(format!(
+                    "analyze_crate() got an unexpected keyword argument '{}'",
+                    key
+                )))
+            }
+        }
+    }
+    Ok(options)
+}
+
+/// Extract the value of an option of ``analyze_crate``, naming the option if it has the wrong type
+fn extract<'py, T: FromPyObject<'py>>(key: &str, value: &Bound<'py, PyAny>) -> PyResult<T> {
+    value
+        .extract()
+        .map_err(|err| PyTypeError::new_err(format!("invalid value for option '{}': {}", key, err)))
+}
+
 #[pyclass]
 #[derive(Debug, Clone, Default)]
 /// pyo3 representation of the result of an analysis
@@ -140,6 +223,10 @@ pub struct AnalysisResult {
     pub enums: Vec<String>,
     #[pyo3(get)]
     pub functions: Vec<String>,
+    #[pyo3(get)]
+    pub excluded_modules: Vec<String>,
+    #[pyo3(get)]
+    pub warnings: Vec<String>,
 }
 
 #[pymethods]
@@ -152,6 +239,12 @@ impl AnalysisResult {
     }
 }
 
+#[pyfunction]
+/// remove the lines hidden with a leading ``# `` from the Rust code blocks of a Markdown docstring
+pub fn strip_hidden_lines(markdown: &str) -> String {
+    analyze::docstring::strip_hidden_lines(markdown)
+}
+
 /// Serialize a value to a file.
 /// The file is only written if the value is different from any existing value.
 fn serialize_to_file<T>(path: &std::path::Path, value: &T) -> PyResult<()>
@@ -168,14 +261,11 @@ where
         }
     };
     if path.exists() {
-        match std::fs::read_to_string(path) {
-            Ok(old_value) => {
-                if value == old_value {
-                    return Ok(());
-                }
+        if let Ok(old_value) = std::fs::read_to_string(path) {
+            if value == old_value {
+                return Ok(());
             }
-            Err(_) => {}
-        };
+        }
     }
     match std::fs::write(path, value) {
         Err(err) => Err(PyIOError::new_err(format!(
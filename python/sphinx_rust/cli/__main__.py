@@ -22,6 +22,90 @@ def main() -> None:
         help="Overwrite the output directory if it exists",
     )
 
+    parser.add_argument(
+        "--crate-name-override",
+        metavar="NAME",
+        help="Name to use as the first path segment of all modules and items",
+    )
+    parser.add_argument(
+        "--changed-file",
+        metavar="PATH",
+        action="append",
+        dest="changed_files",
+        help="Only emit the items declared in this file (may be repeated)",
+    )
+    parser.add_argument(
+        "--metadata-timeout",
+        metavar="SECONDS",
+        type=float,
+        help="Seconds to wait for 'cargo metadata' before it is retried",
+    )
+    parser.add_argument(
+        "--strict",
+        action="store_true",
+        help="Fail if any module cannot be found or parsed",
+    )
+    parser.add_argument(
+        "--preserve-symlinks",
+        action="store_true",
+        help="Do not resolve symlinks in the crate path",
+    )
+    parser.add_argument(
+        "--warn-undocumented",
+        action="store_true",
+        help="Warn for each public module and item without a docstring",
+    )
+    parser.add_argument(
+        "--warn-missing-safety",
+        action="store_true",
+        help="Warn for each public unsafe function, method or trait without a '# Safety' section",
+    )
+    parser.add_argument(
+        "--include-examples",
+        action="store_true",
+        help="Also analyze the example targets of the crate",
+    )
+    parser.add_argument(
+        "--include-tests",
+        action="store_true",
+        help="Also analyze the integration test targets of the crate",
+    )
+    parser.add_argument(
+        "--include-benches",
+        action="store_true",
+        help="Also analyze the benchmark targets of the crate",
+    )
+    parser.add_argument(
+        "--preserve-signature-formatting",
+        action="store_true",
+        help="Render function signatures as written in the source",
+    )
+    parser.add_argument(
+        "--full-initializers",
+        action="store_true",
+        help="Keep the full initializers of constants and statics",
+    )
+    parser.add_argument(
+        "--hide-phantom-fields",
+        action="store_true",
+        help="Drop the PhantomData marker fields of structs and enum variants",
+    )
+    parser.add_argument(
+        "--detect-recursive-types",
+        action="store_true",
+        help="Mark the structs and enums whose fields reference the type itself",
+    )
+    parser.add_argument(
+        "--warn-broken-examples",
+        action="store_true",
+        help="Warn for each Rust code example in the docstrings that does not parse",
+    )
+    parser.add_argument(
+        "--link-docs-rs",
+        action="store_true",
+        help="Link the paths to items of external crates in types to docs.rs",
+    )
+
     args = parser.parse_args()
     output = Path(args.output).resolve()
     if output.exists() and not args.overwrite:
@@ -30,7 +114,26 @@ def main() -> None:
         )
         sys.exit(1)
     output.mkdir(parents=True, exist_ok=True)
-    result = analyze_crate(args.crate, str(output))
+    result = analyze_crate(
+        args.crate,
+        str(output),
+        crate_name_override=args.crate_name_override,
+        strict=args.strict,
+        changed_files=args.changed_files,
+        preserve_symlinks=args.preserve_symlinks,
+        metadata_timeout=args.metadata_timeout,
+        warn_undocumented=args.warn_undocumented,
+        warn_missing_safety=args.warn_missing_safety,
+        include_examples=args.include_examples,
+        include_tests=args.include_tests,
+        include_benches=args.include_benches,
+        preserve_signature_formatting=args.preserve_signature_formatting,
+        full_initializers=args.full_initializers,
+        hide_phantom_fields=args.hide_phantom_fields,
+        detect_recursive_types=args.detect_recursive_types,
+        warn_broken_examples=args.warn_broken_examples,
+        link_docs_rs=args.link_docs_rs,
+    )
     print("Written analysis to", output)  # noqa: T201
     print(result)  # noqa: T201
 
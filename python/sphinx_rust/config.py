@@ -14,6 +14,27 @@ class RustConfig:
     rust_crates: list[str]
     rust_doc_formats: dict[str, str]
     rust_viewcode: bool
+    rust_exclude_patterns: list[str]
+    rust_cfg: list[str]
+    rust_crate_name_override: str | None
+    rust_strict: bool
+    rust_changed_files: list[str] | None
+    rust_preserve_symlinks: bool
+    rust_expand_type_aliases: bool
+    rust_metadata_timeout: float | None
+    rust_warn_undocumented: bool
+    rust_warn_missing_safety: bool
+    rust_include_examples: bool
+    rust_include_tests: bool
+    rust_include_benches: bool
+    rust_preserve_signature_formatting: bool
+    rust_full_initializers: bool
+    rust_hide_phantom_fields: bool
+    rust_detect_recursive_types: bool
+    rust_warn_broken_examples: bool
+    rust_external_docs: dict[str, str]
+    rust_link_docs_rs: bool
+    rust_hide_doctest_lines: bool
 
     @classmethod
     def from_app(cls, app: Sphinx) -> RustConfig:
@@ -22,6 +43,27 @@ class RustConfig:
             rust_crates=app.config.rust_crates,
             rust_doc_formats=app.config.rust_doc_formats,
             rust_viewcode=app.config.rust_viewcode,
+            rust_exclude_patterns=app.config.rust_exclude_patterns,
+            rust_cfg=app.config.rust_cfg,
+            rust_crate_name_override=app.config.rust_crate_name_override,
+            rust_strict=app.config.rust_strict,
+            rust_changed_files=app.config.rust_changed_files,
+            rust_preserve_symlinks=app.config.rust_preserve_symlinks,
+            rust_expand_type_aliases=app.config.rust_expand_type_aliases,
+            rust_metadata_timeout=app.config.rust_metadata_timeout,
+            rust_warn_undocumented=app.config.rust_warn_undocumented,
+            rust_warn_missing_safety=app.config.rust_warn_missing_safety,
+            rust_include_examples=app.config.rust_include_examples,
+            rust_include_tests=app.config.rust_include_tests,
+            rust_include_benches=app.config.rust_include_benches,
+            rust_preserve_signature_formatting=app.config.rust_preserve_signature_formatting,
+            rust_full_initializers=app.config.rust_full_initializers,
+            rust_hide_phantom_fields=app.config.rust_hide_phantom_fields,
+            rust_detect_recursive_types=app.config.rust_detect_recursive_types,
+            rust_warn_broken_examples=app.config.rust_warn_broken_examples,
+            rust_external_docs=app.config.rust_external_docs,
+            rust_link_docs_rs=app.config.rust_link_docs_rs,
+            rust_hide_doctest_lines=app.config.rust_hide_doctest_lines,
         )
 
     @staticmethod
@@ -30,3 +72,24 @@ class RustConfig:
         app.add_config_value("rust_crates", [], "env")
         app.add_config_value("rust_doc_formats", {}, "env")
         app.add_config_value("rust_viewcode", True, "env")
+        app.add_config_value("rust_exclude_patterns", [], "env")
+        app.add_config_value("rust_cfg", [], "env")
+        app.add_config_value("rust_crate_name_override", None, "env")
+        app.add_config_value("rust_strict", False, "env")
+        app.add_config_value("rust_changed_files", None, "env")
+        app.add_config_value("rust_preserve_symlinks", False, "env")
+        app.add_config_value("rust_expand_type_aliases", False, "env")
+        app.add_config_value("rust_metadata_timeout", None, "env")
+        app.add_config_value("rust_warn_undocumented", False, "env")
+        app.add_config_value("rust_warn_missing_safety", False, "env")
+        app.add_config_value("rust_include_examples", False, "env")
+        app.add_config_value("rust_include_tests", False, "env")
+        app.add_config_value("rust_include_benches", False, "env")
+        app.add_config_value("rust_preserve_signature_formatting", False, "env")
+        app.add_config_value("rust_full_initializers", False, "env")
+        app.add_config_value("rust_hide_phantom_fields", False, "env")
+        app.add_config_value("rust_detect_recursive_types", False, "env")
+        app.add_config_value("rust_warn_broken_examples", False, "env")
+        app.add_config_value("rust_external_docs", {}, "env")
+        app.add_config_value("rust_link_docs_rs", False, "env")
+        app.add_config_value("rust_hide_doctest_lines", True, "env")
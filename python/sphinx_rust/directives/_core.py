@@ -9,6 +9,7 @@ from sphinx.util.docutils import LoggingReporter, SphinxDirective
 from sphinx.util.logging import getLogger
 
 from sphinx_rust.config import RustConfig
+from sphinx_rust.sphinx_rust import strip_hidden_lines
 
 if TYPE_CHECKING:
     from sphinx.environment import BuildEnvironment
@@ -107,6 +108,13 @@ def create_summary_table(
     return table
 
 
+def create_alias_index(aliases: list[str], node_id: str) -> addnodes.index:
+    """Create index entries for the search aliases of an item."""
+    return addnodes.index(
+        entries=[("single", alias, node_id, "", None) for alias in aliases]
+    )
+
+
 class DocstringItem(Protocol):
     """An item with a docstring."""
 
@@ -146,6 +154,9 @@ def parse_docstring(
         return []
 
     docstring = item.docstring if docstring is None else docstring
+    if config.rust_hide_doctest_lines and parser_type != "restructuredtext":
+        # fenced code blocks are Markdown, so hidden lines only apply to those formats
+        docstring = strip_hidden_lines(docstring)
 
     source_path = str(
         env.doc2path(  # TODO this actually should be the rust file path
@@ -220,7 +231,11 @@ def type_segs_to_nodes(segs: list[TypeSegment]) -> list[nodes.Node]:
     """Convert a list of type segments to nodes."""
     nodes_: list[nodes.Node] = []
     for seg in segs:
-        if seg.is_path:
+        if seg.url is not None:
+            ref = nodes.reference("", "", refuri=seg.url, internal=False)
+            ref += nodes.strong("", seg.content)
+            nodes_.append(ref)
+        elif seg.is_path:
             # TODO create cross-reference
             nodes_.append(nodes.strong("", seg.content))
         else:
@@ -65,6 +65,10 @@ class RustCrateAutoDirective(RustAutoDirective):
 
         # root += create_field_list(([nodes.Text("Version")], [nodes.Text(crate.version)]))
         root += nodes.paragraph("", f"Version: {crate.version}")
+        if crate.no_std_condition:
+            root += nodes.paragraph("", f"no_std when: {crate.no_std_condition}")
+        elif crate.no_std:
+            root += nodes.paragraph("", "no_std")
 
         desc = addnodes.desc()
         root += desc
@@ -1,18 +1,24 @@
 from __future__ import annotations
 
+from typing import TYPE_CHECKING
+
 from docutils import nodes
 from sphinx import addnodes
 from sphinx.util.logging import getLogger
-from sphinx.util.nodes import make_id
 
 from sphinx_rust.sphinx_rust import load_enum
 
 from ._core import (
     RustAutoDirective,
+    create_alias_index,
     create_field_list,
     parse_docstring,
+    type_segs_to_nodes,
 )
 
+if TYPE_CHECKING:
+    from sphinx_rust.sphinx_rust import Variant
+
 LOGGER = getLogger(__name__)
 
 
@@ -49,14 +55,11 @@ class RustEnumAutoDirective(RustAutoDirective):
         desc = addnodes.desc()
         root += desc
         if enum.variants:
-            sig_lines = [addnodes.desc_signature_line("", f"pub struct {enum.name} {{")]
-            for var in enum.variants:
-                # TODO types
-                sig_lines.append(  # noqa: PERF401
-                    addnodes.desc_signature_line(
-                        "", f"    {var.name}(...),"
-                    )  # TODO properly print variant
-                )
+            sig_lines = [addnodes.desc_signature_line("", f"pub enum {enum.name} {{")]
+            sig_lines.extend(
+                addnodes.desc_signature_line("", "", *variant_signature(var))
+                for var in enum.variants
+            )
             sig_lines.append(addnodes.desc_signature_line("", "}"))
             signature = addnodes.desc_signature(enum.path_str, "", *sig_lines)
             signature["is_multiline"] = True
@@ -67,10 +70,12 @@ class RustEnumAutoDirective(RustAutoDirective):
         desc += signature
         # TODO add variants to signature
         # desc += addnodes.desc_content("", nodes.paragraph("", ))
-        node_id = make_id(self.env, self.doc, "", enum.path_str)
+        node_id = enum.anchor_id
         signature["ids"].append(node_id)
         self.doc.note_explicit_target(signature)
         self.rust_domain.note_object(enum.path_str, "enum", node_id, signature)
+        if enum.doc_aliases:
+            root.insert(0, create_alias_index(enum.doc_aliases, node_id))
 
         if enum.docstring:
             root += parse_docstring(self.env, self.doc, enum)
@@ -78,15 +83,57 @@ class RustEnumAutoDirective(RustAutoDirective):
         if enum.variants:
             section = self.create_section("Variants")
             root += section
-            # TODO document variant fields
             section += create_field_list(
                 [
                     (
                         [nodes.Text(var.name)],
-                        parse_docstring(self.env, self.doc, var),
+                        [
+                            *parse_docstring(self.env, self.doc, var),
+                            *self.variant_fields(var),
+                        ],
                     )
                     for var in enum.variants
                 ]
             )
 
         return root.children
+
+    def variant_fields(self, var: Variant) -> list[nodes.Node]:
+        """Create a field list of the named fields of a struct-like variant."""
+        if var.fields_kind != "struct" or not var.fields:
+            return []
+        return [
+            create_field_list(
+                [
+                    (
+                        [
+                            nodes.Text(field.name),
+                            *([nodes.emphasis("", " (phantom)")] if field.phantom else []),
+                        ],
+                        [
+                            nodes.paragraph("", "", *type_segs_to_nodes(field.type_)),
+                            *parse_docstring(self.env, self.doc, field),
+                        ],
+                    )
+                    for field in var.fields
+                ]
+            )
+        ]
+
+
+def variant_signature(var: Variant) -> list[nodes.Node]:
+    """Render a variant as declared, e.g. ``Point { x: i32, y: i32 }`` or ``Some(T)``."""
+    if var.fields_kind == "unit":
+        return [nodes.Text(f"    {var.name},")]
+    if not var.fields:
+        return [nodes.Text(f"    {var.name}{'()' if var.fields_kind == 'tuple' else ' {}'},")]
+    tuple_ = var.fields_kind == "tuple"
+    output: list[nodes.Node] = [nodes.Text(f"    {var.name}{'(' if tuple_ else ' { '}")]
+    for i, field in enumerate(var.fields):
+        if i > 0:
+            output.append(nodes.Text(", "))
+        if not tuple_:
+            output.append(nodes.Text(f"{field.name}: "))
+        output.extend(type_segs_to_nodes(field.type_))
+    output.append(nodes.Text("),") if tuple_ else nodes.Text(" },"))
+    return output
@@ -3,13 +3,14 @@ from __future__ import annotations
 from docutils import nodes
 from sphinx import addnodes
 from sphinx.util.logging import getLogger
-from sphinx.util.nodes import make_id
 
 from sphinx_rust.sphinx_rust import load_function
 
 from ._core import (
     RustAutoDirective,
+    create_alias_index,
     parse_docstring,
+    type_segs_to_nodes,
 )
 
 LOGGER = getLogger(__name__)
@@ -49,15 +50,17 @@ class RustFunctionAutoDirective(RustAutoDirective):
         root += desc
         signature = addnodes.desc_signature(
             func.path_str,
-            f"pub fn {func.name}(...);",
+            "pub ",
+            *type_segs_to_nodes(func.signature),
+            nodes.Text(";"),
         )
         desc += signature
-        # TODO add inputs / outputs to signature
-        # desc += addnodes.desc_content("", nodes.paragraph("", ))
-        node_id = make_id(self.env, self.doc, "", func.path_str)
+        node_id = func.anchor_id
         signature["ids"].append(node_id)
         self.doc.note_explicit_target(signature)
         self.rust_domain.note_object(func.path_str, "function", node_id, signature)
+        if func.doc_aliases:
+            root.insert(0, create_alias_index(func.doc_aliases, node_id))
 
         if func.docstring:
             root += parse_docstring(self.env, self.doc, func)
@@ -5,7 +5,6 @@ from typing import TYPE_CHECKING
 from docutils import nodes
 from sphinx import addnodes
 from sphinx.util.logging import getLogger
-from sphinx.util.nodes import make_id
 
 from sphinx_rust.sphinx_rust import (
     load_child_enums,
@@ -64,7 +63,7 @@ class RustModuleAutoDirective(RustAutoDirective):
         root += desc
         signature = addnodes.desc_signature(module.path_str, f"pub mod {module.name};")
         desc += signature
-        node_id = make_id(self.env, self.doc, "", module.path_str)
+        node_id = module.anchor_id
         signature["ids"].append(node_id)
         self.doc.note_explicit_target(signature)
         self.rust_domain.note_object(module.path_str, "module", node_id, signature)
@@ -3,12 +3,12 @@ from __future__ import annotations
 from docutils import nodes
 from sphinx import addnodes
 from sphinx.util.logging import getLogger
-from sphinx.util.nodes import make_id
 
 from sphinx_rust.sphinx_rust import load_struct
 
 from ._core import (
     RustAutoDirective,
+    create_alias_index,
     create_field_list,
     parse_docstring,
     type_segs_to_nodes,
@@ -49,15 +49,14 @@ class RustStructAutoDirective(RustAutoDirective):
 
         desc = addnodes.desc()
         root += desc
-        if struct.fields:
+        if struct.fields_kind == "struct" and struct.fields:
             sig_lines = [
                 addnodes.desc_signature_line("", f"pub struct {struct.name} {{")
             ]
-            # TODO properly print structs with tuple fields
             sig_lines.extend(
                 addnodes.desc_signature_line(
                     "",
-                    f"    pub {field.name}: ",
+                    f"    {'pub ' if field.public else ''}{field.name}: ",
                     *type_segs_to_nodes(field.type_),
                     nodes.Text(","),
                 )
@@ -66,18 +65,39 @@ class RustStructAutoDirective(RustAutoDirective):
             sig_lines.append(addnodes.desc_signature_line("", "}"))
             signature = addnodes.desc_signature(struct.path_str, "", *sig_lines)
             signature["is_multiline"] = True
-        else:
+        elif struct.fields_kind == "tuple" and struct.fields:
+            sig_nodes: list[nodes.Node] = [nodes.Text(f"pub struct {struct.name}(")]
+            for i, field in enumerate(struct.fields):
+                if i > 0:
+                    sig_nodes.append(nodes.Text(", "))
+                if field.public:
+                    sig_nodes.append(nodes.Text("pub "))
+                sig_nodes.extend(type_segs_to_nodes(field.type_))
+            sig_nodes.append(nodes.Text(");"))
+            signature = addnodes.desc_signature(struct.path_str, "", *sig_nodes)
+        elif struct.fields_kind == "unit":
+            signature = addnodes.desc_signature(
+                struct.path_str, f"pub struct {struct.name};"
+            )
+        elif struct.fields_kind == "tuple":
             signature = addnodes.desc_signature(
                 struct.path_str,
                 f"pub struct {struct.name}(/* private fields */);",
             )
+        else:
+            signature = addnodes.desc_signature(
+                struct.path_str,
+                f"pub struct {struct.name} {{ /* private fields */ }}",
+            )
         desc += signature
         # TODO add fields to signature
         # desc += addnodes.desc_content("", nodes.paragraph("", ))
-        node_id = make_id(self.env, self.doc, "", struct.path_str)
+        node_id = struct.anchor_id
         signature["ids"].append(node_id)
         self.doc.note_explicit_target(signature)
         self.rust_domain.note_object(struct.path_str, "struct", node_id, signature)
+        if struct.doc_aliases:
+            root.insert(0, create_alias_index(struct.doc_aliases, node_id))
 
         if struct.docstring:
             root += parse_docstring(self.env, self.doc, struct)
@@ -88,7 +108,11 @@ class RustStructAutoDirective(RustAutoDirective):
             section += create_field_list(
                 [
                     (
-                        [nodes.Text(field.name or str(i))],
+                        [
+                            nodes.Text(field.name or str(i)),
+                            *([] if field.public else [nodes.emphasis("", " (private)")]),
+                            *([nodes.emphasis("", " (phantom)")] if field.phantom else []),
+                        ],
                         [
                             nodes.paragraph("", "", *type_segs_to_nodes(field.type_)),
                             *parse_docstring(self.env, self.doc, field),
@@ -113,12 +113,39 @@ class RustDomain(Domain):
             # analyze the crate
             LOGGER.info(f"[rust] Analyzing crate: {path.resolve()!s}")
             try:
-                result = analyze_crate(str(path), str(cache))
+                result = analyze_crate(
+                    str(path),
+                    str(cache),
+                    exclude_patterns=config.rust_exclude_patterns,
+                    cfg=config.rust_cfg,
+                    crate_name_override=config.rust_crate_name_override,
+                    strict=config.rust_strict,
+                    changed_files=config.rust_changed_files,
+                    preserve_symlinks=config.rust_preserve_symlinks,
+                    expand_type_aliases=config.rust_expand_type_aliases,
+                    metadata_timeout=config.rust_metadata_timeout,
+                    warn_undocumented=config.rust_warn_undocumented,
+                    warn_missing_safety=config.rust_warn_missing_safety,
+                    include_examples=config.rust_include_examples,
+                    include_tests=config.rust_include_tests,
+                    include_benches=config.rust_include_benches,
+                    preserve_signature_formatting=config.rust_preserve_signature_formatting,
+                    full_initializers=config.rust_full_initializers,
+                    hide_phantom_fields=config.rust_hide_phantom_fields,
+                    detect_recursive_types=config.rust_detect_recursive_types,
+                    warn_broken_examples=config.rust_warn_broken_examples,
+                    external_docs=config.rust_external_docs,
+                    link_docs_rs=config.rust_link_docs_rs,
+                )
             except OSError as e:
+                if config.rust_strict:
+                    raise
                 LOGGER.warning(
                     f"Error analyzing crate: {e!s}", type="rust", subtype="analyze"
                 )
                 return
+            for warning in result.warnings:
+                LOGGER.warning(f"[rust] {warning}", type="rust", subtype="analyze")
             create_pages(srcdir, result)
             if config.rust_viewcode:
                 create_code_pages(result.crate_, srcdir, cache)
@@ -1,13 +1,78 @@
 from __future__ import annotations
 
+from typing import Literal
+
 __version__: str
 
-def analyze_crate(crate_path: str, cache_path: str) -> AnalysisResult:
+def analyze_crate(
+    crate_path: str,
+    cache_path: str,
+    *,
+    exclude_patterns: list[str] = ...,
+    cfg: list[str] = ...,
+    crate_name_override: str | None = ...,
+    strict: bool = ...,
+    changed_files: list[str] | None = ...,
+    preserve_symlinks: bool = ...,
+    expand_type_aliases: bool = ...,
+    metadata_timeout: float | None = ...,
+    warn_undocumented: bool = ...,
+    warn_missing_safety: bool = ...,
+    include_examples: bool = ...,
+    include_tests: bool = ...,
+    include_benches: bool = ...,
+    preserve_signature_formatting: bool = ...,
+    full_initializers: bool = ...,
+    hide_phantom_fields: bool = ...,
+    detect_recursive_types: bool = ...,
+    warn_broken_examples: bool = ...,
+    external_docs: dict[str, str] = ...,
+    link_docs_rs: bool = ...,
+) -> AnalysisResult:
     """Analyse a crate and cache the results to disk.
 
     :param crate_path: The path to the crate to analyse.
     :param cache_path: The path to the cache directory (must exist).
+    :param exclude_patterns: Glob patterns of module names to exclude, e.g. ``*::generated``.
+    :param cfg: The enabled configuration options, e.g. ``unix`` or ``feature="std"``.
+    :param crate_name_override: The name to use as the first path segment of all modules and items,
+        instead of the name of the crate's target, e.g. that of a facade crate that re-exports it.
+    :param strict: Fail if there are any warnings, e.g. for modules that cannot be found or parsed.
+    :param changed_files: Only emit the items declared in these files, e.g. ``src/net/socket.rs``,
+        for a partial rebuild; the whole crate is still analyzed to resolve paths and visibility.
+    :param preserve_symlinks: Do not resolve symlinks in the crate path,
+        so that module files are resolved within the (possibly symlinked) crate directory.
+    :param expand_type_aliases: Render the type aliases of the crate in types as their target types,
+        e.g. ``Vec<u8>`` for a field of type ``Bytes``, with ``type Bytes = Vec<u8>;``.
+    :param metadata_timeout: The seconds to wait for ``cargo metadata`` before it is killed and retried
+        (120 if ``None``).
+    :param warn_undocumented: Add a warning for each public module and item without a docstring.
+    :param warn_missing_safety: Add a warning for each public ``unsafe`` function, method or trait
+        without a ``# Safety`` section in its docstring.
+    :param include_examples: Also analyze the example targets of the crate, e.g. ``examples/demo.rs``.
+    :param include_tests: Also analyze the integration test targets of the crate, e.g. ``tests/api.rs``.
+    :param include_benches: Also analyze the benchmark targets of the crate, e.g. ``benches/parse.rs``.
+    :param preserve_signature_formatting: Render the signatures of functions as written in the source,
+        with their original formatting and comments, rather than re-serialized.
+    :param full_initializers: Also keep the initializers of constants and statics as written in the source,
+        rather than only a truncated preview.
+    :param hide_phantom_fields: Drop the ``PhantomData<...>`` marker fields of structs and enum variants,
+        rather than annotating them.
+    :param detect_recursive_types: Mark the structs and enums whose fields reference the type itself,
+        directly or through other local types, as ``recursive``.
+    :param warn_broken_examples: Add a warning for each Rust code example in the docstrings that does not parse.
+    :param external_docs: The base URLs of the documentation of external crates, by crate name,
+        e.g. ``{"serde": "https://docs.rs/serde/1.0.0/serde/"}``, to link the paths to their items in types.
+    :param link_docs_rs: Link the paths to items of external crates that are not in ``external_docs``
+        to docs.rs (or doc.rust-lang.org for the standard library), rather than leaving them unlinked.
     :raises IOError: If the analysis fails.
+    :raises TypeError: If an option is unknown, or its value has the wrong type.
+    :raises ValueError: If ``metadata_timeout`` is negative.
+    """
+
+def strip_hidden_lines(markdown: str, /) -> str:
+    """Remove the lines hidden with a leading ``# `` from the Rust code blocks of a Markdown docstring,
+    so that it renders as it would with rustdoc.
     """
 
 def load_crate(cache_path: str, name: str, /) -> Crate | None:
@@ -18,6 +83,14 @@ def load_crate(cache_path: str, name: str, /) -> Crate | None:
     :raises IOError: If the load fails.
     """
 
+def load_public_paths(cache_path: str, name: str, /) -> list[str] | None:
+    """Load the sorted public item paths of a crate from the cache, it they exist.
+
+    :param cache_path: The path to the cache directory.
+    :param name: The name of the crate.
+    :raises IOError: If the load fails.
+    """
+
 def load_module(cache_path: str, full_name: str, /) -> Module | None:
     """Load a module from the cache, it it exists.
 
@@ -117,6 +190,10 @@ class AnalysisResult:
     structs: list[str]
     enums: list[str]
     functions: list[str]
+    excluded_modules: list[str]
+    """The fully qualified names of modules excluded from the analysis."""
+    warnings: list[str]
+    """Non-fatal problems encountered during the analysis."""
 
 class Crate:
     """Representation of a crate."""
@@ -125,19 +202,58 @@ class Crate:
     """The name of the crate."""
     version: str
     """The version of the crate."""
+    description: str | None
+    """The description of the crate, from the manifest."""
+    authors: list[str]
+    """The authors of the crate, from the manifest."""
+    license: str | None
+    """The SPDX license expression of the crate, from the manifest."""
+    license_file: str | None
+    """The path of the license file of the crate, relative to its manifest, from the manifest."""
+    repository: str | None
+    """The repository URL of the crate, from the manifest."""
+    homepage: str | None
+    """The homepage URL of the crate, from the manifest."""
+    rust_version: str | None
+    """The minimum supported Rust version of the crate, from the manifest, e.g. ``1.70.0``."""
+    no_std: bool
+    """Whether the crate is ``#![no_std]``, for the configuration it was analyzed with."""
+    no_std_condition: str | None
+    """The condition under which the crate is ``#![no_std]``,
+    e.g. ``not(feature = "std")``, or ``None`` if it is unconditional."""
 
 class Module:
     """Representation of a module."""
 
     file: str | None
     """The absolute path to the file containing the module."""
+    line_count: int | None
+    """The number of lines in the module file."""
+    byte_size: int | None
+    """The size of the module file in bytes."""
     name: str
     """The name of the module."""
     path: list[str]
     """The fully qualified path"""
     path_str: str
     """The fully qualified name as a string, e.g. ``a::b::c``"""
+    anchor_id: str
+    """A stable, URL-safe identifier, e.g. for anchor links."""
     docstring: str
+    is_generated: bool
+    """Whether the module is likely generated, e.g. its file header has an ``@generated`` marker."""
+    target_kind: str | None
+    """The kind of cargo target the module belongs to (``example``, ``test`` or ``bench``),
+    if it is not part of the library or binary target of the crate."""
+    cfg_info: str | None
+    """The ``#[cfg(...)]`` predicate the module is available under, including those of its enclosing modules,
+    e.g. ``feature = "std"``, or ``None`` if it is unconditional."""
+    cfg_variants: list[str]
+    """The ``#[cfg(...)]`` predicates of all alternative declarations of the module,
+    if it is declared more than once, e.g. ``["unix", "windows"]``,
+    in which case only the active one is documented."""
+    toc: list[Heading]
+    """The headings of the docstring, in order, e.g. for a table of contents."""
 
 class Struct:
     """Representation of a struct."""
@@ -148,8 +264,24 @@ class Struct:
     """The fully qualified path"""
     path_str: str
     """The fully qualified name as a string, e.g. ``a::b::c``"""
+    anchor_id: str
+    """A stable, URL-safe identifier, e.g. for anchor links."""
     docstring: str
+    doc_aliases: list[str]
+    """The search aliases from ``#[doc(alias = "...")]`` attributes."""
+    fields_kind: Literal["unit", "tuple", "struct"]
+    """Whether the struct has no fields, positional fields or named fields."""
     fields: list[Field]
+    deref_target: list[TypeSegment] | None
+    """The ``Target`` of the ``Deref`` impl of the struct, if any."""
+    recursive: bool
+    """Whether the fields of the struct reference the struct itself, e.g. ``next: Option<Box<Node>>``,
+    if the analysis detected recursive types."""
+    cfg_info: str | None
+    """The ``#[cfg(...)]`` predicate the struct is available under, including those of its enclosing modules,
+    e.g. ``feature = "std"``, or ``None`` if it is unconditional."""
+    toc: list[Heading]
+    """The headings of the docstring, in order, e.g. for a table of contents."""
 
 class Enum:
     """Representation of an enum."""
@@ -160,8 +292,24 @@ class Enum:
     """The fully qualified path"""
     path_str: str
     """The fully qualified name as a string, e.g. ``a::b::c``"""
+    anchor_id: str
+    """A stable, URL-safe identifier, e.g. for anchor links."""
     docstring: str
+    doc_aliases: list[str]
+    """The search aliases from ``#[doc(alias = "...")]`` attributes."""
     variants: list[Variant]
+    is_c_like: bool
+    """Whether the enum has variants, all without fields, e.g. ``enum Color { Red = 1, Green = 2 }``."""
+    deref_target: list[TypeSegment] | None
+    """The ``Target`` of the ``Deref`` impl of the enum, if any."""
+    recursive: bool
+    """Whether the fields of the variants reference the enum itself, e.g. ``Neg(Box<Expr>)``,
+    if the analysis detected recursive types."""
+    cfg_info: str | None
+    """The ``#[cfg(...)]`` predicate the enum is available under, including those of its enclosing modules,
+    e.g. ``feature = "std"``, or ``None`` if it is unconditional."""
+    toc: list[Heading]
+    """The headings of the docstring, in order, e.g. for a table of contents."""
 
 class Variant:
     """Representation of an enum variant."""
@@ -173,7 +321,13 @@ class Variant:
     path_str: str
     """The fully qualified name as a string, e.g. ``a::b::c``"""
     docstring: str
+    fields_kind: Literal["unit", "tuple", "struct"]
+    """Whether the variant has no fields, positional fields or named fields."""
     fields: list[Field]
+    serde_rename: str | None
+    """The name the variant is serialized as, from ``#[serde(rename = "...")]``."""
+    serde_arguments: list[str]
+    """The arguments of the ``#[serde(...)]`` attributes, as written, e.g. ``skip``."""
 
 class Field:
     """Representation of a struct field."""
@@ -189,6 +343,24 @@ class Field:
     """The fully qualified name as a string, e.g. ``a::b::c``"""
     docstring: str
     type_: list[TypeSegment]
+    public: bool
+    """Whether the field is public; private fields are only present if private items are included."""
+    serde_rename: str | None
+    """The name the field is serialized as, from ``#[serde(rename = "...")]``."""
+    serde_arguments: list[str]
+    """The arguments of the ``#[serde(...)]`` attributes, as written, e.g. ``default``."""
+    phantom: bool
+    """Whether the field is a ``PhantomData<...>`` marker, which holds no data."""
+
+class Heading:
+    """Representation of a docstring heading, as an entry of a table of contents."""
+
+    level: int
+    """The heading level, from 1 (``#``) to 6 (``######``)."""
+    text: str
+    """The text of the heading, as written."""
+    slug: str
+    """The anchor of the heading, unique within the docstring, e.g. ``error-handling`` for ``# Error handling``."""
 
 class TypeSegment:
     """Representation of a segment of a type.
@@ -198,6 +370,8 @@ class TypeSegment:
 
     content: str
     is_path: bool
+    url: str | None
+    """The documentation URL of a path to an item of an external crate, if it is linked."""
 
 class Function:
     """Representation of a function."""
@@ -208,4 +382,26 @@ class Function:
     """The fully qualified path"""
     path_str: str
     """The fully qualified name as a string, e.g. ``a::b::c``"""
+    anchor_id: str
+    """A stable, URL-safe identifier, e.g. for anchor links."""
     docstring: str
+    doc_aliases: list[str]
+    """The search aliases from ``#[doc(alias = "...")]`` attributes."""
+    signature: list[TypeSegment]
+    """The rendered signature, e.g. ``fn name<T>(a: T) -> u8``,
+    or the signature as written in the source, if the analysis preserved it."""
+    resolved_signature: list[TypeSegment]
+    """The rendered signature, with ``Self`` substituted by the self type of the impl for methods of impls,
+    e.g. ``fn new() -> Wrapper<T>`` in ``impl<T> Wrapper<T>``."""
+    function_kind: Literal["free", "associated_function", "method"]
+    """Whether the function is a free function, or, of an impl or trait,
+    an associated function without a ``self`` receiver, or a method."""
+    generic_params: list[str]
+    """The names of the generic parameters, e.g. ``'a`` and ``T``."""
+    used_generic_params: list[str]
+    """The names of the generic parameters referenced in the parameters or return type."""
+    cfg_info: str | None
+    """The ``#[cfg(...)]`` predicate the function is available under, including those of its enclosing modules,
+    e.g. ``feature = "std"``, or ``None`` if it is unconditional."""
+    toc: list[Heading]
+    """The headings of the docstring, in order, e.g. for a table of contents."""